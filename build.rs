@@ -9,8 +9,21 @@ use std::path::Path;
 mod static_unicase;
 
 #[path = "src/attr/attr_type.rs"]
+#[allow(dead_code)]
 mod attr_type;
 
+#[path = "src/attr/idl_type.rs"]
+#[allow(dead_code)]
+mod idl_type;
+
+#[path = "src/tag/tag_type.rs"]
+#[allow(dead_code)]
+mod tag_type;
+
+#[path = "src/status.rs"]
+#[allow(dead_code)]
+mod status;
+
 #[path = "src/defs/ns_defs.rs"]
 mod ns_defs;
 
@@ -20,6 +33,9 @@ mod html5_defs;
 #[path = "src/defs/svg_defs.rs"]
 mod svg_defs;
 
+#[path = "src/defs/entity_defs.rs"]
+mod entity_defs;
+
 use static_unicase::StaticUniCase;
 
 fn main() {
@@ -41,6 +57,30 @@ impl Def {
         }
     }
 
+    fn tag_flags(&self) -> tag_type::Flags {
+        match &self.kind {
+            DefKind::Static(kind) if self.target_ns != &ns_defs::HTML5 => {
+                kind.tag_flags | tag_type::flags::FOREIGN
+            }
+            DefKind::Static(kind) => kind.tag_flags,
+            DefKind::DataAttr => 0,
+        }
+    }
+
+    ///
+    /// The DOM `tagName`: HTML elements in HTML documents are uppercased.
+    /// `<svg>` and `<math>` are foreign even when defined in the HTML namespace.
+    ///
+    fn tag_name(&self) -> String {
+        match &self.kind {
+            DefKind::Static(kind) if kind.interface.starts_with("HTML") => {
+                kind.local_name.to_ascii_uppercase()
+            }
+            DefKind::Static(kind) => kind.local_name.to_string(),
+            DefKind::DataAttr => String::new(),
+        }
+    }
+
     fn entity_kind(&self) -> EntityKind {
         match &self.kind {
             DefKind::Static(kind) => kind.entity_kind.clone(),
@@ -63,7 +103,11 @@ struct StaticDefKind {
     local_name: &'static str,
     prop: &'static str,
     flags: u32,
-    is_void: bool,
+    tag_flags: tag_type::Flags,
+    interface: &'static str,
+    idl_type: Option<idl_type::IdlType>,
+    status: status::Status,
+    replacement: Option<&'static str>,
 }
 
 #[derive(Clone, Eq, PartialEq)]
@@ -89,7 +133,7 @@ fn codegen() -> std::io::Result<()> {
             def.src_ns == &ns_defs::HTML5 && def.entity_kind() == EntityKind::Tag
         });
         let mut w = BufWriter::new(File::create(
-            &Path::new(&out_dir).join("codegen_html_tag_enum.rs"),
+            Path::new(&out_dir).join("codegen_html_tag_enum.rs"),
         )?);
         let f = &mut w;
 
@@ -105,7 +149,7 @@ fn codegen() -> std::io::Result<()> {
             def.src_ns == &ns_defs::HTML5 && def.entity_kind() == EntityKind::Attribute
         });
         let mut w = BufWriter::new(File::create(
-            &Path::new(&out_dir).join("codegen_html_attr_enum.rs"),
+            Path::new(&out_dir).join("codegen_html_attr_enum.rs"),
         )?);
         let f = &mut w;
 
@@ -123,7 +167,7 @@ fn codegen() -> std::io::Result<()> {
             def.src_ns == &ns_defs::SVG && def.entity_kind() == EntityKind::Tag
         });
         let mut w = BufWriter::new(File::create(
-            &Path::new(&out_dir).join("codegen_svg_tag_enum.rs"),
+            Path::new(&out_dir).join("codegen_svg_tag_enum.rs"),
         )?);
         let f = &mut w;
 
@@ -139,7 +183,7 @@ fn codegen() -> std::io::Result<()> {
             def.src_ns == &ns_defs::SVG && def.entity_kind() == EntityKind::Attribute
         });
         let mut w = BufWriter::new(File::create(
-            &Path::new(&out_dir).join("codegen_svg_attr_enum.rs"),
+            Path::new(&out_dir).join("codegen_svg_attr_enum.rs"),
         )?);
         let f = &mut w;
 
@@ -152,6 +196,33 @@ fn codegen() -> std::io::Result<()> {
         enums::codegen_property_lookup(enum_ident, &attrs, f)?;
     }
 
+    {
+        let mut w = BufWriter::new(File::create(
+            Path::new(&out_dir).join("codegen_entities.rs"),
+        )?);
+        let f = &mut w;
+
+        let mut map_codegen: phf_codegen::Map<&str> = phf_codegen::Map::new();
+        for (name, characters) in entity_defs::DEFS {
+            map_codegen.entry(name, &format!("{:?}", characters));
+        }
+
+        writeln!(
+            f,
+            "pub(crate) const LONGEST_NAME: usize = {};",
+            entity_defs::DEFS
+                .iter()
+                .map(|(name, _)| name.len())
+                .max()
+                .unwrap()
+        )?;
+        writeln!(
+            f,
+            "pub(crate) const NAMED_CHARACTER_REFERENCES: phf::Map<&str, &str> = {};",
+            map_codegen.build()
+        )?;
+    }
+
     Ok(())
 }
 
@@ -162,7 +233,67 @@ fn filtered_defs(filter_fn: impl Fn(&&Def) -> bool) -> Vec<Def> {
 fn defs() -> Vec<Def> {
     let mut defs = vec![];
 
+    assert_known_names(
+        html5_defs::tag_types::DEFS
+            .iter()
+            .flat_map(|(_, tags)| tags.iter()),
+        html5_defs::tags::DEFS.iter().map(|(tag, _, _)| *tag),
+    );
+    assert_known_names(
+        svg_defs::tag_types::DEFS
+            .iter()
+            .flat_map(|(_, tags)| tags.iter()),
+        svg_defs::tags::DEFS.iter().copied(),
+    );
+    assert_known_names(
+        html5_defs::interfaces::DEFS
+            .iter()
+            .flat_map(|(_, tags)| tags.iter()),
+        html5_defs::tags::DEFS.iter().map(|(tag, _, _)| *tag),
+    );
+    assert_known_names(
+        svg_defs::interfaces::DEFS
+            .iter()
+            .flat_map(|(_, tags)| tags.iter()),
+        svg_defs::tags::DEFS.iter().copied(),
+    );
+    assert_known_names(
+        html5_defs::idl_types::DEFS
+            .iter()
+            .flat_map(|(_, attrs)| attrs.iter()),
+        html5_defs::attrs::DEFS.iter().map(|(attr, _, _)| *attr),
+    );
+    assert_known_names(
+        svg_defs::idl_types::DEFS
+            .iter()
+            .flat_map(|(_, attrs)| attrs.iter()),
+        svg_defs::attrs::DEFS.iter().map(|(attr, _, _)| *attr),
+    );
+
+    assert_known_names(
+        html5_defs::statuses::TAGS.iter().map(|(tag, _, _)| tag),
+        html5_defs::tags::DEFS.iter().map(|(tag, _, _)| *tag),
+    );
+    assert_known_names(
+        html5_defs::statuses::ATTRS.iter().map(|(attr, _, _)| attr),
+        html5_defs::attrs::DEFS.iter().map(|(attr, _, _)| *attr),
+    );
+    assert_known_names(
+        svg_defs::statuses::TAGS.iter().map(|(tag, _, _)| tag),
+        svg_defs::tags::DEFS.iter().copied(),
+    );
+    assert_known_names(
+        svg_defs::statuses::ATTRS.iter().map(|(attr, _, _)| attr),
+        svg_defs::attrs::DEFS.iter().map(|(attr, _, _)| *attr),
+    );
+
     for (tag, is_void, target_ns) in html5_defs::tags::DEFS {
+        let mut tag_flags = tag_flags_from_defs(tag, html5_defs::tag_types::DEFS);
+        if is_void.0 {
+            tag_flags |= tag_type::flags::VOID;
+        }
+
+        let (status, replacement) = status_from_defs(tag, html5_defs::statuses::TAGS);
         defs.push(Def {
             src_ns: &ns_defs::HTML5,
             target_ns,
@@ -173,12 +304,17 @@ fn defs() -> Vec<Def> {
                 local_name: tag,
                 prop: "",
                 flags: 0,
-                is_void: is_void.0,
+                tag_flags,
+                interface: interface_from_defs(tag, html5_defs::interfaces::DEFS),
+                idl_type: None,
+                status,
+                replacement,
             }),
         });
     }
 
     for (attr, prop, flags) in html5_defs::attrs::DEFS {
+        let (status, replacement) = status_from_defs(attr, html5_defs::statuses::ATTRS);
         defs.push(Def {
             src_ns: &ns_defs::HTML5,
             target_ns: &ns_defs::HTML5,
@@ -189,7 +325,16 @@ fn defs() -> Vec<Def> {
                 local_name: attr,
                 prop,
                 flags: *flags,
-                is_void: false,
+                tag_flags: 0,
+                interface: "",
+                idl_type: idl_type_from_defs(
+                    attr,
+                    *flags,
+                    html5_defs::idl_types::DEFAULT,
+                    html5_defs::idl_types::DEFS,
+                ),
+                status,
+                replacement,
             }),
         });
     }
@@ -201,6 +346,7 @@ fn defs() -> Vec<Def> {
     });
 
     for tag in svg_defs::tags::DEFS {
+        let (status, replacement) = status_from_defs(tag, svg_defs::statuses::TAGS);
         defs.push(Def {
             src_ns: &ns_defs::SVG,
             target_ns: &ns_defs::SVG,
@@ -211,12 +357,17 @@ fn defs() -> Vec<Def> {
                 local_name: tag,
                 prop: "",
                 flags: 0,
-                is_void: false,
+                tag_flags: tag_flags_from_defs(tag, svg_defs::tag_types::DEFS),
+                interface: interface_from_defs(tag, svg_defs::interfaces::DEFS),
+                idl_type: None,
+                status,
+                replacement,
             }),
         });
     }
 
     for (attr, prop, flags) in svg_defs::attrs::DEFS {
+        let (status, replacement) = status_from_defs(attr, svg_defs::statuses::ATTRS);
         defs.push(Def {
             src_ns: &ns_defs::SVG,
             target_ns: &ns_defs::SVG,
@@ -227,7 +378,16 @@ fn defs() -> Vec<Def> {
                 local_name: attr,
                 prop,
                 flags: *flags,
-                is_void: false,
+                tag_flags: 0,
+                interface: "",
+                idl_type: idl_type_from_defs(
+                    attr,
+                    *flags,
+                    svg_defs::idl_types::DEFAULT,
+                    svg_defs::idl_types::DEFS,
+                ),
+                status,
+                replacement,
             }),
         });
     }
@@ -235,6 +395,70 @@ fn defs() -> Vec<Def> {
     defs
 }
 
+fn assert_known_names<'a>(
+    names: impl Iterator<Item = &'a &'a str>,
+    known: impl Iterator<Item = &'a str> + Clone,
+) {
+    for name in names {
+        if !known.clone().any(|known| known == *name) {
+            panic!("'{}' is not defined", name);
+        }
+    }
+}
+
+fn tag_flags_from_defs(tag: &str, defs: &[(tag_type::Flags, &[&str])]) -> tag_type::Flags {
+    defs.iter()
+        .filter(|(_, tags)| tags.contains(&tag))
+        .fold(0, |acc, (flags, _)| acc | flags)
+}
+
+fn interface_from_defs(tag: &str, defs: &[(&'static str, &[&str])]) -> &'static str {
+    let mut interfaces = defs
+        .iter()
+        .filter(|(_, tags)| tags.contains(&tag))
+        .map(|(interface, _)| *interface);
+
+    match (interfaces.next(), interfaces.next()) {
+        (Some(interface), None) => interface,
+        (None, _) => panic!("'{}' has no interface", tag),
+        (Some(_), Some(_)) => panic!("'{}' has more than one interface", tag),
+    }
+}
+
+///
+/// The IDL type of an attribute: explicitly listed, or else `boolean` for boolean
+/// attributes, `EventHandler` for event handler attributes and the default otherwise.
+///
+fn idl_type_from_defs(
+    attr: &str,
+    flags: attr_type::Flags,
+    default: Option<idl_type::IdlType>,
+    defs: &[(Option<idl_type::IdlType>, &[&str])],
+) -> Option<idl_type::IdlType> {
+    if let Some((idl_type, _)) = defs.iter().find(|(_, attrs)| attrs.contains(&attr)) {
+        *idl_type
+    } else if attr_type::AttrType(flags).is_only_bool() {
+        Some(idl_type::IdlType::Boolean)
+    } else if attr.starts_with("on") {
+        Some(idl_type::IdlType::EventHandler)
+    } else {
+        default
+    }
+}
+
+///
+/// The status of a tag or attribute and its replacement hint, `Current` when not listed.
+///
+fn status_from_defs(
+    name: &str,
+    defs: &[(&str, status::Status, Option<&'static str>)],
+) -> (status::Status, Option<&'static str>) {
+    defs.iter()
+        .find(|(listed, _, _)| *listed == name)
+        .map(|(_, status, replacement)| (*status, *replacement))
+        .unwrap_or((status::Status::Current, None))
+}
+
 fn make_const_ident(input: &str) -> String {
     input.replace('-', "_").to_uppercase()
 }
@@ -370,6 +594,25 @@ mod enums {
                 }),
             )?;
             writeln!(f, "    }}")?;
+            writeln!(
+                f,
+                "    fn idl_type(&self) -> Option<crate::attr::idl_type::IdlType> {{"
+            )?;
+            writeln!(f, "        use crate::attr::idl_type::IdlType;")?;
+            codegen_enum_match_self(
+                f,
+                defs.iter().map(|def| match &def.kind {
+                    DefKind::Static(static_kind) => (
+                        static_kind.variant_ident.as_ref(),
+                        match static_kind.idl_type {
+                            Some(idl_type) => format!("Some(IdlType::{:?})", idl_type),
+                            None => "None".to_string(),
+                        },
+                    ),
+                    DefKind::DataAttr => ("Dataset(data)", "data.idl_type()".to_string()),
+                }),
+            )?;
+            writeln!(f, "    }}")?;
             writeln!(f, "}}")?;
         }
 
@@ -391,6 +634,98 @@ mod enums {
             writeln!(f, "}}")?;
         }
 
+        // Tag
+        if entity_kind == EntityKind::Tag {
+            writeln!(f, "impl crate::tag::Tag for {} {{", enum_ident)?;
+            writeln!(
+                f,
+                "    fn tag_type(&self) -> crate::tag::tag_type::TagType {{"
+            )?;
+            writeln!(f, "        use crate::tag::tag_type::TagType;")?;
+            codegen_enum_match_self(
+                f,
+                defs.iter().filter_map(|def| match &def.kind {
+                    DefKind::Static(static_kind) => Some((
+                        static_kind.variant_ident.as_ref(),
+                        format!("TagType({:#x})", def.tag_flags()),
+                    )),
+                    DefKind::DataAttr => None,
+                }),
+            )?;
+            writeln!(f, "    }}")?;
+            writeln!(f, "}}")?;
+        }
+
+        // InterfaceName
+        if entity_kind == EntityKind::Tag {
+            writeln!(f, "impl crate::InterfaceName for {} {{", enum_ident)?;
+            writeln!(f, "    fn interface_name(&self) -> &str {{")?;
+            codegen_enum_match_self(
+                f,
+                defs.iter().filter_map(|def| match &def.kind {
+                    DefKind::Static(static_kind) => Some((
+                        static_kind.variant_ident.as_ref(),
+                        format!("\"{}\"", static_kind.interface),
+                    )),
+                    DefKind::DataAttr => None,
+                }),
+            )?;
+            writeln!(f, "    }}")?;
+            writeln!(f, "}}")?;
+        }
+
+        // TagName
+        if entity_kind == EntityKind::Tag {
+            writeln!(f, "impl crate::TagName for {} {{", enum_ident)?;
+            writeln!(f, "    fn tag_name(&self) -> &str {{")?;
+            codegen_enum_match_self(
+                f,
+                defs.iter().filter_map(|def| match &def.kind {
+                    DefKind::Static(static_kind) => Some((
+                        static_kind.variant_ident.as_ref(),
+                        format!("\"{}\"", def.tag_name()),
+                    )),
+                    DefKind::DataAttr => None,
+                }),
+            )?;
+            writeln!(f, "    }}")?;
+            writeln!(f, "}}")?;
+        }
+
+        // Conformance
+        {
+            writeln!(f, "impl crate::Conformance for {} {{", enum_ident)?;
+            writeln!(f, "    fn status(&self) -> crate::status::Status {{")?;
+            writeln!(f, "        use crate::status::Status;")?;
+            codegen_enum_match_self(
+                f,
+                defs.iter().map(|def| match &def.kind {
+                    DefKind::Static(static_kind) => (
+                        static_kind.variant_ident.as_ref(),
+                        format!("Status::{:?}", static_kind.status),
+                    ),
+                    DefKind::DataAttr => ("Dataset(_)", "Status::Current".to_string()),
+                }),
+            )?;
+            writeln!(f, "    }}")?;
+            writeln!(
+                f,
+                "    fn replacement_hint(&self) -> Option<&'static str> {{"
+            )?;
+            codegen_enum_match_self(
+                f,
+                defs.iter().map(|def| match &def.kind {
+                    DefKind::Static(static_kind) => (
+                        static_kind.variant_ident.as_ref(),
+                        format!("{:?}", static_kind.replacement),
+                    ),
+                    DefKind::DataAttr => ("Dataset(_)", "None".to_string()),
+                }),
+            )?;
+            writeln!(f, "    }}")?;
+            writeln!(f, "}}")?;
+        }
+
         // IsVoid
         if entity_kind == EntityKind::Tag {
             writeln!(f, "impl crate::IsVoid for {} {{", enum_ident)?;
@@ -400,7 +735,7 @@ mod enums {
                 defs.iter().filter_map(|def| match &def.kind {
                     DefKind::Static(static_kind) => Some((
                         static_kind.variant_ident.as_ref(),
-                        format!("{}", static_kind.is_void),
+                        format!("{}", static_kind.tag_flags & tag_type::flags::VOID > 0),
                     )),
                     DefKind::DataAttr => None,
                 }),
@@ -529,7 +864,7 @@ fn codegen_enum_match_self<'a, W: Write>(
     let mut by_expr: BTreeMap<String, Vec<&'a str>> = BTreeMap::new();
 
     for (variant, expr) in iterator {
-        by_expr.entry(expr).or_insert_with(Vec::new).push(variant);
+        by_expr.entry(expr).or_default().push(variant);
     }
 
     writeln!(f, "        match self {{")?;
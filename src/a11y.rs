@@ -0,0 +1,542 @@
+//!
+//! Accessibility lint rules over typed elements.
+//!
+//! A [Linter] runs [Rule]s keyed on tags and attributes over a tree of nodes,
+//! and reports a [Diagnostic] with a suggested fix for every violation.
+//!
+//! See: https://www.w3.org/TR/WCAG22/
+//!
+
+use std::collections::{HashMap, HashSet};
+
+use crate::attr::AttributeValue;
+use crate::dom::{ElementNode, Node};
+use crate::element::Element;
+use crate::html5::{HtmlAttr, HtmlTag};
+use crate::svg::{SvgAttr, SvgTag};
+use crate::web;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+///
+/// What a rule is keyed on: elements with a tag, or elements with an attribute.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Target {
+    Tag(web::Tag),
+    Attr(web::Attr),
+}
+
+///
+/// A rule, checked on every element matching one of its targets.
+///
+/// `check` returns `true` when the element violates the rule.
+///
+#[derive(Clone, Debug)]
+pub struct Rule {
+    pub id: &'static str,
+    pub severity: Severity,
+    pub targets: Vec<Target>,
+    pub message: &'static str,
+    pub fix: &'static str,
+    pub check: fn(&ElementNode, &Context) -> bool,
+}
+
+///
+/// A violation of a rule by an element.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub tag: web::Tag,
+    pub message: &'static str,
+    pub fix: &'static str,
+}
+
+///
+/// What rules can know about the rest of the tree.
+///
+pub struct Context<'n> {
+    label_targets: HashSet<&'n str>,
+    accesskeys: HashMap<String, usize>,
+    ancestors: Vec<&'n Element>,
+}
+
+impl<'n> Context<'n> {
+    fn new(nodes: &'n [Node]) -> Self {
+        let mut context = Self {
+            label_targets: HashSet::new(),
+            accesskeys: HashMap::new(),
+            ancestors: vec![],
+        };
+        context.collect(nodes);
+        context
+    }
+
+    fn collect(&mut self, nodes: &'n [Node]) {
+        for node in nodes {
+            if let Node::Element(ElementNode { element, children }) = node {
+                if element.tag() == &web::Tag::Html5(HtmlTag::Label) {
+                    if let Some(id) = element.attr_str(&web::Attr::Html5(HtmlAttr::For)) {
+                        self.label_targets.insert(id);
+                    }
+                }
+                for key in element.attr_tokens(&web::Attr::Html5(HtmlAttr::Accesskey)) {
+                    *self.accesskeys.entry(key.to_lowercase()).or_insert(0) += 1;
+                }
+                self.collect(children);
+            }
+        }
+    }
+
+    ///
+    /// The ancestors of the element being checked, outermost first.
+    ///
+    pub fn ancestors(&self) -> &[&'n Element] {
+        &self.ancestors
+    }
+
+    ///
+    /// Whether a `<label for>` in the tree refers to `id`.
+    ///
+    pub fn is_label_target(&self, id: &str) -> bool {
+        self.label_targets.contains(id)
+    }
+
+    ///
+    /// The number of elements in the tree using the case-insensitive access key `key`.
+    ///
+    pub fn accesskey_count(&self, key: &str) -> usize {
+        self.accesskeys
+            .get(&key.to_lowercase())
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+///
+/// A set of rules.
+///
+/// Example:
+/// ```
+/// use web_ns::a11y::{Linter, Severity};
+/// use web_ns::dom::parse_fragment;
+///
+/// let nodes = parse_fragment(r#"<img src=a.png><input id=q><div tabindex=2>x</div>"#).nodes;
+/// let linter = Linter::recommended().severity("tabindex-positive", Severity::Error);
+///
+/// let diagnostics = linter.check(&nodes);
+/// assert_eq!(
+///     diagnostics.iter().map(|d| (d.rule, d.severity)).collect::<Vec<_>>(),
+///     vec![
+///         ("img-alt", Severity::Error),
+///         ("control-label", Severity::Error),
+///         ("tabindex-positive", Severity::Error),
+///     ]
+/// );
+/// assert_eq!(diagnostics[1].fix, "Add a `<label for>`, wrap the control in a `<label>`, or add `aria-label`");
+///
+/// assert!(Linter::recommended().disable("img-alt").check(&parse_fragment("<img>").nodes).is_empty());
+/// ```
+///
+#[derive(Clone, Debug, Default)]
+pub struct Linter {
+    rules: Vec<Rule>,
+}
+
+impl Linter {
+    ///
+    /// A linter without rules.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// A linter with all built-in rules:
+    ///
+    /// | Rule | Severity |
+    /// |------|----------|
+    /// | `img-alt`: `<img>` without `alt` | Error |
+    /// | `svg-img-alt`: `<svg>` without `role="img"` and a `<title>` or `aria-label`, unless `aria-hidden` | Error |
+    /// | `control-label`: `<input>`, `<select>` or `<textarea>` without a label | Error |
+    /// | `tabindex-positive`: `tabindex` above zero | Warning |
+    /// | `no-autofocus`: `autofocus` | Warning |
+    /// | `accesskey-unique`: an `accesskey` used by more than one element | Error |
+    /// | `anchor-is-button`: `<a>` without `href`, with `onclick` or `role="button"` | Warning |
+    /// | `html-lang`: `<html>` without `lang` | Error |
+    ///
+    pub fn recommended() -> Self {
+        let html_tag = |tag: HtmlTag| Target::Tag(web::Tag::Html5(tag));
+        let html_attr = |attr: HtmlAttr| Target::Attr(web::Attr::Html5(attr));
+
+        Self::new()
+            .rule(Rule {
+                id: "img-alt",
+                severity: Severity::Error,
+                targets: vec![html_tag(HtmlTag::Img)],
+                message: "Image without alternative text",
+                fix: "Add an `alt` attribute, empty for decorative images",
+                check: |node, _| !node.element.has_attr(&web::Attr::Html5(HtmlAttr::Alt)),
+            })
+            .rule(Rule {
+                id: "svg-img-alt",
+                severity: Severity::Error,
+                targets: vec![Target::Tag(web::Tag::Svg(SvgTag::Svg))],
+                message: "SVG image without role and accessible name",
+                fix: "Add `role=\"img\"` and a `<title>`, or `aria-hidden=\"true\"` if decorative",
+                check: check_svg,
+            })
+            .rule(Rule {
+                id: "control-label",
+                severity: Severity::Error,
+                targets: vec![
+                    html_tag(HtmlTag::Input),
+                    html_tag(HtmlTag::Select),
+                    html_tag(HtmlTag::Textarea),
+                ],
+                message: "Form control without a label",
+                fix: "Add a `<label for>`, wrap the control in a `<label>`, or add `aria-label`",
+                check: check_control_label,
+            })
+            .rule(Rule {
+                id: "tabindex-positive",
+                severity: Severity::Warning,
+                targets: vec![
+                    html_attr(HtmlAttr::Tabindex),
+                    Target::Attr(web::Attr::Svg(SvgAttr::Tabindex)),
+                ],
+                message: "Positive tabindex changes the focus order",
+                fix: "Use `tabindex=\"0\"` and order the elements in the document instead",
+                check: check_tabindex,
+            })
+            .rule(Rule {
+                id: "no-autofocus",
+                severity: Severity::Warning,
+                targets: vec![html_attr(HtmlAttr::Autofocus)],
+                message: "Autofocus moves focus without user action",
+                fix: "Remove `autofocus`",
+                check: |_, _| true,
+            })
+            .rule(Rule {
+                id: "accesskey-unique",
+                severity: Severity::Error,
+                targets: vec![html_attr(HtmlAttr::Accesskey)],
+                message: "Access key used by more than one element",
+                fix: "Use a different `accesskey` for every element",
+                check: |node, context| {
+                    node.element
+                        .attr_tokens(&web::Attr::Html5(HtmlAttr::Accesskey))
+                        .iter()
+                        .any(|key| context.accesskey_count(key) > 1)
+                },
+            })
+            .rule(Rule {
+                id: "anchor-is-button",
+                severity: Severity::Warning,
+                targets: vec![html_tag(HtmlTag::A)],
+                message: "Link without `href` used as a button",
+                fix: "Use a `<button>`, or add an `href` if it navigates",
+                check: check_anchor,
+            })
+            .rule(Rule {
+                id: "html-lang",
+                severity: Severity::Error,
+                targets: vec![html_tag(HtmlTag::Html)],
+                message: "Document without a language",
+                fix: "Add a `lang` attribute, e.g. `lang=\"en\"`",
+                check: |node, _| !has_value(&node.element, &web::Attr::Html5(HtmlAttr::Lang)),
+            })
+    }
+
+    ///
+    /// Add a rule, replacing a rule with the same id.
+    ///
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rules.retain(|existing| existing.id != rule.id);
+        self.rules.push(rule);
+        self
+    }
+
+    ///
+    /// Change the severity of the rule `id`.
+    ///
+    pub fn severity(mut self, id: &str, severity: Severity) -> Self {
+        for rule in self.rules.iter_mut().filter(|rule| rule.id == id) {
+            rule.severity = severity;
+        }
+        self
+    }
+
+    ///
+    /// Remove the rule `id`.
+    ///
+    pub fn disable(mut self, id: &str) -> Self {
+        self.rules.retain(|rule| rule.id != id);
+        self
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    ///
+    /// Check a tree of nodes, in document order, and in rule order for every element.
+    ///
+    pub fn check(&self, nodes: &[Node]) -> Vec<Diagnostic> {
+        let mut by_tag: HashMap<&web::Tag, Vec<usize>> = HashMap::new();
+        let mut by_attr: HashMap<&web::Attr, Vec<usize>> = HashMap::new();
+        for (index, rule) in self.rules.iter().enumerate() {
+            for target in &rule.targets {
+                match target {
+                    Target::Tag(tag) => by_tag.entry(tag).or_default().push(index),
+                    Target::Attr(attr) => by_attr.entry(attr).or_default().push(index),
+                }
+            }
+        }
+
+        let mut context = Context::new(nodes);
+        let mut diagnostics = vec![];
+        self.check_nodes(nodes, &by_tag, &by_attr, &mut context, &mut diagnostics);
+        diagnostics
+    }
+
+    fn check_nodes<'n>(
+        &self,
+        nodes: &'n [Node],
+        by_tag: &HashMap<&web::Tag, Vec<usize>>,
+        by_attr: &HashMap<&web::Attr, Vec<usize>>,
+        context: &mut Context<'n>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        for node in nodes {
+            if let Node::Element(element_node) = node {
+                let element = &element_node.element;
+                let mut indices: Vec<usize> = by_tag
+                    .get(element.tag())
+                    .into_iter()
+                    .flatten()
+                    .chain(
+                        element
+                            .attrs()
+                            .iter()
+                            .filter_map(|(attr, _)| by_attr.get(attr))
+                            .flatten(),
+                    )
+                    .copied()
+                    .collect();
+                indices.sort_unstable();
+                indices.dedup();
+
+                for index in indices {
+                    let rule = &self.rules[index];
+                    if (rule.check)(element_node, context) {
+                        diagnostics.push(Diagnostic {
+                            rule: rule.id,
+                            severity: rule.severity,
+                            tag: element.tag().clone(),
+                            message: rule.message,
+                            fix: rule.fix,
+                        });
+                    }
+                }
+
+                context.ancestors.push(element);
+                self.check_nodes(
+                    &element_node.children,
+                    by_tag,
+                    by_attr,
+                    context,
+                    diagnostics,
+                );
+                context.ancestors.pop();
+            }
+        }
+    }
+}
+
+fn has_value(element: &Element, attr: &web::Attr) -> bool {
+    element.attr(attr) == Some(&AttributeValue::True) || !element.attr_tokens(attr).is_empty()
+}
+
+fn check_svg(node: &ElementNode, _: &Context) -> bool {
+    let element = &node.element;
+    // `true` is parsed as a boolean
+    let hidden = element.attr(&web::Attr::Svg(SvgAttr::AriaHidden)) == Some(&AttributeValue::True);
+    let is_img = element
+        .attr_tokens(&web::Attr::Svg(SvgAttr::Role))
+        .contains(&"img");
+    let has_title = node.children.iter().any(|child| match child {
+        Node::Element(ElementNode { element, children }) => {
+            element.tag() == &web::Tag::Svg(SvgTag::Title)
+                && children
+                    .iter()
+                    .any(|child| matches!(child, Node::Text(text) if !text.trim().is_empty()))
+        }
+        _ => false,
+    });
+    let has_name = has_title
+        || has_value(element, &web::Attr::Svg(SvgAttr::AriaLabel))
+        || has_value(element, &web::Attr::Svg(SvgAttr::AriaLabelledby));
+
+    !(hidden || is_img && has_name)
+}
+
+fn check_control_label(node: &ElementNode, context: &Context) -> bool {
+    let element = &node.element;
+    let input_type = element
+        .attr_str(&web::Attr::Html5(HtmlAttr::Type))
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    // Buttons are labelled by their value, image buttons are covered by `alt`
+    if matches!(
+        input_type.as_str(),
+        "hidden" | "submit" | "reset" | "button" | "image"
+    ) {
+        return false;
+    }
+
+    let has_label = [
+        HtmlAttr::AriaLabel,
+        HtmlAttr::AriaLabelledby,
+        HtmlAttr::Title,
+    ]
+    .iter()
+    .any(|attr| has_value(element, &web::Attr::Html5(attr.clone())))
+        || element.id().is_some_and(|id| context.is_label_target(id))
+        || context
+            .ancestors()
+            .iter()
+            .any(|ancestor| ancestor.tag() == &web::Tag::Html5(HtmlTag::Label));
+
+    !has_label
+}
+
+fn check_tabindex(node: &ElementNode, _: &Context) -> bool {
+    let tabindex = match node.element.tag() {
+        web::Tag::Html5(_) => web::Attr::Html5(HtmlAttr::Tabindex),
+        web::Tag::Svg(_) => web::Attr::Svg(SvgAttr::Tabindex),
+    };
+    node.element
+        .attr_str(&tabindex)
+        .and_then(|value| value.trim().parse::<i64>().ok())
+        .is_some_and(|value| value > 0)
+}
+
+fn check_anchor(node: &ElementNode, _: &Context) -> bool {
+    let element = &node.element;
+    let is_button = element.has_attr(&web::Attr::Html5(HtmlAttr::Onclick))
+        || element
+            .attr_tokens(&web::Attr::Html5(HtmlAttr::Role))
+            .contains(&"button");
+
+    !element.has_attr(&web::Attr::Html5(HtmlAttr::Href)) && is_button
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::parse_fragment;
+
+    fn rules(input: &str) -> Vec<&'static str> {
+        let fragment = parse_fragment(input);
+        assert!(fragment.errors.is_empty(), "{:?}", fragment.errors);
+        Linter::recommended()
+            .check(&fragment.nodes)
+            .into_iter()
+            .map(|diagnostic| diagnostic.rule)
+            .collect()
+    }
+
+    #[test]
+    fn images() {
+        assert_eq!(rules("<img src=a.png alt=''>"), Vec::<&str>::new());
+        assert_eq!(rules("<svg><rect/></svg>"), vec!["svg-img-alt"]);
+        assert_eq!(
+            rules("<svg role=img><title> </title></svg>"),
+            vec!["svg-img-alt"]
+        );
+        assert!(rules("<svg role=img><title>Logo</title></svg>").is_empty());
+        assert!(rules("<svg role=img aria-label=Logo></svg>").is_empty());
+        assert!(rules("<svg aria-hidden=true></svg>").is_empty());
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(
+            rules("<input id=a><select></select><textarea></textarea>"),
+            vec!["control-label", "control-label", "control-label"]
+        );
+        assert!(rules(
+            "<input id=a><label for=a>A</label><label>B <select></select></label>\
+             <textarea aria-label=C></textarea><input type=hidden><input type=submit>"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn focus_and_keys() {
+        assert_eq!(
+            rules("<div tabindex=1></div><div tabindex=0></div><div tabindex=-1></div>"),
+            vec!["tabindex-positive"]
+        );
+        assert_eq!(
+            rules("<button autofocus accesskey=s>a</button><a href=b accesskey=S>b</a><a accesskey=x href=c>c</a>"),
+            vec!["no-autofocus", "accesskey-unique", "accesskey-unique"]
+        );
+        assert_eq!(
+            rules("<a onclick=f()>a</a><a role=button>b</a><a href=c onclick=f()>c</a>"),
+            vec!["anchor-is-button", "anchor-is-button"]
+        );
+    }
+
+    #[test]
+    fn document_language() {
+        let html = |lang: Option<&str>| {
+            let element = Element::new(web::Tag::Html5(HtmlTag::Html));
+            let element = match lang {
+                Some(lang) => element.with_attr_str("lang", Some(lang)).unwrap(),
+                None => element,
+            };
+            vec![Node::Element(ElementNode {
+                element,
+                children: vec![],
+            })]
+        };
+        let linter = Linter::recommended();
+
+        assert_eq!(linter.check(&html(None))[0].rule, "html-lang");
+        assert_eq!(linter.check(&html(Some(" ")))[0].rule, "html-lang");
+        assert!(linter.check(&html(Some("en"))).is_empty());
+    }
+
+    #[test]
+    fn custom_rules() {
+        let linter = Linter::new().rule(Rule {
+            id: "no-marquee",
+            severity: Severity::Info,
+            targets: vec![Target::Tag(web::Tag::Html5(HtmlTag::Marquee))],
+            message: "Moving content",
+            fix: "Remove `<marquee>`",
+            check: |_, context| context.ancestors().is_empty(),
+        });
+        let nodes = parse_fragment("<marquee>a</marquee><p><marquee>b</marquee></p>").nodes;
+
+        assert_eq!(
+            linter.check(&nodes),
+            vec![Diagnostic {
+                rule: "no-marquee",
+                severity: Severity::Info,
+                tag: web::Tag::Html5(HtmlTag::Marquee),
+                message: "Moving content",
+                fix: "Remove `<marquee>`",
+            }]
+        );
+    }
+}
@@ -6,11 +6,15 @@
 //! Known, internal attributes allocate no memory upon being parsed.
 //!
 pub mod attr_type;
+pub mod diff;
+pub mod idl_type;
+pub mod update;
 
 pub(crate) mod dataset;
 
 mod value;
 
+use crate::limits::Limits;
 use crate::Error;
 
 ///
@@ -18,6 +22,27 @@ use crate::Error;
 ///
 pub trait Attribute {
     fn attr_type(&self) -> attr_type::AttrType;
+
+    ///
+    /// The IDL type of the DOM property reflecting this attribute,
+    /// or `None` if the attribute is not reflected.
+    ///
+    /// Defaults to `None`, which keeps implementations outside the crate compiling.
+    ///
+    /// Example:
+    /// ```
+    /// use web_ns::*;
+    /// use web_ns::attr::idl_type::IdlType;
+    ///
+    /// assert_eq!(html5::HtmlAttr::Class.idl_type(), Some(IdlType::DomString));
+    /// assert_eq!(html5::HtmlAttr::Tabindex.idl_type(), Some(IdlType::Long));
+    /// assert_eq!(html5::HtmlAttr::Disabled.idl_type(), Some(IdlType::Boolean));
+    /// assert_eq!(svg::SvgAttr::Cx.idl_type(), Some(IdlType::SvgAnimatedLength));
+    /// assert_eq!(svg::SvgAttr::Fill.idl_type(), None);
+    /// ```
+    fn idl_type(&self) -> Option<idl_type::IdlType> {
+        None
+    }
 }
 
 ///
@@ -27,6 +52,18 @@ pub trait DeserializeAttributeValue {
     fn deserialize_attribute_value<S>(&self, input: Option<S>) -> Result<AttributeValue, Error>
     where
         S: Into<String> + AsRef<str>;
+
+    ///
+    /// Deserialize, failing with [Error::LimitExceeded] if the value is too long
+    /// or has too many tokens.
+    ///
+    fn deserialize_attribute_value_with_limits<S>(
+        &self,
+        input: Option<S>,
+        limits: &Limits,
+    ) -> Result<AttributeValue, Error>
+    where
+        S: Into<String> + AsRef<str>;
 }
 
 impl<A> DeserializeAttributeValue for A
@@ -37,7 +74,18 @@ where
     where
         S: Into<String> + AsRef<str>,
     {
-        value::parse_attribute(input, self.attr_type())
+        value::parse_attribute(input, self.attr_type(), &Limits::UNLIMITED)
+    }
+
+    fn deserialize_attribute_value_with_limits<S>(
+        &self,
+        input: Option<S>,
+        limits: &Limits,
+    ) -> Result<AttributeValue, Error>
+    where
+        S: Into<String> + AsRef<str>,
+    {
+        value::parse_attribute(input, self.attr_type(), limits)
     }
 }
 
@@ -57,6 +105,36 @@ where
     }
 }
 
+///
+/// Trait for attributes that correspond to a CSS property.
+///
+pub trait CssProperty {
+    ///
+    /// The CSS property of a presentation attribute.
+    ///
+    /// Example:
+    /// ```
+    /// use web_ns::*;
+    ///
+    /// assert_eq!(svg::SvgAttr::StrokeWidth.css_property(), Some("stroke-width"));
+    /// assert_eq!(svg::SvgAttr::ViewBox.css_property(), None);
+    /// ```
+    fn css_property(&self) -> Option<&str>;
+}
+
+impl<A> CssProperty for A
+where
+    A: Attribute + crate::LocalName,
+{
+    fn css_property(&self) -> Option<&str> {
+        if self.attr_type().is_presentation() {
+            Some(self.local_name())
+        } else {
+            None
+        }
+    }
+}
+
 ///
 /// A typed attribute value.
 ///
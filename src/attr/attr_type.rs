@@ -11,11 +11,64 @@ impl AttrType {
         self.any(flags::BOOL)
     }
 
+    ///
+    /// Whether this is a boolean attribute that takes no other values, unlike e.g. `download`.
+    ///
+    pub fn is_only_bool(self) -> bool {
+        self.is_bool()
+            && !self.any(
+                flags::TRUE | flags::FALSE | flags::STRING | flags::EMPTY_STRING | flags::NUMBER,
+            )
+    }
+
+    pub fn is_presentation(self) -> bool {
+        self.any(flags::PRESENTATION)
+    }
+
+    ///
+    /// Whether the value contains URLs, e.g. `href`, `srcset` or `itemtype`.
+    ///
+    pub fn is_url(self) -> bool {
+        self.any(flags::URL)
+    }
+
+    ///
+    /// How this attribute can be animated, if at all.
+    ///
+    pub fn animation_type(self) -> Option<AnimationType> {
+        if self.any(flags::ANIMATED_TRANSFORM) {
+            Some(AnimationType::Transform)
+        } else if self.any(flags::ANIMATED_COLOR) {
+            Some(AnimationType::Color)
+        } else if self.any(flags::ANIMATED_ADDITIVE) {
+            Some(AnimationType::Additive)
+        } else if self.any(flags::ANIMATED_DISCRETE) {
+            Some(AnimationType::Discrete)
+        } else {
+            None
+        }
+    }
+
     pub fn any(self, flags: Flags) -> bool {
         self.0 & flags > 0
     }
 }
 
+///
+/// The way an animatable attribute is animated.
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AnimationType {
+    /// Jumps from one value to the next, e.g. `fill-rule`.
+    Discrete,
+    /// Interpolated and additive values like numbers, lengths and path data.
+    Additive,
+    /// Interpolated and additive colors and paints, e.g. `fill`.
+    Color,
+    /// Transform lists, animated with `<animateTransform>`.
+    Transform,
+}
+
 pub mod flags {
     use super::Flags;
 
@@ -40,4 +93,22 @@ pub mod flags {
     pub const COMMA_SEP: Flags = 0x100;
     pub const SPACE_SEP: Flags = 0x200;
     pub const COMMA_OR_SPACE_SEP: Flags = 0x400;
+
+    /// An SVG presentation attribute, which corresponds to a CSS property of the same name.
+    pub const PRESENTATION: Flags = 0x1000;
+
+    /// Animatable, with discrete animation.
+    pub const ANIMATED_DISCRETE: Flags = 0x2000;
+
+    /// Animatable, interpolated and additive.
+    pub const ANIMATED_ADDITIVE: Flags = 0x4000;
+
+    /// Animatable as a color.
+    pub const ANIMATED_COLOR: Flags = 0x8000;
+
+    /// Animatable as a transform list.
+    pub const ANIMATED_TRANSFORM: Flags = 0x10000;
+
+    /// A URL, or a list of URLs with the list flags.
+    pub const URL: Flags = 0x20000;
 }
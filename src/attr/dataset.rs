@@ -10,38 +10,42 @@ pub struct DataAttr {
 
 impl DataAttr {
     pub(crate) fn parse_attribute(name: &str) -> Result<Self, crate::Error> {
-        if name.len() > 5 && unicase::UniCase::new(&name[..5]) == unicase::UniCase::new("data-") {
-            let strbuf = format!(
-                "data-{}data{}{}",
-                &name[5..],
-                name.chars().nth(5).unwrap().to_uppercase(),
-                &name[6..]
-            );
-            Ok(Self {
-                strbuf,
-                buf_property_start: name.len(),
-                attr_type: AttrType(flags::STRING),
-            })
-        } else {
-            Err(crate::Error::InvalidAttribute)
+        let is_data = name
+            .get(..5)
+            .is_some_and(|prefix| unicase::UniCase::new(prefix) == unicase::UniCase::new("data-"));
+        let rest = if is_data { &name[5..] } else { "" };
+        let mut chars = rest.chars();
+        match chars.next() {
+            Some(first) => {
+                let strbuf = format!(
+                    "data-{}data{}{}",
+                    rest,
+                    first.to_uppercase(),
+                    chars.as_str()
+                );
+                Ok(Self {
+                    strbuf,
+                    buf_property_start: name.len(),
+                    attr_type: AttrType(flags::STRING),
+                })
+            }
+            None => Err(crate::Error::InvalidAttribute),
         }
     }
 
     pub(crate) fn parse_property(name: &str) -> Result<Self, crate::Error> {
-        if name.len() > 4 && name.starts_with("data") {
-            let strbuf = format!(
-                "data-{}{}{}",
-                name.chars().nth(4).unwrap().to_lowercase(),
-                &name[5..],
-                name
-            );
-            Ok(Self {
-                strbuf,
-                buf_property_start: name.len() + 1,
-                attr_type: AttrType(flags::STRING),
-            })
-        } else {
-            Err(crate::Error::InvalidAttribute)
+        let rest = name.strip_prefix("data").unwrap_or("");
+        let mut chars = rest.chars();
+        match chars.next() {
+            Some(first) => {
+                let strbuf = format!("data-{}{}{}", first.to_lowercase(), chars.as_str(), name);
+                Ok(Self {
+                    buf_property_start: strbuf.len() - name.len(),
+                    strbuf,
+                    attr_type: AttrType(flags::STRING),
+                })
+            }
+            None => Err(crate::Error::InvalidAttribute),
         }
     }
 }
@@ -56,6 +60,10 @@ impl crate::attr::Attribute for DataAttr {
     fn attr_type(&self) -> crate::attr::attr_type::AttrType {
         self.attr_type
     }
+
+    fn idl_type(&self) -> Option<crate::attr::idl_type::IdlType> {
+        Some(crate::attr::idl_type::IdlType::DomString)
+    }
 }
 
 impl crate::PropertyName for DataAttr {
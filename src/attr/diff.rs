@@ -0,0 +1,380 @@
+//!
+//! Diffing of attribute sets, for patching a live DOM incrementally.
+//!
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::attr_type::flags::{COMMA_OR_SPACE_SEP, COMMA_SEP, SPACE_SEP};
+use super::idl_type::IdlType;
+use super::update::{update_strategy, UpdateStrategy};
+use super::{Attribute, AttributeValue, SerializeAttributeValue, SerializedAttributeValue};
+use crate::{web, LocalName, PropertyName};
+
+///
+/// Attributes whose value is an unordered set of tokens, compared regardless of
+/// order and repetition. Other lists, e.g. `coords` or `srcset`, are ordered.
+///
+const TOKEN_SETS: &[&str] = &[
+    "accept",
+    "class",
+    "controlslist",
+    "for",
+    "headers",
+    "itemprop",
+    "itemref",
+    "itemtype",
+    "rel",
+    "requiredExtensions",
+    "rev",
+    "sandbox",
+    "systemLanguage",
+];
+
+///
+/// Whether patches are expressed as attribute or property operations.
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PatchMode {
+    /// Only `setAttribute` and `removeAttribute`, e.g. for serialized markup.
+    Attributes,
+    /// Set DOM properties where an attribute is reflected by a property of a simple
+    /// type, and for live state like `value` (see [UpdateStrategy::Property]).
+    /// Other attributes, e.g. `data-*` and SVG attributes, are still patched as attributes.
+    Properties,
+}
+
+///
+/// A single operation of a patch.
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Patch<'a> {
+    /// `element.setAttribute(attr, value)`
+    SetAttribute(&'a web::Attr, String),
+    /// `element.removeAttribute(attr)`
+    RemoveAttribute(&'a web::Attr),
+    /// `element[property] = value`
+    SetProperty(&'a str, PropertyValue),
+}
+
+///
+/// The value of a property set by a patch.
+///
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PropertyValue {
+    Bool(bool),
+    String(String),
+}
+
+///
+/// Compute the operations that turn the attributes `old` into the attributes `new`
+/// on an element with the tag `tag`.
+///
+/// Removals come first in the order of `old`, then changes in the order of `new`.
+/// Boolean attributes that are `False` are absent, and unordered token sets like `class`
+/// are compared as sets.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::attr::diff::{diff, Patch, PatchMode, PropertyValue};
+/// use web_ns::html5::HtmlAttr;
+///
+/// let input = web::Tag::Html5(html5::HtmlTag::Input);
+/// let class = web::Attr::Html5(HtmlAttr::Class);
+/// let disabled = web::Attr::Html5(HtmlAttr::Disabled);
+///
+/// let old = vec![
+///     (class.clone(), AttributeValue::Multi(vec!["a".into(), "b".into()])),
+///     (disabled.clone(), AttributeValue::True),
+/// ];
+/// let new = vec![
+///     (class.clone(), AttributeValue::Multi(vec!["b".into(), "a".into()])),
+///     (disabled.clone(), AttributeValue::False),
+/// ];
+/// let old = old.iter().map(|(attr, value)| (attr, value));
+/// let new = new.iter().map(|(attr, value)| (attr, value));
+///
+/// assert_eq!(
+///     diff(&input, old.clone(), new.clone(), PatchMode::Attributes),
+///     vec![Patch::RemoveAttribute(&disabled)]
+/// );
+/// assert_eq!(
+///     diff(&input, old, new, PatchMode::Properties),
+///     vec![Patch::SetProperty("disabled", PropertyValue::Bool(false))]
+/// );
+/// ```
+///
+pub fn diff<'a>(
+    tag: &web::Tag,
+    old: impl IntoIterator<Item = (&'a web::Attr, &'a AttributeValue)>,
+    new: impl IntoIterator<Item = (&'a web::Attr, &'a AttributeValue)>,
+    mode: PatchMode,
+) -> Vec<Patch<'a>> {
+    let old: Vec<_> = old
+        .into_iter()
+        .filter_map(|(attr, value)| serialize(attr, value).map(|value| (attr, value)))
+        .collect();
+    let new: Vec<_> = new
+        .into_iter()
+        .filter_map(|(attr, value)| serialize(attr, value).map(|value| (attr, value)))
+        .collect();
+
+    let old_map: BTreeMap<&web::Attr, &Serialized> =
+        old.iter().map(|(attr, value)| (*attr, value)).collect();
+    let new_map: BTreeMap<&web::Attr, &Serialized> =
+        new.iter().map(|(attr, value)| (*attr, value)).collect();
+
+    let mut patches = vec![];
+
+    for (attr, _) in &old {
+        if !new_map.contains_key(attr) {
+            patches.push(removal(tag, attr, mode));
+        }
+    }
+
+    for (attr, value) in &new {
+        let changed = match old_map.get(attr) {
+            Some(old_value) => !old_value.same_as(value),
+            None => true,
+        };
+        if changed {
+            patches.push(change(tag, attr, value, mode));
+        }
+    }
+
+    patches
+}
+
+///
+/// An attribute value as it appears in the DOM.
+///
+struct Serialized {
+    value: String,
+    tokens: Option<BTreeSet<String>>,
+}
+
+impl Serialized {
+    fn same_as(&self, other: &Self) -> bool {
+        match (&self.tokens, &other.tokens) {
+            (Some(tokens), Some(other_tokens)) => tokens == other_tokens,
+            _ => self.value == other.value,
+        }
+    }
+}
+
+fn serialize(attr: &web::Attr, value: &AttributeValue) -> Option<Serialized> {
+    let attr_type = attr.attr_type();
+    let value = match attr.serialize_attribute_value(value) {
+        SerializedAttributeValue::Omitted => return None,
+        SerializedAttributeValue::Empty => String::new(),
+        SerializedAttributeValue::String(value) => value,
+    };
+
+    let tokens = if !TOKEN_SETS.contains(&attr.local_name()) {
+        None
+    } else if attr_type.any(SPACE_SEP | COMMA_OR_SPACE_SEP) {
+        Some(
+            value
+                .split(|c: char| c.is_ascii_whitespace() || (c == ',' && !attr_type.any(SPACE_SEP)))
+                .filter(|token| !token.is_empty())
+                .map(str::to_string)
+                .collect(),
+        )
+    } else if attr_type.any(COMMA_SEP) {
+        Some(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|token| !token.is_empty())
+                .map(str::to_string)
+                .collect(),
+        )
+    } else {
+        None
+    };
+
+    Some(Serialized { value, tokens })
+}
+
+///
+/// The property to patch instead of `attr`, if any.
+///
+fn property<'a>(tag: &web::Tag, attr: &'a web::Attr, mode: PatchMode) -> Option<Target<'a>> {
+    if mode == PatchMode::Attributes {
+        return None;
+    }
+    if let UpdateStrategy::Property { property, .. } = update_strategy(tag, attr) {
+        return Some(Target {
+            property,
+            is_bool: attr.attr_type().is_bool(),
+        });
+    }
+    match (attr, attr.idl_type()) {
+        (web::Attr::Html5(crate::html5::HtmlAttr::Dataset(_)), _) => None,
+        (web::Attr::Html5(_), Some(IdlType::Boolean)) => Some(Target {
+            property: attr.property_name(),
+            is_bool: true,
+        }),
+        (web::Attr::Html5(_), Some(IdlType::DomString))
+        | (web::Attr::Html5(_), Some(IdlType::UsvString))
+            if attr.attr_type().is_bool() =>
+        {
+            None
+        }
+        (web::Attr::Html5(_), Some(IdlType::DomString))
+        | (web::Attr::Html5(_), Some(IdlType::UsvString)) => Some(Target {
+            property: attr.property_name(),
+            is_bool: false,
+        }),
+        _ => None,
+    }
+}
+
+struct Target<'a> {
+    property: &'a str,
+    is_bool: bool,
+}
+
+fn removal<'a>(tag: &web::Tag, attr: &'a web::Attr, mode: PatchMode) -> Patch<'a> {
+    match property(tag, attr, mode) {
+        Some(Target {
+            property,
+            is_bool: true,
+        }) => Patch::SetProperty(property, PropertyValue::Bool(false)),
+        // Live state can only be reset through the property.
+        Some(Target { property, .. })
+            if matches!(update_strategy(tag, attr), UpdateStrategy::Property { .. }) =>
+        {
+            Patch::SetProperty(property, PropertyValue::String(String::new()))
+        }
+        _ => Patch::RemoveAttribute(attr),
+    }
+}
+
+fn change<'a>(
+    tag: &web::Tag,
+    attr: &'a web::Attr,
+    value: &Serialized,
+    mode: PatchMode,
+) -> Patch<'a> {
+    match property(tag, attr, mode) {
+        Some(Target {
+            property,
+            is_bool: true,
+        }) => Patch::SetProperty(property, PropertyValue::Bool(true)),
+        Some(Target { property, .. }) => {
+            Patch::SetProperty(property, PropertyValue::String(value.value.clone()))
+        }
+        None => Patch::SetAttribute(attr, value.value.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html5::{HtmlAttr, HtmlTag};
+
+    fn html(attr: HtmlAttr, value: AttributeValue) -> (web::Attr, AttributeValue) {
+        (web::Attr::Html5(attr), value)
+    }
+
+    fn string(value: &str) -> AttributeValue {
+        AttributeValue::String(value.to_string())
+    }
+
+    fn run<'a>(
+        tag: HtmlTag,
+        old: &'a [(web::Attr, AttributeValue)],
+        new: &'a [(web::Attr, AttributeValue)],
+        mode: PatchMode,
+    ) -> Vec<Patch<'a>> {
+        diff(
+            &web::Tag::Html5(tag),
+            old.iter().map(|(attr, value)| (attr, value)),
+            new.iter().map(|(attr, value)| (attr, value)),
+            mode,
+        )
+    }
+
+    #[test]
+    fn minimal_attribute_ops() {
+        let old = vec![
+            html(HtmlAttr::Id, string("a")),
+            html(HtmlAttr::Title, string("t")),
+            html(HtmlAttr::Hidden, AttributeValue::False),
+            html(HtmlAttr::Rel, string("noopener  noreferrer")),
+        ];
+        let new = vec![
+            html(HtmlAttr::Rel, string("noreferrer noopener")),
+            html(HtmlAttr::Id, string("b")),
+            html(HtmlAttr::Hidden, AttributeValue::True),
+        ];
+
+        assert_eq!(
+            run(HtmlTag::A, &old, &new, PatchMode::Attributes),
+            vec![
+                Patch::RemoveAttribute(&old[1].0),
+                Patch::SetAttribute(&new[1].0, "b".to_string()),
+                Patch::SetAttribute(&new[2].0, "".to_string()),
+            ]
+        );
+        assert!(run(HtmlTag::A, &old, &old, PatchMode::Attributes).is_empty());
+    }
+
+    #[test]
+    fn property_ops() {
+        let old = vec![
+            html(HtmlAttr::Value, string("x")),
+            html(HtmlAttr::Checked, AttributeValue::True),
+            html(HtmlAttr::Placeholder, string("p")),
+        ];
+        let new = vec![
+            html(HtmlAttr::Title, string("t")),
+            (
+                web::Attr::Html5(HtmlAttr::Dataset(Box::new(
+                    crate::attr::dataset::DataAttr::parse_attribute("data-x").unwrap(),
+                ))),
+                string("1"),
+            ),
+        ];
+
+        assert_eq!(
+            run(HtmlTag::Input, &old, &new, PatchMode::Properties),
+            vec![
+                Patch::SetProperty("value", PropertyValue::String("".to_string())),
+                Patch::SetProperty("checked", PropertyValue::Bool(false)),
+                Patch::RemoveAttribute(&old[2].0),
+                Patch::SetProperty("title", PropertyValue::String("t".to_string())),
+                Patch::SetAttribute(&new[1].0, "1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn comma_separated_sets() {
+        let old = vec![html(HtmlAttr::Accept, string("image/png, image/gif"))];
+        let new = vec![html(
+            HtmlAttr::Accept,
+            AttributeValue::Multi(vec!["image/gif".to_string(), "image/png".to_string()]),
+        )];
+
+        assert!(run(HtmlTag::Input, &old, &new, PatchMode::Attributes).is_empty());
+    }
+
+    #[test]
+    fn ordered_lists() {
+        let old = vec![
+            html(HtmlAttr::Coords, string("0,0,10,10")),
+            html(HtmlAttr::Class, string("a b")),
+        ];
+        let new = vec![
+            html(HtmlAttr::Coords, string("10,10,0,0")),
+            html(HtmlAttr::Class, string("b a a")),
+        ];
+
+        assert_eq!(
+            run(HtmlTag::Area, &old, &new, PatchMode::Attributes),
+            vec![Patch::SetAttribute(&new[0].0, "10,10,0,0".to_string())]
+        );
+    }
+}
@@ -0,0 +1,62 @@
+///
+/// The WebIDL type of the DOM property reflecting an attribute.
+///
+/// See: https://html.spec.whatwg.org/#reflecting-content-attributes-in-idl-attributes
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum IdlType {
+    DomString,
+    UsvString,
+    Boolean,
+    Long,
+    UnsignedLong,
+    Double,
+    DomTokenList,
+    EventHandler,
+    CssStyleDeclaration,
+    SvgAnimatedBoolean,
+    SvgAnimatedEnumeration,
+    SvgAnimatedInteger,
+    SvgAnimatedLength,
+    SvgAnimatedLengthList,
+    SvgAnimatedNumber,
+    SvgAnimatedNumberList,
+    SvgAnimatedPreserveAspectRatio,
+    SvgAnimatedRect,
+    SvgAnimatedString,
+    SvgAnimatedTransformList,
+    SvgPointList,
+    SvgStringList,
+}
+
+impl IdlType {
+    ///
+    /// The name of the type, as written in WebIDL.
+    ///
+    pub fn idl_name(self) -> &'static str {
+        match self {
+            Self::DomString => "DOMString",
+            Self::UsvString => "USVString",
+            Self::Boolean => "boolean",
+            Self::Long => "long",
+            Self::UnsignedLong => "unsigned long",
+            Self::Double => "double",
+            Self::DomTokenList => "DOMTokenList",
+            Self::EventHandler => "EventHandler",
+            Self::CssStyleDeclaration => "CSSStyleDeclaration",
+            Self::SvgAnimatedBoolean => "SVGAnimatedBoolean",
+            Self::SvgAnimatedEnumeration => "SVGAnimatedEnumeration",
+            Self::SvgAnimatedInteger => "SVGAnimatedInteger",
+            Self::SvgAnimatedLength => "SVGAnimatedLength",
+            Self::SvgAnimatedLengthList => "SVGAnimatedLengthList",
+            Self::SvgAnimatedNumber => "SVGAnimatedNumber",
+            Self::SvgAnimatedNumberList => "SVGAnimatedNumberList",
+            Self::SvgAnimatedPreserveAspectRatio => "SVGAnimatedPreserveAspectRatio",
+            Self::SvgAnimatedRect => "SVGAnimatedRect",
+            Self::SvgAnimatedString => "SVGAnimatedString",
+            Self::SvgAnimatedTransformList => "SVGAnimatedTransformList",
+            Self::SvgPointList => "SVGPointList",
+            Self::SvgStringList => "SVGStringList",
+        }
+    }
+}
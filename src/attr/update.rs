@@ -0,0 +1,164 @@
+//!
+//! How renderers should apply attribute changes to a live DOM.
+//!
+
+use super::idl_type::IdlType;
+use super::Attribute;
+use crate::html5::{HtmlAttr, HtmlTag};
+use crate::{web, LocalName};
+
+///
+/// The way to apply a changed attribute value to a live DOM element.
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpdateStrategy {
+    ///
+    /// Use `setAttribute` and `removeAttribute`. Any reflecting property follows the attribute.
+    ///
+    /// This is the case for most attributes, and for all SVG, `aria-*` and `data-*` attributes.
+    ///
+    Attribute,
+
+    ///
+    /// The attribute only holds the default value, while the live state is in a property
+    /// which the user may change, e.g. the `value` of an `<input>`.
+    ///
+    /// Set `property` to update the live state.
+    /// The attribute is reflected by `default_property`.
+    ///
+    Property {
+        property: &'static str,
+        default_property: &'static str,
+    },
+
+    ///
+    /// An event handler attribute, which holds script source.
+    /// Renderers would normally attach an event listener instead.
+    ///
+    EventHandler,
+}
+
+///
+/// The update strategy for `attr` on `tag`.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::attr::update::{update_strategy, UpdateStrategy};
+///
+/// let input: web::Tag = html5::HTML5_NS.tag_by_local_name("input").unwrap();
+/// let value: web::Attr = input.attr_by_local_name("value").unwrap();
+/// let class: web::Attr = input.attr_by_local_name("class").unwrap();
+/// let data: web::Attr = input.attr_by_local_name("data-id").unwrap();
+///
+/// assert_eq!(
+///     update_strategy(&input, &value),
+///     UpdateStrategy::Property { property: "value", default_property: "defaultValue" }
+/// );
+/// assert_eq!(update_strategy(&input, &class), UpdateStrategy::Attribute);
+/// assert_eq!(update_strategy(&input, &data), UpdateStrategy::Attribute);
+/// ```
+///
+pub fn update_strategy(tag: &web::Tag, attr: &web::Attr) -> UpdateStrategy {
+    let property = |property, default_property| UpdateStrategy::Property {
+        property,
+        default_property,
+    };
+
+    match (tag, attr) {
+        (web::Tag::Html5(HtmlTag::Input), web::Attr::Html5(HtmlAttr::Value)) => {
+            property("value", "defaultValue")
+        }
+        (web::Tag::Html5(HtmlTag::Input), web::Attr::Html5(HtmlAttr::Checked)) => {
+            property("checked", "defaultChecked")
+        }
+        (web::Tag::Html5(HtmlTag::Option), web::Attr::Html5(HtmlAttr::Selected)) => {
+            property("selected", "defaultSelected")
+        }
+        (web::Tag::Html5(HtmlTag::Audio), web::Attr::Html5(HtmlAttr::Muted))
+        | (web::Tag::Html5(HtmlTag::Video), web::Attr::Html5(HtmlAttr::Muted)) => {
+            property("muted", "defaultMuted")
+        }
+        (_, attr) if is_event_handler(attr) => UpdateStrategy::EventHandler,
+        _ => UpdateStrategy::Attribute,
+    }
+}
+
+///
+/// Whether `attr` holds script source. This includes event attributes without
+/// an event handler property, e.g. SVG `onzoom`, as some browsers run them.
+///
+fn is_event_handler(attr: &web::Attr) -> bool {
+    attr.idl_type() == Some(IdlType::EventHandler) || attr.local_name().starts_with("on")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg::{SvgAttr, SvgTag};
+
+    fn html(tag: HtmlTag, attr: HtmlAttr) -> UpdateStrategy {
+        update_strategy(&web::Tag::Html5(tag), &web::Attr::Html5(attr))
+    }
+
+    #[test]
+    fn live_state() {
+        assert_eq!(
+            html(HtmlTag::Input, HtmlAttr::Checked),
+            UpdateStrategy::Property {
+                property: "checked",
+                default_property: "defaultChecked"
+            }
+        );
+        assert_eq!(
+            html(HtmlTag::Video, HtmlAttr::Muted),
+            UpdateStrategy::Property {
+                property: "muted",
+                default_property: "defaultMuted"
+            }
+        );
+        assert_eq!(
+            html(HtmlTag::Button, HtmlAttr::Value),
+            UpdateStrategy::Attribute
+        );
+        assert_eq!(
+            html(HtmlTag::Li, HtmlAttr::Value),
+            UpdateStrategy::Attribute
+        );
+    }
+
+    #[test]
+    fn attributes() {
+        assert_eq!(
+            html(HtmlTag::Div, HtmlAttr::AriaLabel),
+            UpdateStrategy::Attribute
+        );
+        assert_eq!(
+            html(HtmlTag::Div, HtmlAttr::Role),
+            UpdateStrategy::Attribute
+        );
+        assert_eq!(
+            html(HtmlTag::Details, HtmlAttr::Open),
+            UpdateStrategy::Attribute
+        );
+        assert_eq!(
+            html(HtmlTag::Div, HtmlAttr::Onclick),
+            UpdateStrategy::EventHandler
+        );
+
+        let svg = |tag, attr| update_strategy(&web::Tag::Svg(tag), &web::Attr::Svg(attr));
+        assert_eq!(svg(SvgTag::Rect, SvgAttr::Width), UpdateStrategy::Attribute);
+        assert_eq!(
+            svg(SvgTag::Rect, SvgAttr::Onclick),
+            UpdateStrategy::EventHandler
+        );
+        assert_eq!(
+            svg(SvgTag::Rect, SvgAttr::Onmousewheel),
+            UpdateStrategy::EventHandler
+        );
+        assert_eq!(
+            svg(SvgTag::Svg, SvgAttr::Onzoom),
+            UpdateStrategy::EventHandler
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::limits::{Limit, Limits};
 use crate::Error;
 
 use super::{AttributeValue, SerializedAttributeValue};
@@ -9,19 +10,34 @@ use super::attr_type::*;
 /// Parse an Option of a string-based attribute value.
 /// None represents an attribute without a value, e.g. `<foo bar />`
 ///
-pub fn parse_attribute<S>(value: Option<S>, attr_type: AttrType) -> Result<AttributeValue, Error>
+pub fn parse_attribute<S>(
+    value: Option<S>,
+    attr_type: AttrType,
+    limits: &Limits,
+) -> Result<AttributeValue, Error>
 where
     S: Into<String> + AsRef<str>,
 {
-    fn maybe_multi<'s>(it: impl Iterator<Item = &'s str>) -> AttributeValue {
-        let strings: Vec<String> = it.filter(|s| !s.is_empty()).map(|s| s.into()).collect();
-        if strings.len() == 0 {
-            AttributeValue::String(String::new())
+    let maybe_multi = |it: &mut dyn Iterator<Item = &str>| -> Result<AttributeValue, Error> {
+        // Stop at the first token over the limit
+        let strings: Vec<String> = it
+            .filter(|s| !s.is_empty())
+            .take(limits.max_tokens.saturating_add(1))
+            .map(|s| s.into())
+            .collect();
+        if strings.len() > limits.max_tokens {
+            Err(Error::LimitExceeded(Limit::Tokens))
+        } else if strings.is_empty() {
+            Ok(AttributeValue::String(String::new()))
         } else if strings.len() == 1 {
-            AttributeValue::String(strings.into_iter().next().unwrap())
+            Ok(AttributeValue::String(strings.into_iter().next().unwrap()))
         } else {
-            AttributeValue::Multi(strings)
+            Ok(AttributeValue::Multi(strings))
         }
+    };
+
+    if let Some(string) = &value {
+        limits.check_value(string.as_ref())?;
     }
 
     match value {
@@ -60,13 +76,11 @@ where
                 let str = string.as_ref();
 
                 if attr_type.any(SPACE_SEP) {
-                    Ok(maybe_multi(str.split(' ')))
+                    maybe_multi(&mut str.split(' '))
                 } else if attr_type.any(COMMA_SEP) {
-                    Ok(maybe_multi(str.split(',').map(str::trim)))
+                    maybe_multi(&mut str.split(',').map(str::trim))
                 } else if attr_type.any(COMMA_OR_SPACE_SEP) {
-                    Ok(maybe_multi(
-                        str.split(|c| c == ' ' || c == ',').map(str::trim),
-                    ))
+                    maybe_multi(&mut str.split([' ', ',']).map(str::trim))
                 } else if attr_type.any(STRING | NUMBER) {
                     Ok(AttributeValue::String(string.into()))
                 } else {
@@ -134,7 +148,7 @@ mod tests {
         expected_value: AttributeValue,
         expected_serialization: SerializedAttributeValue,
     ) {
-        let attribute_value = parse_attribute(input, attr_type).unwrap();
+        let attribute_value = parse_attribute(input, attr_type, &Limits::UNLIMITED).unwrap();
         assert_eq!(attribute_value, expected_value);
 
         let serialized = serialize_attribute_value(&attribute_value, attr_type);
@@ -176,7 +190,7 @@ mod tests {
             SerializedAttributeValue::Omitted,
         );
 
-        assert!(parse_attribute(Some("t"), boolean).is_err());
+        assert!(parse_attribute(Some("t"), boolean, &Limits::UNLIMITED).is_err());
     }
 
     #[test]
@@ -196,8 +210,8 @@ mod tests {
             SerializedAttributeValue::String("false".to_string()),
         );
 
-        assert!(parse_attribute(none(), tf).is_err());
-        assert!(parse_attribute(Some("t"), tf).is_err());
+        assert!(parse_attribute(none(), tf, &Limits::UNLIMITED).is_err());
+        assert!(parse_attribute(Some("t"), tf, &Limits::UNLIMITED).is_err());
     }
 
     #[test]
@@ -211,10 +225,10 @@ mod tests {
             SerializedAttributeValue::String(String::new()),
         );
 
-        assert!(parse_attribute(none(), es).is_err());
-        assert!(parse_attribute(Some("true"), es).is_err());
-        assert!(parse_attribute(Some("false"), es).is_err());
-        assert!(parse_attribute(Some("t"), es).is_err());
+        assert!(parse_attribute(none(), es, &Limits::UNLIMITED).is_err());
+        assert!(parse_attribute(Some("true"), es, &Limits::UNLIMITED).is_err());
+        assert!(parse_attribute(Some("false"), es, &Limits::UNLIMITED).is_err());
+        assert!(parse_attribute(Some("t"), es, &Limits::UNLIMITED).is_err());
     }
 
     #[test]
@@ -234,7 +248,7 @@ mod tests {
             SerializedAttributeValue::String("true".to_string()),
         );
 
-        assert!(parse_attribute(Some(""), num).is_err());
+        assert!(parse_attribute(Some(""), num, &Limits::UNLIMITED).is_err());
     }
 
     #[test]
@@ -260,7 +274,7 @@ mod tests {
             SerializedAttributeValue::String("a b".to_string()),
         );
 
-        assert!(parse_attribute(none(), sep).is_err());
+        assert!(parse_attribute(none(), sep, &Limits::UNLIMITED).is_err());
     }
 
     #[test]
@@ -292,7 +306,7 @@ mod tests {
             SerializedAttributeValue::String("a, b".to_string()),
         );
 
-        assert!(parse_attribute(none(), sep).is_err());
+        assert!(parse_attribute(none(), sep, &Limits::UNLIMITED).is_err());
     }
 
     #[test]
@@ -324,6 +338,29 @@ mod tests {
             SerializedAttributeValue::String("a b".to_string()),
         );
 
-        assert!(parse_attribute(none(), sep).is_err());
+        assert!(parse_attribute(none(), sep, &Limits::UNLIMITED).is_err());
+    }
+
+    #[test]
+    fn parse_with_limits() {
+        let sep = AttrType(STRING | SPACE_SEP);
+        let limits = Limits {
+            max_value_len: 8,
+            max_tokens: 2,
+            ..Limits::UNLIMITED
+        };
+
+        assert_eq!(
+            parse_attribute(Some("a  b"), sep, &limits).unwrap(),
+            AttributeValue::Multi(vec!["a".to_string(), "b".to_string()])
+        );
+        assert!(matches!(
+            parse_attribute(Some("a b c"), sep, &limits),
+            Err(Error::LimitExceeded(Limit::Tokens))
+        ));
+        assert!(matches!(
+            parse_attribute(Some("abcdefghi"), AttrType(STRING), &limits),
+            Err(Error::LimitExceeded(Limit::ValueLength))
+        ));
     }
 }
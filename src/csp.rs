@@ -0,0 +1,353 @@
+//!
+//! Content-Security-Policy analysis of typed elements.
+//!
+//! Reports what a strict, nonce-based policy without `'unsafe-inline'` would
+//! block, and stamps nonces onto elements that need them.
+//!
+//! See: https://www.w3.org/TR/CSP3/
+//!
+
+mod sha256;
+
+use crate::attr::update::{update_strategy, UpdateStrategy};
+use crate::attr::AttributeValue;
+use crate::dom::{ElementNode, Node};
+use crate::element::Element;
+use crate::html5::{HtmlAttr, HtmlTag};
+use crate::svg::SvgTag;
+use crate::{url, web, LocalName};
+
+///
+/// Something a strict policy blocks.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// An inline event handler attribute, e.g. `onclick`.
+    InlineEventHandler(web::Attr),
+    /// A `javascript:` URL in a URL attribute.
+    JavascriptUrl(web::Attr),
+    /// A `style` attribute.
+    InlineStyleAttribute,
+    /// A `<script>` without the nonce, which is not an inline script with an allowed hash.
+    ScriptWithoutNonce,
+    /// A `<style>` without the nonce or an allowed hash.
+    StyleWithoutNonce,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub tag: web::Tag,
+    pub kind: ViolationKind,
+}
+
+///
+/// The nonce and hash sources of a policy.
+///
+/// Hashes are written as returned by [hash_source], e.g. `sha256-...`, without quotes.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Sources {
+    pub nonce: Option<String>,
+    pub script_hashes: Vec<String>,
+    pub style_hashes: Vec<String>,
+}
+
+///
+/// The hashes of inline blocks, as returned by [apply_nonce].
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InlineHashes {
+    pub scripts: Vec<String>,
+    pub styles: Vec<String>,
+}
+
+///
+/// The `sha256-` hash source for inline content, to be written in a policy in single quotes.
+///
+/// Example:
+/// ```
+/// assert_eq!(
+///     web_ns::csp::hash_source("alert('Hello, world.');"),
+///     "sha256-qznLcsROx4GACP2dm0UCKCzCG+HiZ1guq6ZZDob/Tng="
+/// );
+/// ```
+///
+pub fn hash_source(content: &str) -> String {
+    format!(
+        "sha256-{}",
+        sha256::base64(&sha256::sha256(content.as_bytes()))
+    )
+}
+
+///
+/// Check a single element. `content` is the text of an inline `<script>` or `<style>`.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::csp::{check_element, Sources, ViolationKind};
+/// use web_ns::element::Element;
+///
+/// let a = Element::new(web::Tag::Html5(html5::HtmlTag::A))
+///     .with_attr_str("href", Some(" JavaScript:void(0)"))?
+///     .with_attr_str("onclick", Some("go()"))?;
+///
+/// assert_eq!(
+///     check_element(&a, None, &Sources::default()),
+///     vec![
+///         ViolationKind::JavascriptUrl(web::Attr::Html5(html5::HtmlAttr::Href)),
+///         ViolationKind::InlineEventHandler(web::Attr::Html5(html5::HtmlAttr::Onclick)),
+///     ]
+/// );
+/// # Ok::<(), Error>(())
+/// ```
+///
+pub fn check_element(
+    element: &Element,
+    content: Option<&str>,
+    sources: &Sources,
+) -> Vec<ViolationKind> {
+    let tag = element.tag();
+    let mut violations = vec![];
+
+    for (attr, value) in element.attrs().iter() {
+        if update_strategy(tag, attr) == UpdateStrategy::EventHandler {
+            violations.push(ViolationKind::InlineEventHandler(attr.clone()));
+        } else if attr.local_name() == "style" {
+            violations.push(ViolationKind::InlineStyleAttribute);
+        } else if url::attr_urls(attr, value)
+            .into_iter()
+            .any(|url| url::scheme(url).as_deref() == Some("javascript"))
+        {
+            violations.push(ViolationKind::JavascriptUrl(attr.clone()));
+        }
+    }
+
+    let has_nonce = match (&sources.nonce, nonce(element)) {
+        (Some(expected), Some(nonce)) => expected == nonce,
+        _ => false,
+    };
+    let hash_allowed = |hashes: &[String]| match content {
+        Some(content) => hashes.contains(&hash_source(content)),
+        None => false,
+    };
+
+    match tag {
+        web::Tag::Html5(HtmlTag::Script) | web::Tag::Svg(SvgTag::Script) => {
+            let external = element
+                .attrs()
+                .iter()
+                .any(|(attr, _)| matches!(attr.local_name(), "src" | "href"));
+            let allowed = has_nonce || (!external && hash_allowed(&sources.script_hashes));
+            if !allowed {
+                violations.push(ViolationKind::ScriptWithoutNonce);
+            }
+        }
+        web::Tag::Html5(HtmlTag::Style) | web::Tag::Svg(SvgTag::Style)
+            if !has_nonce && !hash_allowed(&sources.style_hashes) =>
+        {
+            violations.push(ViolationKind::StyleWithoutNonce);
+        }
+        _ => {}
+    }
+
+    violations
+}
+
+///
+/// Check a tree of nodes, in document order.
+///
+pub fn analyze(nodes: &[Node], sources: &Sources) -> Vec<Violation> {
+    let mut violations = vec![];
+    analyze_nodes(nodes, sources, &mut violations);
+    violations
+}
+
+fn analyze_nodes(nodes: &[Node], sources: &Sources, violations: &mut Vec<Violation>) {
+    for node in nodes {
+        if let Node::Element(ElementNode { element, children }) = node {
+            let content = text_content(children);
+            violations.extend(
+                check_element(element, Some(&content), sources)
+                    .into_iter()
+                    .map(|kind| Violation {
+                        tag: element.tag().clone(),
+                        kind,
+                    }),
+            );
+            analyze_nodes(children, sources, violations);
+        }
+    }
+}
+
+///
+/// Set `nonce` on every HTML `<script>`, `<style>` and `<link rel=stylesheet>`,
+/// and compute the hashes of the inline scripts and styles.
+///
+/// Example:
+/// ```
+/// use web_ns::csp::{analyze, apply_nonce, Sources};
+/// use web_ns::dom::{parse_fragment, serialize};
+///
+/// let mut nodes = parse_fragment("<script>a()</script><link rel=stylesheet href=a.css>").nodes;
+/// let hashes = apply_nonce(&mut nodes, "r4nd0m");
+///
+/// assert_eq!(
+///     serialize(&nodes).unwrap(),
+///     r#"<script nonce="r4nd0m">a()</script><link rel="stylesheet" href="a.css" nonce="r4nd0m">"#
+/// );
+/// assert_eq!(hashes.scripts, vec![web_ns::csp::hash_source("a()")]);
+///
+/// let sources = Sources { nonce: Some("r4nd0m".to_string()), ..Sources::default() };
+/// assert!(analyze(&nodes, &sources).is_empty());
+/// ```
+///
+pub fn apply_nonce(nodes: &mut [Node], nonce: &str) -> InlineHashes {
+    let mut hashes = InlineHashes::default();
+    apply_nonce_to_nodes(nodes, nonce, &mut hashes);
+    hashes
+}
+
+fn apply_nonce_to_nodes(nodes: &mut [Node], nonce: &str, hashes: &mut InlineHashes) {
+    for node in nodes {
+        if let Node::Element(ElementNode { element, children }) = node {
+            let stamp = match element.tag() {
+                web::Tag::Html5(HtmlTag::Script) => {
+                    if !element.has_attr(&web::Attr::Html5(HtmlAttr::Src)) {
+                        hashes.scripts.push(hash_source(&text_content(children)));
+                    }
+                    true
+                }
+                web::Tag::Html5(HtmlTag::Style) => {
+                    hashes.styles.push(hash_source(&text_content(children)));
+                    true
+                }
+                web::Tag::Html5(HtmlTag::Link) => element
+                    .attr_tokens(&web::Attr::Html5(HtmlAttr::Rel))
+                    .iter()
+                    .any(|rel| rel.eq_ignore_ascii_case("stylesheet")),
+                _ => false,
+            };
+            if stamp {
+                // The nonce attribute is in the namespace of these HTML tags
+                let _ = element.set_attr(
+                    web::Attr::Html5(HtmlAttr::Nonce),
+                    AttributeValue::String(nonce.to_string()),
+                );
+            }
+            apply_nonce_to_nodes(children, nonce, hashes);
+        }
+    }
+}
+
+fn nonce(element: &Element) -> Option<&str> {
+    match element.tag() {
+        web::Tag::Html5(_) => element.attr_str(&web::Attr::Html5(HtmlAttr::Nonce)),
+        web::Tag::Svg(_) => None,
+    }
+}
+
+fn text_content(nodes: &[Node]) -> String {
+    nodes
+        .iter()
+        .filter_map(|node| match node {
+            Node::Text(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::parse_fragment;
+
+    fn kinds(input: &str, sources: &Sources) -> Vec<ViolationKind> {
+        analyze(&parse_fragment(input).nodes, sources)
+            .into_iter()
+            .map(|violation| violation.kind)
+            .collect()
+    }
+
+    #[test]
+    fn violations() {
+        let sources = Sources::default();
+        assert_eq!(
+            kinds(
+                "<div style='color: red'><img src=a.png srcset='b.png 1x, javascript:x 2x' onerror=f()></div>",
+                &sources
+            ),
+            vec![
+                ViolationKind::InlineStyleAttribute,
+                ViolationKind::JavascriptUrl(web::Attr::Html5(HtmlAttr::Srcset)),
+                ViolationKind::InlineEventHandler(web::Attr::Html5(HtmlAttr::Onerror)),
+            ]
+        );
+        assert_eq!(
+            kinds(
+                "<svg><a href='javascript:x'><script>y</script></a></svg>",
+                &sources
+            ),
+            vec![
+                ViolationKind::JavascriptUrl(web::Attr::Svg(crate::svg::SvgAttr::Href)),
+                ViolationKind::ScriptWithoutNonce,
+            ]
+        );
+        assert!(kinds("<a href='https://x'>y</a>", &sources).is_empty());
+        assert_eq!(
+            kinds(
+                "<table background='javascript:x'><tr itemscope itemtype='/a javascript:y'>",
+                &sources
+            ),
+            vec![
+                ViolationKind::JavascriptUrl(web::Attr::Html5(HtmlAttr::Background)),
+                ViolationKind::JavascriptUrl(web::Attr::Html5(HtmlAttr::Itemtype)),
+            ]
+        );
+    }
+
+    #[test]
+    fn nonces_and_hashes() {
+        let sources = Sources {
+            nonce: Some("n".to_string()),
+            script_hashes: vec![hash_source("ok()")],
+            style_hashes: vec![hash_source("p {}")],
+        };
+        assert!(kinds(
+            "<script>ok()</script><script nonce=n src=a.js></script><style>p {}</style>",
+            &sources
+        )
+        .is_empty());
+        assert_eq!(
+            kinds(
+                "<script>no()</script><script src=a.js></script><script nonce=x>ok()</script><style nonce=m>q {}</style>",
+                &sources
+            ),
+            vec![
+                ViolationKind::ScriptWithoutNonce,
+                ViolationKind::ScriptWithoutNonce,
+                ViolationKind::StyleWithoutNonce,
+            ]
+        );
+    }
+
+    #[test]
+    fn apply() {
+        let mut nodes = parse_fragment(
+            "<style>p {}</style><script src=a.js></script><link rel=icon href=i.png>",
+        )
+        .nodes;
+        let hashes = apply_nonce(&mut nodes, "n");
+        assert_eq!(
+            hashes,
+            InlineHashes {
+                scripts: vec![],
+                styles: vec![hash_source("p {}")],
+            }
+        );
+        assert_eq!(
+            crate::dom::serialize(&nodes).unwrap(),
+            r#"<style nonce="n">p {}</style><script src="a.js" nonce="n"></script><link rel="icon" href="i.png">"#
+        );
+    }
+}
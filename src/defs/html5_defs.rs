@@ -5,7 +5,7 @@ pub mod tags {
 
     pub struct Void(pub bool);
 
-    pub const DEFS: &[(&str, Void, &'static NS)] = &[
+    pub const DEFS: &[(&str, Void, &NS)] = &[
         ("a", Void(false), &HTML5),
         ("abbr", Void(false), &HTML5),
         ("acronym", Void(false), &HTML5),
@@ -153,7 +153,493 @@ pub mod tags {
         ("var", Void(false), &HTML5),
         ("video", Void(false), &HTML5),
         ("wbr", Void(true), &HTML5),
-        ("xm", Void(false), &HTML5),
+        ("xmp", Void(false), &HTML5),
+    ];
+}
+
+pub mod tag_types {
+    // This import path is altered because this file is included from build.rs
+    use crate::tag_type::{flags::*, Flags};
+
+    /// Flags for sets of tags. See: https://html.spec.whatwg.org/#elements-2
+    pub const DEFS: &[(Flags, &[&str])] = &[
+        (
+            RAW_TEXT,
+            &["iframe", "noembed", "noframes", "script", "style", "xmp"],
+        ),
+        (ESCAPABLE_RAW_TEXT, &["textarea", "title"]),
+        (PLAINTEXT, &["plaintext"]),
+        (TEMPLATE, &["template"]),
+        // Tree construction. See: https://html.spec.whatwg.org/#special
+        (
+            SPECIAL,
+            &[
+                "address",
+                "applet",
+                "area",
+                "article",
+                "aside",
+                "base",
+                "basefont",
+                "bgsound",
+                "blockquote",
+                "body",
+                "br",
+                "button",
+                "caption",
+                "center",
+                "col",
+                "colgroup",
+                "dd",
+                "details",
+                "dir",
+                "div",
+                "dl",
+                "dt",
+                "embed",
+                "fieldset",
+                "figcaption",
+                "figure",
+                "footer",
+                "form",
+                "frame",
+                "frameset",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "head",
+                "header",
+                "hgroup",
+                "hr",
+                "html",
+                "iframe",
+                "img",
+                "input",
+                "keygen",
+                "li",
+                "link",
+                "listing",
+                "main",
+                "marquee",
+                "menu",
+                "meta",
+                "nav",
+                "noembed",
+                "noframes",
+                "noscript",
+                "object",
+                "ol",
+                "p",
+                "param",
+                "plaintext",
+                "pre",
+                "script",
+                "section",
+                "select",
+                "source",
+                "style",
+                "summary",
+                "table",
+                "tbody",
+                "td",
+                "template",
+                "textarea",
+                "tfoot",
+                "th",
+                "thead",
+                "title",
+                "tr",
+                "track",
+                "ul",
+                "wbr",
+                "xmp",
+            ],
+        ),
+        (
+            FORMATTING,
+            &[
+                "a", "b", "big", "code", "em", "font", "i", "nobr", "s", "small", "strike",
+                "strong", "tt", "u",
+            ],
+        ),
+        (
+            SCOPE,
+            &[
+                "applet", "caption", "html", "table", "td", "th", "marquee", "object", "template",
+            ],
+        ),
+        (LIST_ITEM_SCOPE, &["ol", "ul"]),
+        (BUTTON_SCOPE, &["button"]),
+        (TABLE_SCOPE, &["html", "table", "template"]),
+        (NOT_SELECT_SCOPE, &["optgroup", "option"]),
+        // Content categories. See: https://html.spec.whatwg.org/#kinds-of-content
+        (
+            METADATA,
+            &[
+                "base", "link", "meta", "noscript", "script", "style", "template", "title",
+            ],
+        ),
+        (
+            FLOW,
+            &[
+                "a",
+                "abbr",
+                "address",
+                "article",
+                "aside",
+                "audio",
+                "b",
+                "bdi",
+                "bdo",
+                "blockquote",
+                "br",
+                "button",
+                "canvas",
+                "cite",
+                "code",
+                "data",
+                "datalist",
+                "del",
+                "details",
+                "dfn",
+                "dialog",
+                "div",
+                "dl",
+                "em",
+                "embed",
+                "fieldset",
+                "figure",
+                "footer",
+                "form",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "header",
+                "hgroup",
+                "hr",
+                "i",
+                "iframe",
+                "img",
+                "input",
+                "ins",
+                "kbd",
+                "label",
+                "main",
+                "map",
+                "mark",
+                "math",
+                "menu",
+                "meter",
+                "nav",
+                "noscript",
+                "object",
+                "ol",
+                "output",
+                "p",
+                "picture",
+                "pre",
+                "progress",
+                "q",
+                "ruby",
+                "s",
+                "samp",
+                "script",
+                "section",
+                "select",
+                "slot",
+                "small",
+                "span",
+                "strong",
+                "sub",
+                "sup",
+                "svg",
+                "table",
+                "template",
+                "textarea",
+                "time",
+                "u",
+                "ul",
+                "var",
+                "video",
+                "wbr",
+            ],
+        ),
+        (SECTIONING, &["article", "aside", "nav", "section"]),
+        (HEADING, &["h1", "h2", "h3", "h4", "h5", "h6", "hgroup"]),
+        (
+            PHRASING,
+            &[
+                "a", "abbr", "audio", "b", "bdi", "bdo", "br", "button", "canvas", "cite", "code",
+                "data", "datalist", "del", "dfn", "em", "embed", "i", "iframe", "img", "input",
+                "ins", "kbd", "label", "map", "mark", "math", "meter", "noscript", "object",
+                "output", "picture", "progress", "q", "ruby", "s", "samp", "script", "select",
+                "slot", "small", "span", "strong", "sub", "sup", "svg", "template", "textarea",
+                "time", "u", "var", "video", "wbr",
+            ],
+        ),
+        (
+            EMBEDDED,
+            &[
+                "audio", "canvas", "embed", "iframe", "img", "math", "object", "picture", "svg",
+                "video",
+            ],
+        ),
+        (
+            INTERACTIVE,
+            &[
+                "button", "details", "embed", "iframe", "input", "label", "select", "textarea",
+            ],
+        ),
+        (
+            FORM_ASSOCIATED,
+            &[
+                "button", "fieldset", "img", "input", "object", "output", "select", "textarea",
+            ],
+        ),
+        // Form-associated categories. See: https://html.spec.whatwg.org/#categories
+        (
+            LISTED,
+            &[
+                "button", "fieldset", "input", "object", "output", "select", "textarea",
+            ],
+        ),
+        (
+            LABELABLE,
+            &[
+                "button", "input", "meter", "output", "progress", "select", "textarea",
+            ],
+        ),
+        (SUBMITTABLE, &["button", "input", "select", "textarea"]),
+        (RESETTABLE, &["input", "output", "select", "textarea"]),
+        (
+            AUTOCAPITALIZE_INHERITING,
+            &[
+                "button", "fieldset", "input", "output", "select", "textarea",
+            ],
+        ),
+        (
+            PALPABLE,
+            &[
+                "a",
+                "abbr",
+                "address",
+                "article",
+                "aside",
+                "b",
+                "bdi",
+                "bdo",
+                "blockquote",
+                "button",
+                "canvas",
+                "cite",
+                "code",
+                "data",
+                "details",
+                "dfn",
+                "div",
+                "dl",
+                "em",
+                "embed",
+                "fieldset",
+                "figure",
+                "footer",
+                "form",
+                "h1",
+                "h2",
+                "h3",
+                "h4",
+                "h5",
+                "h6",
+                "header",
+                "hgroup",
+                "i",
+                "iframe",
+                "img",
+                "input",
+                "ins",
+                "kbd",
+                "label",
+                "main",
+                "map",
+                "mark",
+                "math",
+                "menu",
+                "meter",
+                "nav",
+                "object",
+                "ol",
+                "output",
+                "p",
+                "picture",
+                "pre",
+                "progress",
+                "q",
+                "ruby",
+                "s",
+                "samp",
+                "section",
+                "select",
+                "small",
+                "span",
+                "strong",
+                "sub",
+                "sup",
+                "svg",
+                "table",
+                "textarea",
+                "time",
+                "u",
+                "ul",
+                "var",
+            ],
+        ),
+        (SCRIPT_SUPPORTING, &["script", "template"]),
+    ];
+}
+
+pub mod interfaces {
+    /// DOM interfaces of tags. See: https://html.spec.whatwg.org/#elements-3
+    ///
+    /// Obsolete tags that are not recognized by the DOM are `HTMLUnknownElement`.
+    pub const DEFS: &[(&str, &[&str])] = &[
+        ("HTMLAnchorElement", &["a"]),
+        ("HTMLAreaElement", &["area"]),
+        ("HTMLAudioElement", &["audio"]),
+        ("HTMLBaseElement", &["base"]),
+        ("HTMLBodyElement", &["body"]),
+        ("HTMLBRElement", &["br"]),
+        ("HTMLButtonElement", &["button"]),
+        ("HTMLCanvasElement", &["canvas"]),
+        ("HTMLDataElement", &["data"]),
+        ("HTMLDataListElement", &["datalist"]),
+        ("HTMLDetailsElement", &["details"]),
+        ("HTMLDialogElement", &["dialog"]),
+        ("HTMLDirectoryElement", &["dir"]),
+        ("HTMLDivElement", &["div"]),
+        ("HTMLDListElement", &["dl"]),
+        ("HTMLEmbedElement", &["embed"]),
+        ("HTMLFieldSetElement", &["fieldset"]),
+        ("HTMLFontElement", &["font"]),
+        ("HTMLFormElement", &["form"]),
+        ("HTMLFrameElement", &["frame"]),
+        ("HTMLFrameSetElement", &["frameset"]),
+        ("HTMLHeadElement", &["head"]),
+        ("HTMLHeadingElement", &["h1", "h2", "h3", "h4", "h5", "h6"]),
+        ("HTMLHRElement", &["hr"]),
+        ("HTMLHtmlElement", &["html"]),
+        ("HTMLIFrameElement", &["iframe"]),
+        ("HTMLImageElement", &["img"]),
+        ("HTMLInputElement", &["input"]),
+        ("HTMLLabelElement", &["label"]),
+        ("HTMLLegendElement", &["legend"]),
+        ("HTMLLIElement", &["li"]),
+        ("HTMLLinkElement", &["link"]),
+        ("HTMLMapElement", &["map"]),
+        ("HTMLMarqueeElement", &["marquee"]),
+        ("HTMLMenuElement", &["menu"]),
+        ("HTMLMetaElement", &["meta"]),
+        ("HTMLMeterElement", &["meter"]),
+        ("HTMLModElement", &["del", "ins"]),
+        ("HTMLObjectElement", &["object"]),
+        ("HTMLOListElement", &["ol"]),
+        ("HTMLOptGroupElement", &["optgroup"]),
+        ("HTMLOptionElement", &["option"]),
+        ("HTMLOutputElement", &["output"]),
+        ("HTMLParagraphElement", &["p"]),
+        ("HTMLParamElement", &["param"]),
+        ("HTMLPictureElement", &["picture"]),
+        ("HTMLPreElement", &["listing", "pre", "xmp"]),
+        ("HTMLProgressElement", &["progress"]),
+        ("HTMLQuoteElement", &["blockquote", "q"]),
+        ("HTMLScriptElement", &["script"]),
+        ("HTMLSelectElement", &["select"]),
+        ("HTMLSlotElement", &["slot"]),
+        ("HTMLSourceElement", &["source"]),
+        ("HTMLSpanElement", &["span"]),
+        ("HTMLStyleElement", &["style"]),
+        ("HTMLTableCaptionElement", &["caption"]),
+        ("HTMLTableCellElement", &["td", "th"]),
+        ("HTMLTableColElement", &["col", "colgroup"]),
+        ("HTMLTableElement", &["table"]),
+        ("HTMLTableRowElement", &["tr"]),
+        ("HTMLTableSectionElement", &["tbody", "tfoot", "thead"]),
+        ("HTMLTemplateElement", &["template"]),
+        ("HTMLTextAreaElement", &["textarea"]),
+        ("HTMLTimeElement", &["time"]),
+        ("HTMLTitleElement", &["title"]),
+        ("HTMLTrackElement", &["track"]),
+        ("HTMLUListElement", &["ul"]),
+        ("HTMLVideoElement", &["video"]),
+        (
+            "HTMLElement",
+            &[
+                "abbr",
+                "acronym",
+                "address",
+                "article",
+                "aside",
+                "b",
+                "basefont",
+                "bdi",
+                "bdo",
+                "big",
+                "center",
+                "cite",
+                "code",
+                "dd",
+                "dfn",
+                "dt",
+                "em",
+                "figcaption",
+                "figure",
+                "footer",
+                "header",
+                "hgroup",
+                "i",
+                "kbd",
+                "main",
+                "mark",
+                "nav",
+                "nobr",
+                "noembed",
+                "noframes",
+                "noscript",
+                "plaintext",
+                "rb",
+                "rp",
+                "rt",
+                "rtc",
+                "ruby",
+                "s",
+                "samp",
+                "section",
+                "small",
+                "strike",
+                "strong",
+                "sub",
+                "summary",
+                "sup",
+                "tt",
+                "u",
+                "var",
+                "wbr",
+            ],
+        ),
+        (
+            "HTMLUnknownElement",
+            &[
+                "applet", "bgsound", "blink", "command", "content", "element", "image", "isindex",
+                "keygen", "menuitem", "multicol", "nextid", "rbc", "shadow", "spacer",
+            ],
+        ),
+        ("MathMLElement", &["math"]),
+        ("SVGSVGElement", &["svg"]),
     ];
 }
 
@@ -166,12 +652,84 @@ pub mod attrs {
         ("accept", "accept", COMMA_SEP | STRING),
         ("accept-charset", "acceptCharset", SPACE_SEP | STRING),
         ("accesskey", "accessKey", SPACE_SEP | STRING),
-        ("action", "action", STRING),
+        ("action", "action", STRING | URL),
         ("allow", "allow", STRING),
         ("allowfullscreen", "allowFullScreen", BOOL),
         ("allowpaymentrequest", "allowPaymentRequest", BOOL),
         ("allowusermedia", "allowUserMedia", BOOL),
         ("alt", "alt", STRING),
+        // WAI-ARIA. See: https://w3c.github.io/aria/#ARIAMixin
+        (
+            "aria-activedescendant",
+            "ariaActiveDescendantElement",
+            STRING,
+        ),
+        ("aria-atomic", "ariaAtomic", STRING),
+        ("aria-autocomplete", "ariaAutoComplete", STRING),
+        ("aria-braillelabel", "ariaBrailleLabel", STRING),
+        (
+            "aria-brailleroledescription",
+            "ariaBrailleRoleDescription",
+            STRING,
+        ),
+        ("aria-busy", "ariaBusy", STRING),
+        ("aria-checked", "ariaChecked", STRING),
+        ("aria-colcount", "ariaColCount", STRING),
+        ("aria-colindex", "ariaColIndex", STRING),
+        ("aria-colindextext", "ariaColIndexText", STRING),
+        ("aria-colspan", "ariaColSpan", STRING),
+        ("aria-controls", "ariaControlsElements", SPACE_SEP | STRING),
+        ("aria-current", "ariaCurrent", STRING),
+        (
+            "aria-describedby",
+            "ariaDescribedByElements",
+            SPACE_SEP | STRING,
+        ),
+        ("aria-description", "ariaDescription", STRING),
+        ("aria-details", "ariaDetailsElements", SPACE_SEP | STRING),
+        ("aria-disabled", "ariaDisabled", STRING),
+        (
+            "aria-errormessage",
+            "ariaErrorMessageElements",
+            SPACE_SEP | STRING,
+        ),
+        ("aria-expanded", "ariaExpanded", STRING),
+        ("aria-flowto", "ariaFlowToElements", SPACE_SEP | STRING),
+        ("aria-haspopup", "ariaHasPopup", STRING),
+        ("aria-hidden", "ariaHidden", STRING),
+        ("aria-invalid", "ariaInvalid", STRING),
+        ("aria-keyshortcuts", "ariaKeyShortcuts", SPACE_SEP | STRING),
+        ("aria-label", "ariaLabel", STRING),
+        (
+            "aria-labelledby",
+            "ariaLabelledByElements",
+            SPACE_SEP | STRING,
+        ),
+        ("aria-level", "ariaLevel", STRING),
+        ("aria-live", "ariaLive", STRING),
+        ("aria-modal", "ariaModal", STRING),
+        ("aria-multiline", "ariaMultiLine", STRING),
+        ("aria-multiselectable", "ariaMultiSelectable", STRING),
+        ("aria-orientation", "ariaOrientation", STRING),
+        ("aria-owns", "ariaOwnsElements", SPACE_SEP | STRING),
+        ("aria-placeholder", "ariaPlaceholder", STRING),
+        ("aria-posinset", "ariaPosInSet", STRING),
+        ("aria-pressed", "ariaPressed", STRING),
+        ("aria-readonly", "ariaReadOnly", STRING),
+        ("aria-relevant", "ariaRelevant", SPACE_SEP | STRING),
+        ("aria-required", "ariaRequired", STRING),
+        ("aria-roledescription", "ariaRoleDescription", STRING),
+        ("aria-rowcount", "ariaRowCount", STRING),
+        ("aria-rowindex", "ariaRowIndex", STRING),
+        ("aria-rowindextext", "ariaRowIndexText", STRING),
+        ("aria-rowspan", "ariaRowSpan", STRING),
+        ("aria-selected", "ariaSelected", STRING),
+        ("aria-setsize", "ariaSetSize", STRING),
+        ("aria-sort", "ariaSort", STRING),
+        ("aria-valuemax", "ariaValueMax", STRING),
+        ("aria-valuemin", "ariaValueMin", STRING),
+        ("aria-valuenow", "ariaValueNow", STRING),
+        ("aria-valuetext", "ariaValueText", STRING),
         ("as", "as", STRING),
         ("async", "async", BOOL),
         ("autocapitalize", "autoCapitalize", STRING),
@@ -181,7 +739,7 @@ pub mod attrs {
         ("capture", "capture", BOOL),
         ("charset", "charSet", STRING),
         ("checked", "checked", BOOL),
-        ("cite", "cite", STRING),
+        ("cite", "cite", STRING | URL),
         ("class", "className", SPACE_SEP | STRING),
         ("cols", "cols", NUMBER),
         ("colspan", "colSpan", STRING),
@@ -195,7 +753,7 @@ pub mod attrs {
         ("controlslist", "controlsList", SPACE_SEP | STRING),
         ("coords", "coords", COMMA_SEP | STRING),
         ("crossorigin", "crossOrigin", STRING),
-        ("data", "data", STRING),
+        ("data", "data", STRING | URL),
         ("datetime", "dateTime", STRING),
         ("decoding", "decoding", STRING),
         ("default", "default", BOOL),
@@ -208,7 +766,7 @@ pub mod attrs {
         ("enctype", "encType", STRING),
         ("enterkeyhint", "enterKeyHint", STRING),
         ("form", "form", STRING),
-        ("formaction", "formAction", STRING),
+        ("formaction", "formAction", STRING | URL),
         ("formenctype", "formEncType", STRING),
         ("formmethod", "formMethod", STRING),
         ("formnovalidate", "formNoValidate", BOOL),
@@ -217,22 +775,22 @@ pub mod attrs {
         ("height", "height", NUMBER),
         ("hidden", "hidden", BOOL),
         ("high", "high", NUMBER),
-        ("href", "href", STRING),
+        ("href", "href", STRING | URL),
         ("hreflang", "hrefLang", STRING),
         ("for", "htmlFor", SPACE_SEP | STRING),
         ("http-equiv", "httpEquiv", SPACE_SEP | STRING),
         ("id", "id", STRING),
         ("imagesizes", "imageSizes", STRING),
-        ("imagesrcset", "imageSrcSet", COMMA_SEP | STRING),
+        ("imagesrcset", "imageSrcSet", COMMA_SEP | STRING | URL),
         ("inputmode", "inputMode", STRING),
         ("integrity", "integrity", STRING),
         ("is", "is", STRING),
         ("ismap", "isMap", BOOL),
-        ("itemid", "itemId", STRING),
+        ("itemid", "itemId", STRING | URL),
         ("itemprop", "itemProp", SPACE_SEP | STRING),
         ("itemref", "itemRef", SPACE_SEP | STRING),
         ("itemscope", "itemScope", BOOL),
-        ("itemtype", "itemType", SPACE_SEP | STRING),
+        ("itemtype", "itemType", SPACE_SEP | STRING | URL),
         ("kind", "kind", STRING),
         ("label", "label", STRING),
         ("lang", "lang", STRING),
@@ -241,7 +799,7 @@ pub mod attrs {
         ("loading", "loading", STRING),
         ("loop", "loop", BOOL),
         ("low", "low", NUMBER),
-        ("manifest", "manifest", STRING),
+        ("manifest", "manifest", STRING | URL),
         ("max", "max", STRING),
         ("maxlength", "maxLength", NUMBER),
         ("media", "media", STRING),
@@ -344,16 +902,17 @@ pub mod attrs {
         ("open", "open", BOOL),
         ("optimum", "optimum", NUMBER),
         ("pattern", "pattern", STRING),
-        ("ping", "ping", SPACE_SEP | STRING),
+        ("ping", "ping", SPACE_SEP | STRING | URL),
         ("placeholder", "placeholder", STRING),
         ("playsinline", "playsInline", BOOL),
-        ("poster", "poster", STRING),
+        ("poster", "poster", STRING | URL),
         ("preload", "preload", STRING),
         ("readonly", "readOnly", BOOL),
         ("referrerpolicy", "referrerPolicy", STRING),
         ("rel", "rel", SPACE_SEP | STRING),
         ("required", "required", BOOL),
         ("reversed", "reversed", BOOL),
+        ("role", "role", SPACE_SEP | STRING),
         ("rows", "rows", NUMBER),
         ("rowspan", "rowSpan", NUMBER),
         ("sandbox", "sandbox", SPACE_SEP | STRING),
@@ -367,10 +926,10 @@ pub mod attrs {
         ("slot", "slot", STRING),
         ("span", "span", NUMBER),
         ("spellcheck", "spellCheck", TRUE | FALSE),
-        ("src", "src", STRING),
+        ("src", "src", STRING | URL),
         ("srcdoc", "srcDoc", STRING),
         ("srclang", "srcLang", STRING),
-        ("srcset", "srcSet", COMMA_SEP | STRING),
+        ("srcset", "srcSet", COMMA_SEP | STRING | URL),
         ("start", "start", NUMBER),
         ("step", "step", STRING),
         ("style", "style", STRING),
@@ -388,9 +947,9 @@ pub mod attrs {
         // See: https://html.spec.whatwg.org/#other-elements,-attributes-and-apis
         ("align", "align", STRING), // Several. Use CSS `text-align` instead,
         ("alink", "aLink", STRING), // `<body>`. Use CSS `a:active {color}` instead
-        ("archive", "archive", SPACE_SEP | STRING), // `<object>`. List of URIs to archives
+        ("archive", "archive", SPACE_SEP | STRING | URL), // `<object>`. List of URIs to archives
         ("axis", "axis", STRING),   // `<td>` and `<th>`. Use `scope` on `<th>`
-        ("background", "background", STRING), // `<body>`. Use CSS `background-image` instead
+        ("background", "background", STRING | URL), // `<body>`. Use CSS `background-image` instead
         ("bgcolor", "bgColor", STRING), // `<body>` and table elements. Use CSS `background-color` instead
         ("border", "border", NUMBER),   // `<table>`. Use CSS `border-width` instead,
         ("bordercolor", "borderColor", STRING), // `<table>`. Use CSS `border-color` instead,
@@ -399,10 +958,10 @@ pub mod attrs {
         ("cellspacing", "cellSpacing", STRING), // `<table>`
         ("char", "char", STRING), // Several table elements. When `align=char`, sets the character to align on
         ("charoff", "charOff", STRING), // Several table elements. When `char`, offsets the alignment
-        ("classid", "classId", STRING), // `<object>`
+        ("classid", "classId", STRING | URL), // `<object>`
         ("clear", "clear", STRING),     // `<br>`. Use CSS `clear` instead
         ("code", "code", STRING),       // `<object>`
-        ("codebase", "codeBase", STRING), // `<object>`
+        ("codebase", "codeBase", STRING | URL), // `<object>`
         ("codetype", "codeType", STRING), // `<object>`
         ("color", "color", STRING),     // `<font>` and `<hr>`. Use CSS instead
         ("compact", "compact", BOOL),   // Lists. Use CSS to reduce space between items instead
@@ -414,8 +973,8 @@ pub mod attrs {
         ("hspace", "hSpace", NUMBER),   // `<img>` and `<object>`
         ("leftmargin", "leftMargin", NUMBER), // `<body>`
         ("link", "link", STRING),       // `<body>`. Use CSS `a:link {color: *}` instead
-        ("longdesc", "longDesc", STRING), // `<frame>`, `<iframe>`, and `<img>`. Use an `<a>`
-        ("lowsrc", "lowSrc", STRING),   // `<img>`. Use a `<picture>`
+        ("longdesc", "longDesc", STRING | URL), // `<frame>`, `<iframe>`, and `<img>`. Use an `<a>`
+        ("lowsrc", "lowSrc", STRING | URL), // `<img>`. Use a `<picture>`
         ("marginheight", "marginHeight", NUMBER), // `<body>`
         ("marginwidth", "marginWidth", NUMBER), // `<body>`
         ("noresize", "noResize", BOOL), // `<frame>`
@@ -423,7 +982,7 @@ pub mod attrs {
         ("noshade", "noShade", BOOL), // `<hr>`. Use background-color and height instead of borders
         ("nowrap", "noWrap", BOOL),   // `<td>` and `<th>`
         ("object", "object", STRING), // `<applet>`
-        ("profile", "profile", STRING), // `<head>`
+        ("profile", "profile", STRING | URL), // `<head>`
         ("prompt", "prompt", STRING), // `<isindex>`
         ("rev", "rev", STRING),       // `<link>`
         ("rightmargin", "rightMargin", NUMBER), // `<body>`
@@ -452,3 +1011,284 @@ pub mod attrs {
         ("unselectable", "unselectable", STRING),
     ];
 }
+
+pub mod idl_types {
+    // This import path is altered because this file is included from build.rs
+    use crate::idl_type::IdlType::{self, *};
+
+    /// The IDL type of attributes that are not listed in `DEFS`.
+    /// Boolean attributes are `boolean` and event handler attributes are `EventHandler`.
+    pub const DEFAULT: Option<IdlType> = Some(DomString);
+
+    /// IDL types of reflecting properties, `None` when not reflected.
+    /// Where the type depends on the element, the type of the most common one is used.
+    /// See: https://html.spec.whatwg.org/#reflecting-content-attributes-in-idl-attributes
+    pub const DEFS: &[(Option<IdlType>, &[&str])] = &[
+        (
+            Some(UsvString),
+            &[
+                "action",
+                "cite",
+                "data",
+                "formaction",
+                "href",
+                "imagesrcset",
+                "longdesc",
+                "lowsrc",
+                "ping",
+                "poster",
+                "src",
+                "srcset",
+            ],
+        ),
+        (Some(Boolean), &["draggable", "spellcheck", "translate"]),
+        (Some(Long), &["maxlength", "minlength", "start", "tabindex"]),
+        (
+            Some(UnsignedLong),
+            &[
+                "cols", "colspan", "height", "hspace", "rows", "rowspan", "size", "span", "vspace",
+                "width",
+            ],
+        ),
+        (Some(Double), &["high", "low", "optimum"]),
+        (Some(DomTokenList), &["controlslist", "sandbox"]),
+        (Some(CssStyleDeclaration), &["style"]),
+        (Some(DomString), &["capture"]),
+        // Reflected as element references.
+        (
+            None,
+            &[
+                "aria-activedescendant",
+                "aria-controls",
+                "aria-describedby",
+                "aria-details",
+                "aria-errormessage",
+                "aria-flowto",
+                "aria-labelledby",
+                "aria-owns",
+            ],
+        ),
+        // Not reflected.
+        (
+            None,
+            &[
+                "allowtransparency",
+                "allowusermedia",
+                "autocorrect",
+                "autosave",
+                "bordercolor",
+                "bottommargin",
+                "classid",
+                "is",
+                "itemid",
+                "itemprop",
+                "itemref",
+                "itemtype",
+                "language",
+                "leftmargin",
+                "manifest",
+                "prefix",
+                "profile",
+                "prompt",
+                "property",
+                "results",
+                "rightmargin",
+                "scoped",
+                "seamless",
+                "security",
+                "topmargin",
+                "typemustmatch",
+                "unselectable",
+            ],
+        ),
+    ];
+}
+
+pub mod statuses {
+    // This import path is altered because this file is included from build.rs
+    use crate::status::Status::{self, *};
+
+    /// Tags that are not current, with a replacement hint. Tags not listed are `Current`.
+    /// See: https://html.spec.whatwg.org/#non-conforming-features
+    pub const TAGS: &[(&str, Status, Option<&str>)] = &[
+        ("acronym", Obsolete, Some("Use `<abbr>`")),
+        ("applet", Obsolete, Some("Use `<embed>` or `<object>`")),
+        ("basefont", Obsolete, Some("Use CSS `font` properties")),
+        ("bgsound", Obsolete, Some("Use `<audio>`")),
+        ("big", Obsolete, Some("Use CSS `font-size`")),
+        ("blink", Obsolete, Some("Use CSS animations")),
+        (
+            "center",
+            Obsolete,
+            Some("Use CSS `text-align` or `margin: auto`"),
+        ),
+        ("command", Obsolete, Some("Use `<button>`")),
+        ("content", Obsolete, Some("Use `<slot>`")),
+        ("dir", Obsolete, Some("Use `<ul>`")),
+        ("element", Obsolete, Some("Use `customElements.define()`")),
+        ("font", Obsolete, Some("Use CSS `font` properties")),
+        ("frame", Obsolete, Some("Use `<iframe>` and CSS")),
+        ("frameset", Obsolete, Some("Use `<iframe>` and CSS")),
+        ("image", Obsolete, Some("Use `<img>`")),
+        (
+            "isindex",
+            Obsolete,
+            Some("Use a `<form>` with a text `<input>`"),
+        ),
+        ("keygen", Obsolete, Some("Use the Web Cryptography API")),
+        ("listing", Obsolete, Some("Use `<pre>` and `<code>`")),
+        ("marquee", Obsolete, Some("Use CSS animations")),
+        ("menuitem", Obsolete, Some("Use `<button>`")),
+        ("multicol", Obsolete, Some("Use CSS `columns`")),
+        ("nextid", Obsolete, Some("Use GUIDs")),
+        ("nobr", Obsolete, Some("Use CSS `white-space: nowrap`")),
+        (
+            "noembed",
+            Obsolete,
+            Some("Use `<object>` with fallback content"),
+        ),
+        ("noframes", Obsolete, Some("Use `<iframe>` and CSS")),
+        (
+            "param",
+            Obsolete,
+            Some("Use the `data` attribute of `<object>`"),
+        ),
+        (
+            "plaintext",
+            Obsolete,
+            Some("Use the `text/plain` MIME type"),
+        ),
+        (
+            "rb",
+            Obsolete,
+            Some("Put the ruby base directly in `<ruby>`"),
+        ),
+        (
+            "rbc",
+            Obsolete,
+            Some("Put the ruby base directly in `<ruby>`"),
+        ),
+        ("rtc", Obsolete, Some("Use nested `<ruby>` elements")),
+        ("shadow", Obsolete, Some("Use `<slot>`")),
+        ("spacer", Obsolete, Some("Use CSS")),
+        ("strike", Obsolete, Some("Use `<del>` or `<s>`")),
+        (
+            "tt",
+            Obsolete,
+            Some("Use `<code>`, `<kbd>`, `<samp>` or `<var>`"),
+        ),
+        ("xmp", Obsolete, Some("Use `<pre>` and `<code>`")),
+    ];
+
+    /// Attributes that are not current on any element, with a replacement hint.
+    /// Attributes that are obsolete only on some elements, e.g. `name` on `<a>`, are `Current`.
+    /// Obsolete but conforming features, e.g. `border` on `<img>`, are `Deprecated`.
+    /// See: https://html.spec.whatwg.org/#non-conforming-features
+    pub const ATTRS: &[(&str, Status, Option<&str>)] = &[
+        ("align", Obsolete, Some("Use CSS `text-align`")),
+        ("alink", Obsolete, Some("Use CSS `a:active {color}`")),
+        (
+            "allowpaymentrequest",
+            Obsolete,
+            Some("Use `allow=\"payment\"`"),
+        ),
+        ("allowtransparency", NonStandard, None),
+        (
+            "allowusermedia",
+            Obsolete,
+            Some("Use `allow` with `camera` and `microphone`"),
+        ),
+        ("archive", Obsolete, Some("Use `data` and `type`")),
+        ("autosave", NonStandard, None),
+        ("axis", Obsolete, Some("Use `scope` on `<th>`")),
+        ("background", Obsolete, Some("Use CSS `background-image`")),
+        ("bgcolor", Obsolete, Some("Use CSS `background-color`")),
+        ("border", Deprecated, Some("Use CSS `border-width`")),
+        ("bordercolor", NonStandard, Some("Use CSS `border-color`")),
+        ("bottommargin", NonStandard, Some("Use CSS `margin-bottom`")),
+        ("cellpadding", Obsolete, Some("Use CSS `padding`")),
+        ("cellspacing", Obsolete, Some("Use CSS `border-spacing`")),
+        ("char", Obsolete, Some("Use CSS")),
+        ("charoff", Obsolete, Some("Use CSS")),
+        ("classid", Obsolete, Some("Use `data` and `type`")),
+        ("clear", Obsolete, Some("Use CSS `clear`")),
+        ("code", Obsolete, Some("Use `data` and `type`")),
+        ("codebase", Obsolete, Some("Use `data` and `type`")),
+        ("codetype", Obsolete, Some("Use `data` and `type`")),
+        ("color", Obsolete, Some("Use CSS `color`")),
+        ("compact", Obsolete, Some("Use CSS")),
+        ("declare", Obsolete, Some("Repeat the `<object>` element")),
+        ("event", Obsolete, Some("Use DOM events")),
+        ("face", Obsolete, Some("Use CSS `font-family`")),
+        ("frame", Obsolete, Some("Use CSS `border`")),
+        ("frameborder", Obsolete, Some("Use CSS `border`")),
+        ("hspace", Obsolete, Some("Use CSS `margin`")),
+        (
+            "language",
+            Deprecated,
+            Some("Use `type`, or omit it for JavaScript"),
+        ),
+        ("leftmargin", NonStandard, Some("Use CSS `margin-left`")),
+        ("link", Obsolete, Some("Use CSS `a:link {color}`")),
+        ("longdesc", Obsolete, Some("Use an `<a>`")),
+        ("lowsrc", Obsolete, Some("Use `<picture>` or `srcset`")),
+        ("manifest", Obsolete, Some("Use service workers")),
+        ("marginheight", Obsolete, Some("Use CSS `margin`")),
+        ("marginwidth", Obsolete, Some("Use CSS `margin`")),
+        ("nohref", Obsolete, Some("Omit `href`")),
+        ("noresize", Obsolete, Some("Use `<iframe>` and CSS")),
+        (
+            "noshade",
+            Obsolete,
+            Some("Use CSS `background-color` and `height`"),
+        ),
+        ("nowrap", Obsolete, Some("Use CSS `white-space: nowrap`")),
+        ("object", Obsolete, Some("Use `data` and `type`")),
+        ("profile", Obsolete, Some("Use `<link>` or `<meta name>`")),
+        ("prompt", Obsolete, Some("Use a `<label>`")),
+        ("results", NonStandard, None),
+        (
+            "rev",
+            Obsolete,
+            Some("Use `rel` with the opposite link type"),
+        ),
+        ("rightmargin", NonStandard, Some("Use CSS `margin-right`")),
+        ("rules", Obsolete, Some("Use CSS `border`")),
+        ("scheme", Obsolete, Some("Put the scheme in the value")),
+        (
+            "scoped",
+            Obsolete,
+            Some("Use selectors that match the subtree"),
+        ),
+        (
+            "scrolling",
+            Obsolete,
+            Some("Use CSS `overflow` in the framed document"),
+        ),
+        ("seamless", Obsolete, None),
+        ("security", NonStandard, Some("Use `sandbox`")),
+        (
+            "standby",
+            Obsolete,
+            Some("Load the resource without a progress message"),
+        ),
+        ("summary", Obsolete, Some("Use `<caption>`")),
+        ("text", Obsolete, Some("Use CSS `color`")),
+        ("topmargin", NonStandard, Some("Use CSS `margin-top`")),
+        (
+            "typemustmatch",
+            Obsolete,
+            Some("Check the type on the server"),
+        ),
+        (
+            "unselectable",
+            NonStandard,
+            Some("Use CSS `user-select: none`"),
+        ),
+        ("valign", Obsolete, Some("Use CSS `vertical-align`")),
+        ("valuetype", Obsolete, Some("Use `name` and `value`")),
+        ("version", Obsolete, Some("Use a doctype")),
+        ("vlink", Obsolete, Some("Use CSS `a:visited {color}`")),
+        ("vspace", Obsolete, Some("Use CSS `margin`")),
+    ];
+}
@@ -97,6 +97,222 @@ pub mod tags {
     ];
 }
 
+pub mod tag_types {
+    // This import path is altered because this file is included from build.rs
+    use crate::tag_type::{flags::*, Flags};
+
+    /// Flags for sets of tags.
+    pub const DEFS: &[(Flags, &[&str])] = &[
+        // Tree construction. See: https://html.spec.whatwg.org/#special
+        (SPECIAL | SCOPE, &["desc", "foreignObject", "title"]),
+        // Element categories. See: https://www.w3.org/TR/SVG2/intro.html#TermAnimationElement
+        (
+            SVG_ANIMATION,
+            &[
+                "animate",
+                "animateColor",
+                "animateMotion",
+                "animateTransform",
+                "discard",
+                "set",
+            ],
+        ),
+        (
+            SVG_CONTAINER,
+            &[
+                "a",
+                "clipPath",
+                "defs",
+                "g",
+                "glyph",
+                "marker",
+                "mask",
+                "missing-glyph",
+                "pattern",
+                "svg",
+                "switch",
+                "symbol",
+            ],
+        ),
+        (SVG_DESCRIPTIVE, &["desc", "metadata", "title"]),
+        (
+            SVG_FILTER_PRIMITIVE,
+            &[
+                "feBlend",
+                "feColorMatrix",
+                "feComponentTransfer",
+                "feComposite",
+                "feConvolveMatrix",
+                "feDiffuseLighting",
+                "feDisplacementMap",
+                "feDropShadow",
+                "feFlood",
+                "feGaussianBlur",
+                "feImage",
+                "feMerge",
+                "feMorphology",
+                "feOffset",
+                "feSpecularLighting",
+                "feTile",
+                "feTurbulence",
+            ],
+        ),
+        (SVG_GRADIENT, &["linearGradient", "radialGradient"]),
+        (
+            SVG_GRAPHICS,
+            &[
+                "audio",
+                "canvas",
+                "circle",
+                "ellipse",
+                "foreignObject",
+                "iframe",
+                "image",
+                "line",
+                "path",
+                "polygon",
+                "polyline",
+                "rect",
+                "text",
+                "textPath",
+                "tspan",
+                "video",
+            ],
+        ),
+        (
+            SVG_LIGHT_SOURCE,
+            &["feDistantLight", "fePointLight", "feSpotLight"],
+        ),
+        (
+            SVG_PAINT_SERVER,
+            &["linearGradient", "pattern", "radialGradient", "solidColor"],
+        ),
+        (
+            SVG_SHAPE,
+            &[
+                "circle", "ellipse", "line", "path", "polygon", "polyline", "rect",
+            ],
+        ),
+        (SVG_STRUCTURAL, &["defs", "g", "svg", "symbol", "use"]),
+        (
+            SVG_TEXT_CONTENT,
+            &["altGlyph", "text", "textPath", "tref", "tspan"],
+        ),
+        (
+            SVG_TEXT_CONTENT_CHILD,
+            &["altGlyph", "textPath", "tref", "tspan"],
+        ),
+    ];
+}
+
+pub mod interfaces {
+    /// DOM interfaces of tags. See: https://www.w3.org/TR/SVG2/idlindex.html
+    ///
+    /// Tags without an interface of their own, e.g. SVG Tiny elements
+    /// and HTML elements in the SVG namespace, are `SVGElement`.
+    pub const DEFS: &[(&str, &[&str])] = &[
+        ("SVGAElement", &["a"]),
+        ("SVGAltGlyphElement", &["altGlyph"]),
+        ("SVGAltGlyphDefElement", &["altGlyphDef"]),
+        ("SVGAltGlyphItemElement", &["altGlyphItem"]),
+        ("SVGAnimateElement", &["animate"]),
+        ("SVGAnimateColorElement", &["animateColor"]),
+        ("SVGAnimateMotionElement", &["animateMotion"]),
+        ("SVGAnimateTransformElement", &["animateTransform"]),
+        ("SVGCircleElement", &["circle"]),
+        ("SVGClipPathElement", &["clipPath"]),
+        ("SVGColorProfileElement", &["color-profile"]),
+        ("SVGCursorElement", &["cursor"]),
+        ("SVGDefsElement", &["defs"]),
+        ("SVGDescElement", &["desc"]),
+        ("SVGDiscardElement", &["discard"]),
+        ("SVGEllipseElement", &["ellipse"]),
+        ("SVGFEBlendElement", &["feBlend"]),
+        ("SVGFEColorMatrixElement", &["feColorMatrix"]),
+        ("SVGFEComponentTransferElement", &["feComponentTransfer"]),
+        ("SVGFECompositeElement", &["feComposite"]),
+        ("SVGFEConvolveMatrixElement", &["feConvolveMatrix"]),
+        ("SVGFEDiffuseLightingElement", &["feDiffuseLighting"]),
+        ("SVGFEDisplacementMapElement", &["feDisplacementMap"]),
+        ("SVGFEDistantLightElement", &["feDistantLight"]),
+        ("SVGFEDropShadowElement", &["feDropShadow"]),
+        ("SVGFEFloodElement", &["feFlood"]),
+        ("SVGFEFuncAElement", &["feFuncA"]),
+        ("SVGFEFuncBElement", &["feFuncB"]),
+        ("SVGFEFuncGElement", &["feFuncG"]),
+        ("SVGFEFuncRElement", &["feFuncR"]),
+        ("SVGFEGaussianBlurElement", &["feGaussianBlur"]),
+        ("SVGFEImageElement", &["feImage"]),
+        ("SVGFEMergeElement", &["feMerge"]),
+        ("SVGFEMergeNodeElement", &["feMergeNode"]),
+        ("SVGFEMorphologyElement", &["feMorphology"]),
+        ("SVGFEOffsetElement", &["feOffset"]),
+        ("SVGFEPointLightElement", &["fePointLight"]),
+        ("SVGFESpecularLightingElement", &["feSpecularLighting"]),
+        ("SVGFESpotLightElement", &["feSpotLight"]),
+        ("SVGFETileElement", &["feTile"]),
+        ("SVGFETurbulenceElement", &["feTurbulence"]),
+        ("SVGFilterElement", &["filter"]),
+        ("SVGFontElement", &["font"]),
+        ("SVGFontFaceElement", &["font-face"]),
+        ("SVGFontFaceFormatElement", &["font-face-format"]),
+        ("SVGFontFaceNameElement", &["font-face-name"]),
+        ("SVGFontFaceSrcElement", &["font-face-src"]),
+        ("SVGFontFaceUriElement", &["font-face-uri"]),
+        ("SVGForeignObjectElement", &["foreignObject"]),
+        ("SVGGElement", &["g"]),
+        ("SVGGlyphElement", &["glyph"]),
+        ("SVGGlyphRefElement", &["glyphRef"]),
+        ("SVGHKernElement", &["hkern"]),
+        ("SVGImageElement", &["image"]),
+        ("SVGLineElement", &["line"]),
+        ("SVGLinearGradientElement", &["linearGradient"]),
+        ("SVGMarkerElement", &["marker"]),
+        ("SVGMaskElement", &["mask"]),
+        ("SVGMetadataElement", &["metadata"]),
+        ("SVGMissingGlyphElement", &["missing-glyph"]),
+        ("SVGMPathElement", &["mpath"]),
+        ("SVGPathElement", &["path"]),
+        ("SVGPatternElement", &["pattern"]),
+        ("SVGPolygonElement", &["polygon"]),
+        ("SVGPolylineElement", &["polyline"]),
+        ("SVGRadialGradientElement", &["radialGradient"]),
+        ("SVGRectElement", &["rect"]),
+        ("SVGScriptElement", &["script"]),
+        ("SVGSetElement", &["set"]),
+        ("SVGStopElement", &["stop"]),
+        ("SVGStyleElement", &["style"]),
+        ("SVGSVGElement", &["svg"]),
+        ("SVGSwitchElement", &["switch"]),
+        ("SVGSymbolElement", &["symbol"]),
+        ("SVGTextElement", &["text"]),
+        ("SVGTextPathElement", &["textPath"]),
+        ("SVGTitleElement", &["title"]),
+        ("SVGTRefElement", &["tref"]),
+        ("SVGTSpanElement", &["tspan"]),
+        ("SVGUseElement", &["use"]),
+        ("SVGViewElement", &["view"]),
+        ("SVGVKernElement", &["vkern"]),
+        (
+            "SVGElement",
+            &[
+                "animation",
+                "audio",
+                "canvas",
+                "handler",
+                "iframe",
+                "listener",
+                "prefetch",
+                "solidColor",
+                "tbreak",
+                "textArea",
+                "unknown",
+                "video",
+            ],
+        ),
+    ];
+}
+
 pub mod attrs {
     // This import path is altered because this file is included from build.rs
     use crate::attr_type::flags::*;
@@ -106,151 +322,371 @@ pub mod attrs {
         ("accent-height", "accentHeight", NUMBER),
         ("accumulate", "accumulate", STRING),
         ("additive", "additive", STRING),
-        ("alignment-baseline", "alignmentBaseline", STRING),
+        (
+            "alignment-baseline",
+            "alignmentBaseline",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         ("alphabetic", "alphabetic", NUMBER),
-        ("amplitude", "amplitude", NUMBER),
+        ("amplitude", "amplitude", NUMBER | ANIMATED_ADDITIVE),
         ("arabic-form", "arabicForm", STRING),
+        // WAI-ARIA. See: https://www.w3.org/TR/SVG2/struct.html#WAIARIAAttributes
+        (
+            "aria-activedescendant",
+            "ariaActiveDescendantElement",
+            STRING,
+        ),
+        ("aria-atomic", "ariaAtomic", STRING),
+        ("aria-autocomplete", "ariaAutoComplete", STRING),
+        ("aria-braillelabel", "ariaBrailleLabel", STRING),
+        (
+            "aria-brailleroledescription",
+            "ariaBrailleRoleDescription",
+            STRING,
+        ),
+        ("aria-busy", "ariaBusy", STRING),
+        ("aria-checked", "ariaChecked", STRING),
+        ("aria-colcount", "ariaColCount", STRING),
+        ("aria-colindex", "ariaColIndex", STRING),
+        ("aria-colindextext", "ariaColIndexText", STRING),
+        ("aria-colspan", "ariaColSpan", STRING),
+        ("aria-controls", "ariaControlsElements", SPACE_SEP | STRING),
+        ("aria-current", "ariaCurrent", STRING),
+        (
+            "aria-describedby",
+            "ariaDescribedByElements",
+            SPACE_SEP | STRING,
+        ),
+        ("aria-description", "ariaDescription", STRING),
+        ("aria-details", "ariaDetailsElements", SPACE_SEP | STRING),
+        ("aria-disabled", "ariaDisabled", STRING),
+        (
+            "aria-errormessage",
+            "ariaErrorMessageElements",
+            SPACE_SEP | STRING,
+        ),
+        ("aria-expanded", "ariaExpanded", STRING),
+        ("aria-flowto", "ariaFlowToElements", SPACE_SEP | STRING),
+        ("aria-haspopup", "ariaHasPopup", STRING),
+        ("aria-hidden", "ariaHidden", STRING),
+        ("aria-invalid", "ariaInvalid", STRING),
+        ("aria-keyshortcuts", "ariaKeyShortcuts", SPACE_SEP | STRING),
+        ("aria-label", "ariaLabel", STRING),
+        (
+            "aria-labelledby",
+            "ariaLabelledByElements",
+            SPACE_SEP | STRING,
+        ),
+        ("aria-level", "ariaLevel", STRING),
+        ("aria-live", "ariaLive", STRING),
+        ("aria-modal", "ariaModal", STRING),
+        ("aria-multiline", "ariaMultiLine", STRING),
+        ("aria-multiselectable", "ariaMultiSelectable", STRING),
+        ("aria-orientation", "ariaOrientation", STRING),
+        ("aria-owns", "ariaOwnsElements", SPACE_SEP | STRING),
+        ("aria-placeholder", "ariaPlaceholder", STRING),
+        ("aria-posinset", "ariaPosInSet", STRING),
+        ("aria-pressed", "ariaPressed", STRING),
+        ("aria-readonly", "ariaReadOnly", STRING),
+        ("aria-relevant", "ariaRelevant", SPACE_SEP | STRING),
+        ("aria-required", "ariaRequired", STRING),
+        ("aria-roledescription", "ariaRoleDescription", STRING),
+        ("aria-rowcount", "ariaRowCount", STRING),
+        ("aria-rowindex", "ariaRowIndex", STRING),
+        ("aria-rowindextext", "ariaRowIndexText", STRING),
+        ("aria-rowspan", "ariaRowSpan", STRING),
+        ("aria-selected", "ariaSelected", STRING),
+        ("aria-setsize", "ariaSetSize", STRING),
+        ("aria-sort", "ariaSort", STRING),
+        ("aria-valuemax", "ariaValueMax", STRING),
+        ("aria-valuemin", "ariaValueMin", STRING),
+        ("aria-valuenow", "ariaValueNow", STRING),
+        ("aria-valuetext", "ariaValueText", STRING),
         ("ascent", "ascent", NUMBER),
         ("attributeName", "attributeName", STRING),
         ("attributeType", "attributeType", STRING),
-        ("azimuth", "azimuth", NUMBER),
+        ("azimuth", "azimuth", NUMBER | ANIMATED_ADDITIVE),
         ("bandwidth", "bandwidth", STRING),
-        ("baseline-shift", "baselineShift", STRING),
-        ("baseFrequency", "baseFrequency", STRING),
+        (
+            "baseline-shift",
+            "baselineShift",
+            STRING | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
+        ("baseFrequency", "baseFrequency", STRING | ANIMATED_ADDITIVE),
         ("baseProfile", "baseProfile", STRING),
         ("bbox", "bbox", STRING),
         ("begin", "begin", STRING),
-        ("bias", "bias", NUMBER),
+        ("bias", "bias", NUMBER | ANIMATED_ADDITIVE),
         ("by", "by", STRING),
         ("calcMode", "calcMode", STRING),
         ("cap-height", "capHeight", NUMBER),
-        ("class", "className", SPACE_SEP),
-        ("clip", "clip", STRING),
-        ("clip-path", "clipPath", STRING),
-        ("clipPathUnits", "clipPathUnits", STRING),
-        ("clip-rule", "clipRule", STRING),
-        ("color", "color", STRING),
-        ("color-interpolation", "colorInterpolation", STRING),
+        ("class", "className", SPACE_SEP | ANIMATED_DISCRETE),
+        ("clip", "clip", STRING | PRESENTATION | ANIMATED_DISCRETE),
+        (
+            "clip-path",
+            "clipPath",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        ("clipPathUnits", "clipPathUnits", STRING | ANIMATED_DISCRETE),
+        (
+            "clip-rule",
+            "clipRule",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        ("color", "color", STRING | PRESENTATION | ANIMATED_COLOR),
+        (
+            "color-interpolation",
+            "colorInterpolation",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         (
             "color-interpolation-filters",
             "colorInterpolationFilters",
-            STRING,
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "color-profile",
+            "colorProfile",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "color-rendering",
+            "colorRendering",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
         ),
-        ("color-profile", "colorProfile", STRING),
-        ("color-rendering", "colorRendering", STRING),
         ("content", "content", STRING),
         ("contentScriptType", "contentScriptType", STRING),
         ("contentStyleType", "contentStyleType", STRING),
         ("crossorigin", "crossOrigin", STRING),
-        ("cursor", "cursor", STRING),
-        ("cx", "cx", STRING),
-        ("cy", "cy", STRING),
-        ("d", "d", STRING),
+        (
+            "cursor",
+            "cursor",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        ("cx", "cx", STRING | ANIMATED_ADDITIVE),
+        ("cy", "cy", STRING | ANIMATED_ADDITIVE),
+        ("d", "d", STRING | ANIMATED_ADDITIVE),
         ("datatype", "dataType", STRING),
         ("defaultAction", "defaultAction", STRING),
         ("descent", "descent", NUMBER),
-        ("diffuseConstant", "diffuseConstant", NUMBER),
-        ("direction", "direction", STRING),
-        ("display", "display", STRING),
+        (
+            "diffuseConstant",
+            "diffuseConstant",
+            NUMBER | ANIMATED_ADDITIVE,
+        ),
+        (
+            "direction",
+            "direction",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "display",
+            "display",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         ("dur", "dur", STRING),
-        ("divisor", "divisor", NUMBER),
-        ("dominant-baseline", "dominantBaseline", STRING),
+        ("divisor", "divisor", NUMBER | ANIMATED_ADDITIVE),
+        (
+            "dominant-baseline",
+            "dominantBaseline",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         ("download", "download", BOOL),
-        ("dx", "dx", STRING),
-        ("dy", "dy", STRING),
-        ("edgeMode", "edgeMode", STRING),
+        ("dx", "dx", STRING | ANIMATED_ADDITIVE),
+        ("dy", "dy", STRING | ANIMATED_ADDITIVE),
+        ("edgeMode", "edgeMode", STRING | ANIMATED_DISCRETE),
         ("editable", "editable", STRING),
-        ("elevation", "elevation", NUMBER),
-        ("enable-background", "enableBackground", STRING),
+        ("elevation", "elevation", NUMBER | ANIMATED_ADDITIVE),
+        (
+            "enable-background",
+            "enableBackground",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         ("end", "end", STRING),
         ("event", "event", STRING),
-        ("exponent", "exponent", NUMBER),
+        ("exponent", "exponent", NUMBER | ANIMATED_ADDITIVE),
         (
             "externalResourcesRequired",
             "externalResourcesRequired",
             STRING,
         ),
-        ("fill", "fill", STRING),
-        ("fill-opacity", "fillOpacity", NUMBER),
-        ("fill-rule", "fillRule", STRING),
-        ("filter", "filter", STRING),
+        ("fill", "fill", STRING | PRESENTATION | ANIMATED_COLOR),
+        (
+            "fill-opacity",
+            "fillOpacity",
+            NUMBER | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
+        (
+            "fill-rule",
+            "fillRule",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "filter",
+            "filter",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         ("filterRes", "filterRes", STRING),
-        ("filterUnits", "filterUnits", STRING),
-        ("flood-color", "floodColor", STRING),
-        ("flood-opacity", "floodOpacity", STRING),
+        ("filterUnits", "filterUnits", STRING | ANIMATED_DISCRETE),
+        (
+            "flood-color",
+            "floodColor",
+            STRING | PRESENTATION | ANIMATED_COLOR,
+        ),
+        (
+            "flood-opacity",
+            "floodOpacity",
+            STRING | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
         ("focusable", "focusable", STRING),
         ("focusHighlight", "focusHighlight", STRING),
-        ("font-family", "fontFamily", STRING),
-        ("font-size", "fontSize", STRING),
-        ("font-size-adjust", "fontSizeAdjust", STRING),
-        ("font-stretch", "fontStretch", STRING),
-        ("font-style", "fontStyle", STRING),
-        ("font-variant", "fontVariant", STRING),
-        ("font-weight", "fontWeight", STRING),
+        (
+            "font-family",
+            "fontFamily",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "font-size",
+            "fontSize",
+            STRING | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
+        (
+            "font-size-adjust",
+            "fontSizeAdjust",
+            STRING | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
+        (
+            "font-stretch",
+            "fontStretch",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "font-style",
+            "fontStyle",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "font-variant",
+            "fontVariant",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "font-weight",
+            "fontWeight",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         ("format", "format", STRING),
-        ("fr", "fr", STRING),
+        ("fr", "fr", STRING | ANIMATED_ADDITIVE),
         ("from", "from", STRING),
-        ("fx", "fx", STRING),
-        ("fy", "fy", STRING),
+        ("fx", "fx", STRING | ANIMATED_ADDITIVE),
+        ("fy", "fy", STRING | ANIMATED_ADDITIVE),
         ("g1", "g1", COMMA_SEP),
         ("g2", "g2", COMMA_SEP),
         ("glyph-name", "glyphName", COMMA_SEP),
         (
             "glyph-orientation-horizontal",
             "glyphOrientationHorizontal",
-            STRING,
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
         ),
         (
             "glyph-orientation-vertical",
             "glyphOrientationVertical",
-            STRING,
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
         ),
         ("glyphRef", "glyphRef", STRING),
-        ("gradientTransform", "gradientTransform", STRING),
-        ("gradientUnits", "gradientUnits", STRING),
+        (
+            "gradientTransform",
+            "gradientTransform",
+            STRING | ANIMATED_TRANSFORM,
+        ),
+        ("gradientUnits", "gradientUnits", STRING | ANIMATED_DISCRETE),
         ("handler", "handler", STRING),
         ("hanging", "hanging", NUMBER),
         ("hatchContentUnits", "hatchContentUnits", STRING),
         ("hatchUnits", "hatchUnits", STRING),
-        ("height", "height", STRING),
-        ("href", "href", STRING),
+        ("height", "height", STRING | ANIMATED_ADDITIVE),
+        ("href", "href", STRING | ANIMATED_DISCRETE | URL),
         ("hreflang", "hrefLang", STRING),
         ("horiz-adv-x", "horizAdvX", NUMBER),
         ("horiz-origin-x", "horizOriginX", NUMBER),
         ("horiz-origin-y", "horizOriginY", NUMBER),
         ("id", "id", STRING),
         ("ideographic", "ideographic", NUMBER),
-        ("image-rendering", "imageRendering", STRING),
+        (
+            "image-rendering",
+            "imageRendering",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         ("initialVisibility", "initialVisibility", STRING),
-        ("in", "in", STRING),
-        ("in2", "in2", STRING),
-        ("intercept", "intercept", NUMBER),
+        ("in", "in", STRING | ANIMATED_DISCRETE),
+        ("in2", "in2", STRING | ANIMATED_DISCRETE),
+        ("intercept", "intercept", NUMBER | ANIMATED_ADDITIVE),
         ("k", "k", NUMBER),
-        ("k1", "k1", NUMBER),
-        ("k2", "k2", NUMBER),
-        ("k3", "k3", NUMBER),
-        ("k4", "k4", NUMBER),
-        ("kernelMatrix", "kernelMatrix", COMMA_SEP | SPACE_SEP),
-        ("kernelUnitLength", "kernelUnitLength", STRING),
+        ("k1", "k1", NUMBER | ANIMATED_ADDITIVE),
+        ("k2", "k2", NUMBER | ANIMATED_ADDITIVE),
+        ("k3", "k3", NUMBER | ANIMATED_ADDITIVE),
+        ("k4", "k4", NUMBER | ANIMATED_ADDITIVE),
+        (
+            "kernelMatrix",
+            "kernelMatrix",
+            COMMA_SEP | SPACE_SEP | ANIMATED_ADDITIVE,
+        ),
+        (
+            "kernelUnitLength",
+            "kernelUnitLength",
+            STRING | ANIMATED_ADDITIVE,
+        ),
         ("keyPoints", "keyPoints", STRING),
         ("keySplines", "keySplines", STRING),
         ("keyTimes", "keyTimes", STRING),
-        ("kerning", "kerning", STRING),
+        (
+            "kerning",
+            "kerning",
+            STRING | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
         ("lang", "lang", STRING),
-        ("lengthAdjust", "lengthAdjust", STRING),
-        ("letter-spacing", "letterSpacing", STRING),
-        ("lighting-color", "lightingColor", STRING),
-        ("limitingConeAngle", "limitingConeAngle", NUMBER),
+        ("lengthAdjust", "lengthAdjust", STRING | ANIMATED_DISCRETE),
+        (
+            "letter-spacing",
+            "letterSpacing",
+            STRING | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
+        (
+            "lighting-color",
+            "lightingColor",
+            STRING | PRESENTATION | ANIMATED_COLOR,
+        ),
+        (
+            "limitingConeAngle",
+            "limitingConeAngle",
+            NUMBER | ANIMATED_ADDITIVE,
+        ),
         ("local", "local", STRING),
-        ("marker-end", "markerEnd", STRING),
-        ("marker-mid", "markerMid", STRING),
-        ("marker-start", "markerStart", STRING),
-        ("markerHeight", "markerHeight", STRING),
-        ("markerUnits", "markerUnits", STRING),
-        ("markerWidth", "markerWidth", STRING),
-        ("mask", "mask", STRING),
-        ("maskContentUnits", "maskContentUnits", STRING),
-        ("maskUnits", "maskUnits", STRING),
+        (
+            "marker-end",
+            "markerEnd",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "marker-mid",
+            "markerMid",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "marker-start",
+            "markerStart",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        ("markerHeight", "markerHeight", STRING | ANIMATED_ADDITIVE),
+        ("markerUnits", "markerUnits", STRING | ANIMATED_DISCRETE),
+        ("markerWidth", "markerWidth", STRING | ANIMATED_ADDITIVE),
+        ("mask", "mask", STRING | PRESENTATION | ANIMATED_DISCRETE),
+        (
+            "maskContentUnits",
+            "maskContentUnits",
+            STRING | ANIMATED_DISCRETE,
+        ),
+        ("maskUnits", "maskUnits", STRING | ANIMATED_DISCRETE),
         ("mathematical", "mathematical", STRING),
         ("max", "max", STRING),
         ("media", "media", STRING),
@@ -258,9 +694,9 @@ pub mod attrs {
         ("mediaContentEncodings", "mediaContentEncodings", STRING),
         ("mediaSize", "mediaSize", NUMBER),
         ("mediaTime", "mediaTime", STRING),
-        ("method", "method", STRING),
+        ("method", "method", STRING | ANIMATED_DISCRETE),
         ("min", "min", STRING),
-        ("mode", "mode", STRING),
+        ("mode", "mode", STRING | ANIMATED_DISCRETE),
         ("name", "name", STRING),
         ("nav-down", "navDown", STRING),
         ("nav-down-left", "navDownLeft", STRING),
@@ -272,9 +708,9 @@ pub mod attrs {
         ("nav-up", "navUp", STRING),
         ("nav-up-left", "navUpLeft", STRING),
         ("nav-up-right", "navUpRight", STRING),
-        ("numOctaves", "numOctaves", STRING),
+        ("numOctaves", "numOctaves", STRING | ANIMATED_ADDITIVE),
         ("observer", "observer", STRING),
-        ("offset", "offset", STRING),
+        ("offset", "offset", STRING | ANIMATED_ADDITIVE),
         ("onabort", "onAbort", STRING),
         ("onactivate", "onActivate", STRING),
         ("onafterprint", "onAfterPrint", STRING),
@@ -354,42 +790,74 @@ pub mod attrs {
         ("onvolumechange", "onVolumeChange", STRING),
         ("onwaiting", "onWaiting", STRING),
         ("onzoom", "onZoom", STRING),
-        ("opacity", "opacity", STRING),
-        ("operator", "operator", STRING),
-        ("order", "order", STRING),
-        ("orient", "orient", STRING),
+        (
+            "opacity",
+            "opacity",
+            STRING | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
+        ("operator", "operator", STRING | ANIMATED_DISCRETE),
+        ("order", "order", STRING | ANIMATED_ADDITIVE),
+        ("orient", "orient", STRING | ANIMATED_DISCRETE),
         ("orientation", "orientation", STRING),
         ("origin", "origin", STRING),
-        ("overflow", "overflow", STRING),
+        (
+            "overflow",
+            "overflow",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         ("overlay", "overlay", STRING),
         ("overline-position", "overlinePosition", NUMBER),
         ("overline-thickness", "overlineThickness", NUMBER),
-        ("paint-order", "paintOrder", STRING),
+        (
+            "paint-order",
+            "paintOrder",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         ("panose-1", "panose1", STRING),
         ("path", "path", STRING),
-        ("pathLength", "pathLength", NUMBER),
-        ("patternContentUnits", "patternContentUnits", STRING),
-        ("patternTransform", "patternTransform", STRING),
-        ("patternUnits", "patternUnits", STRING),
+        ("pathLength", "pathLength", NUMBER | ANIMATED_ADDITIVE),
+        (
+            "patternContentUnits",
+            "patternContentUnits",
+            STRING | ANIMATED_DISCRETE,
+        ),
+        (
+            "patternTransform",
+            "patternTransform",
+            STRING | ANIMATED_TRANSFORM,
+        ),
+        ("patternUnits", "patternUnits", STRING | ANIMATED_DISCRETE),
         ("phase", "phase", STRING),
-        ("ping", "ping", SPACE_SEP),
+        ("ping", "ping", SPACE_SEP | URL),
         ("pitch", "pitch", STRING),
         ("playbackorder", "playbackOrder", STRING),
-        ("pointer-events", "pointerEvents", STRING),
-        ("points", "points", STRING),
-        ("pointsAtX", "pointsAtX", NUMBER),
-        ("pointsAtY", "pointsAtY", NUMBER),
-        ("pointsAtZ", "pointsAtZ", NUMBER),
-        ("preserveAlpha", "preserveAlpha", STRING),
-        ("preserveAspectRatio", "preserveAspectRatio", STRING),
-        ("primitiveUnits", "primitiveUnits", STRING),
+        (
+            "pointer-events",
+            "pointerEvents",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        ("points", "points", STRING | ANIMATED_ADDITIVE),
+        ("pointsAtX", "pointsAtX", NUMBER | ANIMATED_ADDITIVE),
+        ("pointsAtY", "pointsAtY", NUMBER | ANIMATED_ADDITIVE),
+        ("pointsAtZ", "pointsAtZ", NUMBER | ANIMATED_ADDITIVE),
+        ("preserveAlpha", "preserveAlpha", STRING | ANIMATED_DISCRETE),
+        (
+            "preserveAspectRatio",
+            "preserveAspectRatio",
+            STRING | ANIMATED_DISCRETE,
+        ),
+        (
+            "primitiveUnits",
+            "primitiveUnits",
+            STRING | ANIMATED_DISCRETE,
+        ),
         ("propagate", "propagate", STRING),
         ("property", "property", COMMA_SEP | SPACE_SEP),
-        ("r", "r", STRING),
-        ("radius", "radius", STRING),
+        ("r", "r", STRING | ANIMATED_ADDITIVE),
+        ("radius", "radius", STRING | ANIMATED_ADDITIVE),
         ("referrerpolicy", "referrerPolicy", STRING),
-        ("refX", "refX", STRING),
-        ("refY", "refY", STRING),
+        ("refX", "refX", STRING | ANIMATED_ADDITIVE),
+        ("refY", "refY", STRING | ANIMATED_ADDITIVE),
         ("rel", "rel", COMMA_SEP | SPACE_SEP),
         ("rev", "rev", COMMA_SEP | SPACE_SEP),
         ("rendering-intent", "renderingIntent", STRING),
@@ -409,40 +877,89 @@ pub mod attrs {
         ("requiredFormats", "requiredFormats", COMMA_SEP | SPACE_SEP),
         ("resource", "resource", STRING),
         ("restart", "restart", STRING),
-        ("result", "result", STRING),
-        ("rotate", "rotate", STRING),
-        ("rx", "rx", STRING),
-        ("ry", "ry", STRING),
-        ("scale", "scale", STRING),
-        ("seed", "seed", STRING),
-        ("shape-rendering", "shapeRendering", STRING),
+        ("result", "result", STRING | ANIMATED_DISCRETE),
+        ("role", "role", SPACE_SEP | STRING),
+        ("rotate", "rotate", STRING | ANIMATED_ADDITIVE),
+        ("rx", "rx", STRING | ANIMATED_ADDITIVE),
+        ("ry", "ry", STRING | ANIMATED_ADDITIVE),
+        ("scale", "scale", STRING | ANIMATED_ADDITIVE),
+        ("seed", "seed", STRING | ANIMATED_ADDITIVE),
+        (
+            "shape-rendering",
+            "shapeRendering",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         ("side", "side", STRING),
-        ("slope", "slope", STRING),
+        ("slope", "slope", STRING | ANIMATED_ADDITIVE),
         ("snapshotTime", "snapshotTime", STRING),
-        ("specularConstant", "specularConstant", NUMBER),
-        ("specularExponent", "specularExponent", NUMBER),
-        ("spreadMethod", "spreadMethod", STRING),
-        ("spacing", "spacing", STRING),
-        ("startOffset", "startOffset", STRING),
-        ("stdDeviation", "stdDeviation", STRING),
+        (
+            "specularConstant",
+            "specularConstant",
+            NUMBER | ANIMATED_ADDITIVE,
+        ),
+        (
+            "specularExponent",
+            "specularExponent",
+            NUMBER | ANIMATED_ADDITIVE,
+        ),
+        ("spreadMethod", "spreadMethod", STRING | ANIMATED_DISCRETE),
+        ("spacing", "spacing", STRING | ANIMATED_DISCRETE),
+        ("startOffset", "startOffset", STRING | ANIMATED_ADDITIVE),
+        ("stdDeviation", "stdDeviation", STRING | ANIMATED_ADDITIVE),
         ("stemh", "stemh", STRING),
         ("stemv", "stemv", STRING),
-        ("stitchTiles", "stitchTiles", STRING),
-        ("stop-color", "stopColor", STRING),
-        ("stop-opacity", "stopOpacity", STRING),
+        ("stitchTiles", "stitchTiles", STRING | ANIMATED_DISCRETE),
+        (
+            "stop-color",
+            "stopColor",
+            STRING | PRESENTATION | ANIMATED_COLOR,
+        ),
+        (
+            "stop-opacity",
+            "stopOpacity",
+            STRING | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
         ("strikethrough-position", "strikethroughPosition", NUMBER),
         ("strikethrough-thickness", "strikethroughThickness", NUMBER),
         ("string", "string", STRING),
-        ("stroke", "stroke", STRING),
-        ("stroke-dasharray", "strokeDashArray", COMMA_SEP | SPACE_SEP),
-        ("stroke-dashoffset", "strokeDashOffset", STRING),
-        ("stroke-linecap", "strokeLineCap", STRING),
-        ("stroke-linejoin", "strokeLineJoin", STRING),
-        ("stroke-miterlimit", "strokeMiterLimit", NUMBER),
-        ("stroke-opacity", "strokeOpacity", NUMBER),
-        ("stroke-width", "strokeWidth", STRING),
-        ("style", "style", STRING),
-        ("surfaceScale", "surfaceScale", NUMBER),
+        ("stroke", "stroke", STRING | PRESENTATION | ANIMATED_COLOR),
+        (
+            "stroke-dasharray",
+            "strokeDashArray",
+            COMMA_SEP | SPACE_SEP | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
+        (
+            "stroke-dashoffset",
+            "strokeDashOffset",
+            STRING | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
+        (
+            "stroke-linecap",
+            "strokeLineCap",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "stroke-linejoin",
+            "strokeLineJoin",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "stroke-miterlimit",
+            "strokeMiterLimit",
+            NUMBER | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
+        (
+            "stroke-opacity",
+            "strokeOpacity",
+            NUMBER | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
+        (
+            "stroke-width",
+            "strokeWidth",
+            STRING | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
+        ("style", "style", STRING | ANIMATED_DISCRETE),
+        ("surfaceScale", "surfaceScale", NUMBER | ANIMATED_ADDITIVE),
         ("syncBehavior", "syncBehavior", STRING),
         ("syncBehaviorDefault", "syncBehaviorDefault", STRING),
         ("syncMaster", "syncMaster", STRING),
@@ -450,56 +967,431 @@ pub mod attrs {
         ("syncToleranceDefault", "syncToleranceDefault", STRING),
         ("systemLanguage", "systemLanguage", COMMA_SEP | SPACE_SEP),
         ("tabindex", "tabIndex", NUMBER),
-        ("tableValues", "tableValues", STRING),
-        ("target", "target", STRING),
-        ("targetX", "targetX", NUMBER),
-        ("targetY", "targetY", NUMBER),
-        ("text-anchor", "textAnchor", STRING),
-        ("text-decoration", "textDecoration", STRING),
-        ("text-rendering", "textRendering", STRING),
-        ("textLength", "textLength", STRING),
+        ("tableValues", "tableValues", STRING | ANIMATED_ADDITIVE),
+        ("target", "target", STRING | ANIMATED_DISCRETE),
+        ("targetX", "targetX", NUMBER | ANIMATED_ADDITIVE),
+        ("targetY", "targetY", NUMBER | ANIMATED_ADDITIVE),
+        (
+            "text-anchor",
+            "textAnchor",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "text-decoration",
+            "textDecoration",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        (
+            "text-rendering",
+            "textRendering",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        ("textLength", "textLength", STRING | ANIMATED_ADDITIVE),
         ("timelinebegin", "timelineBegin", STRING),
         ("title", "title", STRING),
         ("transformBehavior", "transformBehavior", STRING),
-        ("type", "type", STRING),
+        ("type", "type", STRING | ANIMATED_DISCRETE),
         ("typeof", "typeOf", COMMA_SEP | SPACE_SEP),
         ("to", "to", STRING),
-        ("transform", "transform", STRING),
+        (
+            "transform",
+            "transform",
+            STRING | PRESENTATION | ANIMATED_TRANSFORM,
+        ),
         ("u1", "u1", STRING),
         ("u2", "u2", STRING),
         ("underline-position", "underlinePosition", NUMBER),
         ("underline-thickness", "underlineThickness", NUMBER),
         ("unicode", "unicode", STRING),
-        ("unicode-bidi", "unicodeBidi", STRING),
+        (
+            "unicode-bidi",
+            "unicodeBidi",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         ("unicode-range", "unicodeRange", STRING),
         ("units-per-em", "unitsPerEm", NUMBER),
-        ("values", "values", STRING),
+        ("values", "values", STRING | ANIMATED_ADDITIVE),
         ("v-alphabetic", "vAlphabetic", NUMBER),
         ("v-mathematical", "vMathematical", NUMBER),
-        ("vector-effect", "vectorEffect", STRING),
+        (
+            "vector-effect",
+            "vectorEffect",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
         ("v-hanging", "vHanging", NUMBER),
         ("v-ideographic", "vIdeographic", NUMBER),
         ("version", "version", STRING),
         ("vert-adv-y", "vertAdvY", NUMBER),
         ("vert-origin-x", "vertOriginX", NUMBER),
         ("vert-origin-y", "vertOriginY", NUMBER),
-        ("viewBox", "viewBox", STRING),
+        ("viewBox", "viewBox", STRING | ANIMATED_DISCRETE),
         ("viewTarget", "viewTarget", STRING),
-        ("visibility", "visibility", STRING),
-        ("width", "width", STRING),
+        (
+            "visibility",
+            "visibility",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        ("width", "width", STRING | ANIMATED_ADDITIVE),
         ("widths", "widths", STRING),
-        ("word-spacing", "wordSpacing", STRING),
-        ("writing-mode", "writingMode", STRING),
-        ("x", "x", STRING),
-        ("x1", "x1", STRING),
-        ("x2", "x2", STRING),
-        ("xChannelSelector", "xChannelSelector", STRING),
+        (
+            "word-spacing",
+            "wordSpacing",
+            STRING | PRESENTATION | ANIMATED_ADDITIVE,
+        ),
+        (
+            "writing-mode",
+            "writingMode",
+            STRING | PRESENTATION | ANIMATED_DISCRETE,
+        ),
+        ("x", "x", STRING | ANIMATED_ADDITIVE),
+        ("x1", "x1", STRING | ANIMATED_ADDITIVE),
+        ("x2", "x2", STRING | ANIMATED_ADDITIVE),
+        (
+            "xChannelSelector",
+            "xChannelSelector",
+            STRING | ANIMATED_DISCRETE,
+        ),
         ("x-height", "xHeight", NUMBER),
-        ("y", "y", STRING),
-        ("y1", "y1", STRING),
-        ("y2", "y2", STRING),
-        ("yChannelSelector", "yChannelSelector", STRING),
-        ("z", "z", STRING),
+        ("y", "y", STRING | ANIMATED_ADDITIVE),
+        ("y1", "y1", STRING | ANIMATED_ADDITIVE),
+        ("y2", "y2", STRING | ANIMATED_ADDITIVE),
+        (
+            "yChannelSelector",
+            "yChannelSelector",
+            STRING | ANIMATED_DISCRETE,
+        ),
+        ("z", "z", STRING | ANIMATED_ADDITIVE),
         ("zoomAndPan", "zoomAndPan", STRING),
     ];
 }
+
+pub mod idl_types {
+    // This import path is altered because this file is included from build.rs
+    use crate::idl_type::IdlType::{self, *};
+
+    /// The IDL type of attributes that are not listed in `DEFS`, e.g. presentation
+    /// attributes. Event handler attributes are `EventHandler`.
+    pub const DEFAULT: Option<IdlType> = None;
+
+    /// IDL types of reflecting properties, `None` when not reflected.
+    /// Where the type depends on the element, the type of the most common one is used.
+    /// See: https://www.w3.org/TR/SVG2/idlindex.html
+    pub const DEFS: &[(Option<IdlType>, &[&str])] = &[
+        (
+            Some(DomString),
+            &[
+                "crossorigin",
+                "download",
+                "hreflang",
+                "id",
+                "media",
+                "ping",
+                "referrerpolicy",
+                "rel",
+                "title",
+                "type",
+            ],
+        ),
+        // WAI-ARIA, except for attributes reflected as element references.
+        (
+            Some(DomString),
+            &[
+                "aria-atomic",
+                "aria-autocomplete",
+                "aria-braillelabel",
+                "aria-brailleroledescription",
+                "aria-busy",
+                "aria-checked",
+                "aria-colcount",
+                "aria-colindex",
+                "aria-colindextext",
+                "aria-colspan",
+                "aria-current",
+                "aria-description",
+                "aria-disabled",
+                "aria-expanded",
+                "aria-haspopup",
+                "aria-hidden",
+                "aria-invalid",
+                "aria-keyshortcuts",
+                "aria-label",
+                "aria-level",
+                "aria-live",
+                "aria-modal",
+                "aria-multiline",
+                "aria-multiselectable",
+                "aria-orientation",
+                "aria-placeholder",
+                "aria-posinset",
+                "aria-pressed",
+                "aria-readonly",
+                "aria-relevant",
+                "aria-required",
+                "aria-roledescription",
+                "aria-rowcount",
+                "aria-rowindex",
+                "aria-rowindextext",
+                "aria-rowspan",
+                "aria-selected",
+                "aria-setsize",
+                "aria-sort",
+                "aria-valuemax",
+                "aria-valuemin",
+                "aria-valuenow",
+                "aria-valuetext",
+                "role",
+            ],
+        ),
+        (Some(Long), &["tabindex"]),
+        (Some(CssStyleDeclaration), &["style"]),
+        (
+            Some(SvgAnimatedString),
+            &["class", "href", "in", "in2", "result", "target"],
+        ),
+        (
+            Some(SvgAnimatedLength),
+            &[
+                "cx",
+                "cy",
+                "fr",
+                "fx",
+                "fy",
+                "height",
+                "markerHeight",
+                "markerWidth",
+                "r",
+                "refX",
+                "refY",
+                "rx",
+                "ry",
+                "startOffset",
+                "textLength",
+                "width",
+                "x",
+                "x1",
+                "x2",
+                "y",
+                "y1",
+                "y2",
+            ],
+        ),
+        (Some(SvgAnimatedLengthList), &["dx", "dy"]),
+        (
+            Some(SvgAnimatedNumber),
+            &[
+                "amplitude",
+                "azimuth",
+                "bias",
+                "diffuseConstant",
+                "divisor",
+                "elevation",
+                "exponent",
+                "intercept",
+                "k1",
+                "k2",
+                "k3",
+                "k4",
+                "limitingConeAngle",
+                "offset",
+                "pathLength",
+                "pointsAtX",
+                "pointsAtY",
+                "pointsAtZ",
+                "scale",
+                "seed",
+                "slope",
+                "specularConstant",
+                "specularExponent",
+                "surfaceScale",
+                "z",
+            ],
+        ),
+        (
+            Some(SvgAnimatedInteger),
+            &["numOctaves", "targetX", "targetY"],
+        ),
+        (
+            Some(SvgAnimatedNumberList),
+            &["kernelMatrix", "rotate", "tableValues", "values"],
+        ),
+        (
+            Some(SvgAnimatedEnumeration),
+            &[
+                "clipPathUnits",
+                "edgeMode",
+                "filterUnits",
+                "gradientUnits",
+                "lengthAdjust",
+                "markerUnits",
+                "maskContentUnits",
+                "maskUnits",
+                "method",
+                "mode",
+                "operator",
+                "patternContentUnits",
+                "patternUnits",
+                "primitiveUnits",
+                "spacing",
+                "spreadMethod",
+                "stitchTiles",
+                "xChannelSelector",
+                "yChannelSelector",
+            ],
+        ),
+        (
+            Some(SvgAnimatedBoolean),
+            &["externalResourcesRequired", "preserveAlpha"],
+        ),
+        (Some(SvgAnimatedRect), &["viewBox"]),
+        (
+            Some(SvgAnimatedPreserveAspectRatio),
+            &["preserveAspectRatio"],
+        ),
+        (
+            Some(SvgAnimatedTransformList),
+            &["gradientTransform", "patternTransform", "transform"],
+        ),
+        (Some(SvgPointList), &["points"]),
+        (
+            Some(SvgStringList),
+            &["requiredExtensions", "systemLanguage"],
+        ),
+        // Event attributes without an event handler property. `onmousewheel` has a
+        // legacy one in browsers, and runs scripts.
+        (None, &["onactivate", "onfocusin", "onfocusout", "onzoom"]),
+    ];
+}
+
+pub mod statuses {
+    // This import path is altered because this file is included from build.rs
+    use crate::status::Status::{self, *};
+
+    const SVG_FONTS: Option<&str> = Some("Use CSS `@font-face` with a WOFF font");
+
+    /// Tags that are not current, with a replacement hint. Tags not listed are `Current`.
+    /// SVG Tiny 1.2 elements, which SVG 2 does not include, are `Obsolete`.
+    /// See: https://www.w3.org/TR/SVG2/changes.html
+    pub const TAGS: &[(&str, Status, Option<&str>)] = &[
+        ("altGlyph", Obsolete, Some("Use CSS `font-variant`")),
+        ("altGlyphDef", Obsolete, Some("Use CSS `font-variant`")),
+        ("altGlyphItem", Obsolete, Some("Use CSS `font-variant`")),
+        ("animateColor", Obsolete, Some("Use `<animate>`")),
+        (
+            "animation",
+            Obsolete,
+            Some("Use `<foreignObject>` or `<use>`"),
+        ),
+        ("color-profile", Obsolete, Some("Use CSS `@color-profile`")),
+        ("cursor", Obsolete, Some("Use the CSS `cursor` property")),
+        ("font", Obsolete, SVG_FONTS),
+        ("font-face", Obsolete, SVG_FONTS),
+        ("font-face-format", Obsolete, SVG_FONTS),
+        ("font-face-name", Obsolete, SVG_FONTS),
+        ("font-face-src", Obsolete, SVG_FONTS),
+        ("font-face-uri", Obsolete, SVG_FONTS),
+        ("glyph", Obsolete, SVG_FONTS),
+        ("glyphRef", Obsolete, SVG_FONTS),
+        ("handler", Obsolete, Some("Use `<script>` and DOM events")),
+        ("hkern", Obsolete, SVG_FONTS),
+        ("listener", Obsolete, Some("Use `<script>` and DOM events")),
+        ("missing-glyph", Obsolete, SVG_FONTS),
+        (
+            "prefetch",
+            Obsolete,
+            Some("Use `<link rel=\"prefetch\">` in HTML"),
+        ),
+        ("solidColor", Obsolete, Some("Use a color value")),
+        ("tbreak", Obsolete, Some("Use separate `<text>` elements")),
+        (
+            "textArea",
+            Obsolete,
+            Some("Use `<text>` with CSS `inline-size`"),
+        ),
+        ("tref", Obsolete, Some("Copy the text into a `<tspan>`")),
+        ("vkern", Obsolete, SVG_FONTS),
+    ];
+
+    /// Attributes that are not current, with a replacement hint. Attributes not listed are `Current`.
+    /// See: https://www.w3.org/TR/SVG2/changes.html
+    pub const ATTRS: &[(&str, Status, Option<&str>)] = &[
+        ("accent-height", Obsolete, SVG_FONTS),
+        ("alphabetic", Obsolete, SVG_FONTS),
+        ("arabic-form", Obsolete, SVG_FONTS),
+        ("ascent", Obsolete, SVG_FONTS),
+        ("attributeType", Deprecated, Some("Omit it")),
+        ("bandwidth", Obsolete, None),
+        ("baseProfile", Obsolete, Some("Omit it")),
+        ("bbox", Obsolete, SVG_FONTS),
+        ("cap-height", Obsolete, SVG_FONTS),
+        ("clip", Deprecated, Some("Use `clip-path`")),
+        (
+            "contentScriptType",
+            Obsolete,
+            Some("Use `type` on `<script>`"),
+        ),
+        (
+            "contentStyleType",
+            Obsolete,
+            Some("Use `type` on `<style>`"),
+        ),
+        ("descent", Obsolete, SVG_FONTS),
+        ("enable-background", Obsolete, Some("Use CSS `isolation`")),
+        ("externalResourcesRequired", Obsolete, Some("Omit it")),
+        ("filterRes", Obsolete, Some("Omit it")),
+        ("focusable", Obsolete, Some("Use `tabindex`")),
+        ("g1", Obsolete, SVG_FONTS),
+        ("g2", Obsolete, SVG_FONTS),
+        ("glyph-name", Obsolete, SVG_FONTS),
+        (
+            "glyph-orientation-horizontal",
+            Obsolete,
+            Some("Use CSS `text-orientation`"),
+        ),
+        (
+            "glyph-orientation-vertical",
+            Deprecated,
+            Some("Use CSS `text-orientation`"),
+        ),
+        ("glyphRef", Obsolete, SVG_FONTS),
+        ("hanging", Obsolete, SVG_FONTS),
+        ("horiz-adv-x", Obsolete, SVG_FONTS),
+        ("horiz-origin-x", Obsolete, SVG_FONTS),
+        ("horiz-origin-y", Obsolete, SVG_FONTS),
+        ("ideographic", Obsolete, SVG_FONTS),
+        ("k", Obsolete, SVG_FONTS),
+        ("kerning", Obsolete, Some("Use CSS `font-kerning`")),
+        ("mathematical", Obsolete, SVG_FONTS),
+        ("overline-position", Obsolete, SVG_FONTS),
+        ("overline-thickness", Obsolete, SVG_FONTS),
+        ("panose-1", Obsolete, SVG_FONTS),
+        (
+            "requiredFeatures",
+            Obsolete,
+            Some("Use `requiredExtensions`, or omit it"),
+        ),
+        ("requiredFonts", Obsolete, None),
+        ("requiredFormats", Obsolete, None),
+        ("slope", Obsolete, SVG_FONTS),
+        ("stemh", Obsolete, SVG_FONTS),
+        ("stemv", Obsolete, SVG_FONTS),
+        ("strikethrough-position", Obsolete, SVG_FONTS),
+        ("strikethrough-thickness", Obsolete, SVG_FONTS),
+        ("u1", Obsolete, SVG_FONTS),
+        ("u2", Obsolete, SVG_FONTS),
+        ("underline-position", Obsolete, SVG_FONTS),
+        ("underline-thickness", Obsolete, SVG_FONTS),
+        ("unicode", Obsolete, SVG_FONTS),
+        ("units-per-em", Obsolete, SVG_FONTS),
+        ("v-alphabetic", Obsolete, SVG_FONTS),
+        ("v-hanging", Obsolete, SVG_FONTS),
+        ("v-ideographic", Obsolete, SVG_FONTS),
+        ("v-mathematical", Obsolete, SVG_FONTS),
+        ("version", Obsolete, Some("Omit it")),
+        ("vert-adv-y", Obsolete, SVG_FONTS),
+        ("vert-origin-x", Obsolete, SVG_FONTS),
+        ("vert-origin-y", Obsolete, SVG_FONTS),
+        ("widths", Obsolete, SVG_FONTS),
+        ("x-height", Obsolete, SVG_FONTS),
+        ("zoomAndPan", Deprecated, Some("Omit it")),
+    ];
+}
@@ -0,0 +1,636 @@
+//!
+//! A lightweight typed DOM, and a parser for HTML fragments.
+//!
+//! The parser builds a tree from the tokens of the [crate::tokenizer], so text,
+//! comments and character references are read like in a browser. It is not a
+//! complete implementation of the HTML tree construction stage. It handles void
+//! elements, implied end tags and switching into and out of SVG, and reports
+//! anything it does not recognize.
+//!
+
+use crate::attr::{AttributeValue, DeserializeAttributeValue};
+use crate::element::Element;
+use crate::html5::optional_tags::{end_tag_rule, EndTagRule};
+use crate::html5::{HtmlAttr, HtmlTag};
+use crate::limits::{Limit, Limits};
+use crate::svg::SvgTag;
+use crate::tokenizer::{
+    EndTag, ErrorCode, ParseError as TokenizerError, Resolved, StartTag, TagAttribute, TokenKind,
+    Tokenizer,
+};
+use crate::{escape, web, Error, IsVoid, LocalName};
+
+///
+/// A node of a DOM tree.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Node {
+    Element(ElementNode),
+    Text(String),
+    Comment(String),
+}
+
+///
+/// An element and its children.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElementNode {
+    pub element: Element,
+    pub children: Vec<Node>,
+}
+
+///
+/// The result of parsing a fragment.
+///
+#[derive(Clone, Debug)]
+pub struct Fragment {
+    pub nodes: Vec<Node>,
+    pub errors: Vec<ParseError>,
+}
+
+///
+/// Something in the input that was not understood, or was recovered from.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The byte offset of the tag or attribute in the input.
+    pub offset: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A tag unknown in the current namespace. Its children are kept.
+    UnknownTag(String),
+    /// An attribute unknown for its element. It is dropped.
+    UnknownAttribute(String),
+    /// An attribute value that does not parse. It is kept as a string.
+    InvalidAttributeValue(String),
+    /// A repeated attribute. Only the first one is kept.
+    DuplicateAttribute(String),
+    /// An end tag without an open element.
+    UnexpectedEndTag(String),
+    /// An element implicitly closed, which has no optional end tag.
+    UnclosedElement(String),
+    /// `<foo/>` for a non-void HTML element. The slash is ignored.
+    SelfClosingNonVoid(String),
+    /// A doctype, which is ignored in fragments.
+    UnexpectedDoctype,
+    /// The input ended inside a tag, which is dropped.
+    EofInTag,
+    /// A tag or attribute beyond the [Limits]. A tag is handled like an unknown
+    /// tag, an attribute is dropped.
+    LimitExceeded(Limit),
+}
+
+///
+/// HTML tags that break out of foreign content.
+///
+/// See: https://html.spec.whatwg.org/#parsing-main-inforeign
+///
+pub(crate) const FOREIGN_BREAKOUT: &[&str] = &[
+    "b",
+    "big",
+    "blockquote",
+    "body",
+    "br",
+    "center",
+    "code",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "embed",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "hr",
+    "i",
+    "img",
+    "li",
+    "listing",
+    "menu",
+    "meta",
+    "nobr",
+    "ol",
+    "p",
+    "pre",
+    "ruby",
+    "s",
+    "small",
+    "span",
+    "strong",
+    "strike",
+    "sub",
+    "sup",
+    "table",
+    "tt",
+    "u",
+    "ul",
+    "var",
+];
+
+///
+/// Parse an HTML fragment, as if it was the content of `<body>`.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::dom::*;
+///
+/// let fragment = parse_fragment(r#"<p class=a>x &amp; y<p>z<svg viewbox="0 0 1 1"><rect/></svg>"#);
+/// assert!(fragment.errors.is_empty());
+/// assert_eq!(
+///     serialize(&fragment.nodes).unwrap(),
+///     r#"<p class="a">x &amp; y</p><p>z<svg viewBox="0 0 1 1"><rect/></svg></p>"#
+/// );
+///
+/// let fragment = parse_fragment("<blink foo=bar>!</blink>");
+/// assert_eq!(
+///     fragment.errors.iter().map(|error| &error.kind).collect::<Vec<_>>(),
+///     vec![&ParseErrorKind::UnknownAttribute("foo".to_string())]
+/// );
+/// ```
+///
+pub fn parse_fragment(input: &str) -> Fragment {
+    parse_fragment_with_limits(input, &Limits::UNLIMITED)
+}
+
+///
+/// Parse an HTML fragment, dropping names and values beyond `limits`.
+///
+/// Example:
+/// ```
+/// use web_ns::dom::*;
+/// use web_ns::limits::{Limit, Limits};
+///
+/// let limits = Limits { max_tokens: 2, max_data_attrs: 1, ..Limits::default() };
+/// let fragment = parse_fragment_with_limits("<p class='a b c' data-x=1 data-y=2>", &limits);
+/// assert_eq!(
+///     fragment.errors.iter().map(|error| &error.kind).collect::<Vec<_>>(),
+///     vec![
+///         &ParseErrorKind::LimitExceeded(Limit::Tokens),
+///         &ParseErrorKind::LimitExceeded(Limit::DataAttrs),
+///     ]
+/// );
+/// assert_eq!(serialize(&fragment.nodes).unwrap(), r#"<p data-x="1"></p>"#);
+/// ```
+///
+pub fn parse_fragment_with_limits(input: &str, limits: &Limits) -> Fragment {
+    let mut parser = Parser {
+        input,
+        limits,
+        stack: vec![],
+        nodes: vec![],
+        errors: vec![],
+    };
+    parser.run();
+    Fragment {
+        nodes: parser.nodes,
+        errors: parser.errors,
+    }
+}
+
+///
+/// Serialize nodes as HTML.
+///
+/// Fails if a text or comment cannot be represented, e.g. `</script>` inside `<script>`.
+///
+pub fn serialize(nodes: &[Node]) -> Result<String, Error> {
+    let mut output = String::new();
+    write_nodes(nodes, &web::Tag::Html5(HtmlTag::Body), &mut output)?;
+    Ok(output)
+}
+
+fn write_nodes(nodes: &[Node], parent: &web::Tag, output: &mut String) -> Result<(), Error> {
+    for node in nodes {
+        match node {
+            Node::Element(ElementNode { element, children }) if children.is_empty() => {
+                output.push_str(&element.to_string());
+            }
+            Node::Element(ElementNode { element, children }) => {
+                output.push_str(&element.start_tag());
+                write_nodes(children, element.tag(), output)?;
+                output.push_str(&element.end_tag().ok_or(Error::InvalidText)?);
+            }
+            Node::Text(text) => output.push_str(&escape::escape_text(parent, text)?),
+            Node::Comment(comment) => {
+                output.push_str("<!--");
+                output.push_str(escape::escape_comment(comment)?);
+                output.push_str("-->");
+            }
+        }
+    }
+    Ok(())
+}
+
+struct Parser<'i> {
+    input: &'i str,
+    limits: &'i Limits,
+    stack: Vec<ElementNode>,
+    nodes: Vec<Node>,
+    errors: Vec<ParseError>,
+}
+
+impl<'i> Parser<'i> {
+    fn run(&mut self) {
+        let mut tokenizer = Tokenizer::new(self.input);
+        let mut reported = 0;
+
+        while let Some(token) = tokenizer.next() {
+            // Errors of the tokenizer come first, they are found before the token is emitted
+            for error in &tokenizer.errors()[reported..] {
+                self.tokenizer_error(error);
+            }
+            reported = tokenizer.errors().len();
+
+            let offset = token.span.start;
+            match token.kind {
+                TokenKind::Doctype(_) => self.error(offset, ParseErrorKind::UnexpectedDoctype),
+                TokenKind::StartTag(start_tag) => self.insert(offset, start_tag),
+                TokenKind::EndTag(EndTag {
+                    name: Resolved::Known(tag),
+                }) => self.end_tag(offset, tag.local_name()),
+                // Unknown tags were already reported at their start tag
+                TokenKind::EndTag(_) => {}
+                TokenKind::Comment(comment) => self.append(Node::Comment(comment)),
+                TokenKind::Text(text) => self.append(Node::Text(text)),
+            }
+        }
+        for error in &tokenizer.errors()[reported..] {
+            self.tokenizer_error(error);
+        }
+
+        while !self.stack.is_empty() {
+            self.close_top(self.input.len());
+        }
+    }
+
+    ///
+    /// Report the errors of the tokenizer which affect the tree.
+    ///
+    fn tokenizer_error(&mut self, error: &TokenizerError) {
+        match error.code {
+            ErrorCode::EofInTag => self.error(error.offset, ParseErrorKind::EofInTag),
+            ErrorCode::DuplicateAttribute => {
+                let rest = &self.input[error.offset..];
+                let first_len = rest.chars().next().map(char::len_utf8).unwrap_or(0);
+                let name_len = rest[first_len..]
+                    .find(|c: char| c.is_ascii_whitespace() || matches!(c, '/' | '>' | '='))
+                    .map(|len| len + first_len)
+                    .unwrap_or(rest.len());
+                let name = rest[..name_len].to_string();
+                self.error(error.offset, ParseErrorKind::DuplicateAttribute(name));
+            }
+            _ => {}
+        }
+    }
+
+    fn insert(&mut self, offset: usize, start_tag: StartTag) {
+        let StartTag {
+            name,
+            attrs,
+            self_closing,
+        } = start_tag;
+        let local_name = name.local_name().to_string();
+
+        if self.in_foreign_content() && FOREIGN_BREAKOUT.contains(&local_name.as_str()) {
+            while self.in_foreign_content() {
+                self.close_top(offset);
+            }
+        }
+
+        if self.limits.check_name(&local_name).is_err() {
+            self.error(offset, ParseErrorKind::LimitExceeded(Limit::NameLength));
+            return;
+        }
+        let tag = match name {
+            Resolved::Known(tag) => tag,
+            Resolved::Unknown(name) => {
+                self.error(offset, ParseErrorKind::UnknownTag(name));
+                return;
+            }
+        };
+
+        if let web::Tag::Html5(html_tag) = &tag {
+            while matches!(
+                self.stack.last().map(|node| node.element.tag()),
+                Some(web::Tag::Html5(open)) if closes(open, html_tag)
+            ) {
+                self.close_top(offset);
+            }
+        }
+
+        let mut element = Element::new(tag.clone());
+        let mut data_attrs = 0;
+        for TagAttribute { name, value, span } in attrs {
+            let attr_offset = span.start;
+            if let Err(Error::LimitExceeded(limit)) = self.limits.check_name(name.local_name()) {
+                self.error(attr_offset, ParseErrorKind::LimitExceeded(limit));
+                continue;
+            }
+            let attr = match name {
+                Resolved::Known(attr) => attr,
+                Resolved::Unknown(name) => {
+                    self.error(attr_offset, ParseErrorKind::UnknownAttribute(name));
+                    continue;
+                }
+            };
+            if let web::Attr::Html5(HtmlAttr::Dataset(_)) = &attr {
+                if data_attrs >= self.limits.max_data_attrs {
+                    self.error(attr_offset, ParseErrorKind::LimitExceeded(Limit::DataAttrs));
+                    continue;
+                }
+                data_attrs += 1;
+            }
+            let value = match attr
+                .deserialize_attribute_value_with_limits(Some(value.as_str()), self.limits)
+            {
+                Ok(value) => value,
+                Err(Error::LimitExceeded(limit)) => {
+                    self.error(attr_offset, ParseErrorKind::LimitExceeded(limit));
+                    continue;
+                }
+                Err(_) => {
+                    let name = attr.local_name().to_string();
+                    self.error(attr_offset, ParseErrorKind::InvalidAttributeValue(name));
+                    AttributeValue::String(value)
+                }
+            };
+            // The namespace of the attribute always matches the tag it was looked up with.
+            let _ = element.set_attr(attr, value);
+        }
+
+        let node = ElementNode {
+            element,
+            children: vec![],
+        };
+        let is_foreign = matches!(tag, web::Tag::Svg(_));
+
+        if tag.is_void() || (self_closing && is_foreign) {
+            self.append(Node::Element(node));
+            return;
+        }
+        if self_closing {
+            self.error(offset, ParseErrorKind::SelfClosingNonVoid(local_name));
+        }
+        // The content of raw text elements is tokenized as text
+        self.stack.push(node);
+    }
+
+    fn end_tag(&mut self, offset: usize, name: &str) {
+        let open = self
+            .stack
+            .iter()
+            .rposition(|node| node.element.tag().local_name().eq_ignore_ascii_case(name));
+
+        match open {
+            Some(index) => {
+                while self.stack.len() > index + 1 {
+                    self.close_top(offset);
+                }
+                self.pop();
+            }
+            None => self.error(offset, ParseErrorKind::UnexpectedEndTag(name.to_string())),
+        }
+    }
+
+    ///
+    /// Close the current element without an end tag.
+    ///
+    fn close_top(&mut self, offset: usize) {
+        let optional = match self.stack.last().map(|node| node.element.tag()) {
+            Some(web::Tag::Html5(tag)) => end_tag_rule(tag).is_some(),
+            _ => false,
+        };
+        if !optional {
+            if let Some(node) = self.stack.last() {
+                let name = node.element.tag().local_name().to_string();
+                self.error(offset, ParseErrorKind::UnclosedElement(name));
+            }
+        }
+        self.pop();
+    }
+
+    fn pop(&mut self) {
+        if let Some(node) = self.stack.pop() {
+            self.append(Node::Element(node));
+        }
+    }
+
+    fn append(&mut self, node: Node) {
+        let children = match self.stack.last_mut() {
+            Some(parent) => &mut parent.children,
+            None => &mut self.nodes,
+        };
+        match (children.last_mut(), node) {
+            (Some(Node::Text(text)), Node::Text(more)) => text.push_str(&more),
+            (_, node) => children.push(node),
+        }
+    }
+
+    ///
+    /// Whether the current node is an SVG element which is not an HTML integration point.
+    ///
+    fn in_foreign_content(&self) -> bool {
+        match self.stack.last().map(|node| node.element.tag()) {
+            Some(web::Tag::Svg(SvgTag::ForeignObject))
+            | Some(web::Tag::Svg(SvgTag::Desc))
+            | Some(web::Tag::Svg(SvgTag::Title)) => false,
+            Some(web::Tag::Svg(_)) => true,
+            _ => false,
+        }
+    }
+
+    fn error(&mut self, offset: usize, kind: ParseErrorKind) {
+        self.errors.push(ParseError { offset, kind });
+    }
+}
+
+///
+/// Whether a start tag of `next` implies the end tag of the open element `open`.
+///
+pub(crate) fn closes(open: &HtmlTag, next: &HtmlTag) -> bool {
+    match end_tag_rule(open) {
+        Some(EndTagRule::FollowedBy(followers, _)) => followers.contains(next),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html5::HtmlAttr;
+
+    fn kinds(fragment: &Fragment) -> Vec<&ParseErrorKind> {
+        fragment.errors.iter().map(|error| &error.kind).collect()
+    }
+
+    fn round_trip(input: &str) -> String {
+        let fragment = parse_fragment(input);
+        assert!(fragment.errors.is_empty(), "{:?}", fragment.errors);
+        serialize(&fragment.nodes).unwrap()
+    }
+
+    #[test]
+    fn tree() {
+        let fragment = parse_fragment("<ul><li>a<li>b</ul><!--c-->");
+        let li = |text: &str| {
+            Node::Element(ElementNode {
+                element: Element::new(web::Tag::Html5(HtmlTag::Li)),
+                children: vec![Node::Text(text.to_string())],
+            })
+        };
+
+        assert!(fragment.errors.is_empty());
+        assert_eq!(
+            fragment.nodes,
+            vec![
+                Node::Element(ElementNode {
+                    element: Element::new(web::Tag::Html5(HtmlTag::Ul)),
+                    children: vec![li("a"), li("b")],
+                }),
+                Node::Comment("c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        assert_eq!(
+            round_trip("<div id=x hidden>a&lt;b &#x41;&#66; &unknown;</div>"),
+            r#"<div id="x" hidden>a&lt;b AB &amp;unknown;</div>"#
+        );
+        assert_eq!(
+            round_trip("<script>if (a</b) {}</script><br/><textarea>&amp;</textarea>"),
+            "<script>if (a</b) {}</script><br><textarea>&amp;</textarea>"
+        );
+        assert_eq!(
+            round_trip(r#"<input value='"quoted"' disabled>"#),
+            r#"<input value="&quot;quoted&quot;" disabled>"#
+        );
+        assert_eq!(round_trip("é<b>ü</b> < x"), "é<b>ü</b> &lt; x");
+        assert_eq!(
+            round_trip("<p title='&copy&amp'>&copy; 2024 &hellip; &eacute; &amp x &lt y</p>"),
+            r#"<p title="©&amp;">© 2024 … é &amp; x &lt; y</p>"#
+        );
+    }
+
+    #[test]
+    fn svg_namespace() {
+        let fragment = parse_fragment(
+            "<svg><clippath><rect/></clippath><foreignObject><p>x</p></foreignObject><p>y</svg>",
+        );
+        assert_eq!(
+            kinds(&fragment),
+            vec![
+                &ParseErrorKind::UnclosedElement("svg".to_string()),
+                &ParseErrorKind::UnexpectedEndTag("svg".to_string()),
+            ]
+        );
+        assert_eq!(
+            serialize(&fragment.nodes).unwrap(),
+            "<svg><clipPath><rect/></clipPath><foreignObject><p>x</p></foreignObject></svg><p>y</p>"
+        );
+
+        let svg = match &fragment.nodes[0] {
+            Node::Element(node) => node,
+            _ => panic!(),
+        };
+        assert_eq!(svg.element.tag(), &web::Tag::Svg(SvgTag::Svg));
+    }
+
+    #[test]
+    fn errors() {
+        let fragment =
+            parse_fragment("<!DOCTYPE html><div id=a ID=b bogus>x</span><my-el>y</my-el><div/>");
+        assert_eq!(
+            kinds(&fragment),
+            vec![
+                &ParseErrorKind::UnexpectedDoctype,
+                &ParseErrorKind::DuplicateAttribute("ID".to_string()),
+                &ParseErrorKind::UnknownAttribute("bogus".to_string()),
+                &ParseErrorKind::UnexpectedEndTag("span".to_string()),
+                &ParseErrorKind::UnknownTag("my-el".to_string()),
+                &ParseErrorKind::SelfClosingNonVoid("div".to_string()),
+                &ParseErrorKind::UnclosedElement("div".to_string()),
+                &ParseErrorKind::UnclosedElement("div".to_string()),
+            ]
+        );
+
+        let div = match &fragment.nodes[0] {
+            Node::Element(node) => &node.element,
+            _ => panic!(),
+        };
+        assert_eq!(div.attr_str(&web::Attr::Html5(HtmlAttr::Id)), Some("a"));
+        assert_eq!(div.attrs().len(), 1);
+        assert_eq!(fragment.errors[1].offset, "<!DOCTYPE html><div id=a ".len());
+
+        assert_eq!(
+            kinds(&parse_fragment("<p")),
+            vec![&ParseErrorKind::EofInTag]
+        );
+    }
+
+    #[test]
+    fn limits() {
+        let limits = Limits {
+            max_name_len: 8,
+            max_value_len: 4,
+            ..Limits::UNLIMITED
+        };
+        let fragment = parse_fragment_with_limits(
+            "<div id=abcde title=abcd data-too-long=x><blockquote>x</blockquote></div>",
+            &limits,
+        );
+        assert_eq!(
+            kinds(&fragment),
+            vec![
+                &ParseErrorKind::LimitExceeded(Limit::ValueLength),
+                &ParseErrorKind::LimitExceeded(Limit::NameLength),
+                &ParseErrorKind::LimitExceeded(Limit::NameLength),
+                &ParseErrorKind::UnexpectedEndTag("blockquote".to_string()),
+            ]
+        );
+        assert_eq!(
+            serialize(&fragment.nodes).unwrap(),
+            r#"<div title="abcd">x</div>"#
+        );
+
+        let limits = Limits {
+            max_name_len: 8,
+            max_data_attrs: 1,
+            ..Limits::UNLIMITED
+        };
+        let fragment = parse_fragment_with_limits("<p data-éééé=1 data-é=2 data-ü=3>", &limits);
+        assert_eq!(
+            kinds(&fragment),
+            vec![
+                &ParseErrorKind::LimitExceeded(Limit::NameLength),
+                &ParseErrorKind::LimitExceeded(Limit::DataAttrs),
+            ]
+        );
+        assert_eq!(serialize(&fragment.nodes).unwrap(), r#"<p data-é="2"></p>"#);
+    }
+
+    #[test]
+    fn non_ascii_attribute_names() {
+        let fragment = parse_fragment("<div é=1 ü>x</div>");
+        assert_eq!(
+            kinds(&fragment),
+            vec![
+                &ParseErrorKind::UnknownAttribute("é".to_string()),
+                &ParseErrorKind::UnknownAttribute("ü".to_string()),
+            ]
+        );
+        assert_eq!(serialize(&fragment.nodes).unwrap(), "<div>x</div>");
+
+        // Must not panic
+        parse_fragment("<svg<</é ");
+    }
+}
@@ -0,0 +1,471 @@
+//!
+//! A typed element: a [web::Tag] and its attributes.
+//!
+
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use crate::attr::{
+    AttributeValue, DeserializeAttributeValue, SerializeAttributeValue, SerializedAttributeValue,
+};
+use crate::escape::escape_attribute_value;
+use crate::html5::HtmlAttr;
+use crate::limits::{Limit, Limits};
+use crate::tag::Tag;
+use crate::{web, AttrByLocalName, Error, IsVoid, LocalName};
+
+///
+/// Attributes keyed by [web::Attr], in insertion order.
+///
+/// Replacing the value of an attribute keeps its position.
+///
+#[derive(Clone, Debug, Default)]
+pub struct AttrMap {
+    entries: Vec<(web::Attr, AttributeValue)>,
+    /// The position of each attribute in `entries`.
+    index: HashMap<web::Attr, usize>,
+}
+
+impl AttrMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, attr: &web::Attr) -> Option<&AttributeValue> {
+        self.position(attr).map(|index| &self.entries[index].1)
+    }
+
+    pub fn get_mut(&mut self, attr: &web::Attr) -> Option<&mut AttributeValue> {
+        match self.position(attr) {
+            Some(index) => Some(&mut self.entries[index].1),
+            None => None,
+        }
+    }
+
+    pub fn contains(&self, attr: &web::Attr) -> bool {
+        self.position(attr).is_some()
+    }
+
+    ///
+    /// Insert or replace the value of `attr`, returning the old value.
+    ///
+    pub fn insert(&mut self, attr: web::Attr, value: AttributeValue) -> Option<AttributeValue> {
+        match self.position(&attr) {
+            Some(index) => Some(std::mem::replace(&mut self.entries[index].1, value)),
+            None => {
+                self.index.insert(attr.clone(), self.entries.len());
+                self.entries.push((attr, value));
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, attr: &web::Attr) -> Option<AttributeValue> {
+        let index = self.index.remove(attr)?;
+        for position in self.index.values_mut() {
+            if *position > index {
+                *position -= 1;
+            }
+        }
+        Some(self.entries.remove(index).1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&web::Attr, &AttributeValue)> + Clone {
+        self.entries.iter().map(|(attr, value)| (attr, value))
+    }
+
+    fn position(&self, attr: &web::Attr) -> Option<usize> {
+        self.index.get(attr).copied()
+    }
+}
+
+impl PartialEq for AttrMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl Eq for AttrMap {}
+
+impl Hash for AttrMap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.entries.hash(state);
+    }
+}
+
+impl std::iter::FromIterator<(web::Attr, AttributeValue)> for AttrMap {
+    fn from_iter<I: IntoIterator<Item = (web::Attr, AttributeValue)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl Extend<(web::Attr, AttributeValue)> for AttrMap {
+    fn extend<I: IntoIterator<Item = (web::Attr, AttributeValue)>>(&mut self, iter: I) {
+        for (attr, value) in iter {
+            self.insert(attr, value);
+        }
+    }
+}
+
+impl IntoIterator for AttrMap {
+    type Item = (web::Attr, AttributeValue);
+    type IntoIter = std::vec::IntoIter<(web::Attr, AttributeValue)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+///
+/// An element with a tag and typed attributes, without children.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::element::Element;
+///
+/// let a = Element::new(web::Tag::Html5(html5::HtmlTag::A))
+///     .with_attr_str("HREF", Some("/?a&b"))?
+///     .with_attr_str("class", Some("x y"))?
+///     .with_attr_str("hidden", None)?;
+///
+/// assert_eq!(a.attr_str_by_name("href"), Some("/?a&b"));
+/// assert_eq!(a.classes(), vec!["x", "y"]);
+/// assert_eq!(a.to_string(), r#"<a href="/?a&amp;b" class="x y" hidden></a>"#);
+/// # Ok::<(), Error>(())
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Element {
+    tag: web::Tag,
+    attrs: AttrMap,
+}
+
+impl Element {
+    pub fn new(tag: web::Tag) -> Self {
+        Self {
+            tag,
+            attrs: AttrMap::new(),
+        }
+    }
+
+    ///
+    /// Builder method to set a typed attribute.
+    ///
+    /// Fails with [Error::NamespaceMismatch] if `attr` is not in the namespace of the tag.
+    ///
+    pub fn with_attr(mut self, attr: web::Attr, value: AttributeValue) -> Result<Self, Error> {
+        self.set_attr(attr, value)?;
+        Ok(self)
+    }
+
+    ///
+    /// Builder method to set an attribute by its case-insensitive local name,
+    /// parsing the value. `None` is a valueless attribute.
+    ///
+    pub fn with_attr_str(mut self, local_name: &str, value: Option<&str>) -> Result<Self, Error> {
+        self.set_attr_str(local_name, value)?;
+        Ok(self)
+    }
+
+    pub fn tag(&self) -> &web::Tag {
+        &self.tag
+    }
+
+    pub fn attrs(&self) -> &AttrMap {
+        &self.attrs
+    }
+
+    pub fn into_attrs(self) -> AttrMap {
+        self.attrs
+    }
+
+    ///
+    /// Set a typed attribute, returning the old value.
+    ///
+    pub fn set_attr(
+        &mut self,
+        attr: web::Attr,
+        value: AttributeValue,
+    ) -> Result<Option<AttributeValue>, Error> {
+        match (&self.tag, &attr) {
+            (web::Tag::Html5(_), web::Attr::Html5(_)) | (web::Tag::Svg(_), web::Attr::Svg(_)) => {
+                Ok(self.attrs.insert(attr, value))
+            }
+            _ => Err(Error::NamespaceMismatch),
+        }
+    }
+
+    ///
+    /// Set an attribute by its case-insensitive local name, parsing the value.
+    ///
+    pub fn set_attr_str(&mut self, local_name: &str, value: Option<&str>) -> Result<(), Error> {
+        let attr: web::Attr = self.tag.attr_by_local_name(local_name)?;
+        let value = attr.deserialize_attribute_value(value)?;
+        self.attrs.insert(attr, value);
+        Ok(())
+    }
+
+    ///
+    /// Like [Element::set_attr_str], failing with [Error::LimitExceeded] for
+    /// names, values or `data-*` attributes beyond `limits`.
+    ///
+    /// Example:
+    /// ```
+    /// use web_ns::*;
+    /// use web_ns::element::Element;
+    /// use web_ns::limits::{Limit, Limits};
+    ///
+    /// let limits = Limits { max_data_attrs: 1, ..Limits::default() };
+    /// let mut div = Element::new(web::Tag::Html5(html5::HtmlTag::Div));
+    ///
+    /// div.set_attr_str_with_limits("data-a", Some("1"), &limits)?;
+    /// div.set_attr_str_with_limits("data-a", Some("2"), &limits)?;
+    /// assert!(matches!(
+    ///     div.set_attr_str_with_limits("data-b", Some("3"), &limits),
+    ///     Err(Error::LimitExceeded(Limit::DataAttrs))
+    /// ));
+    /// # Ok::<(), Error>(())
+    /// ```
+    ///
+    pub fn set_attr_str_with_limits(
+        &mut self,
+        local_name: &str,
+        value: Option<&str>,
+        limits: &Limits,
+    ) -> Result<(), Error> {
+        let attr: web::Attr = limits.attr_by_local_name(&self.tag, local_name)?;
+        if is_dataset(&attr) && !self.attrs.contains(&attr) {
+            let count = self
+                .attrs
+                .iter()
+                .filter(|(attr, _)| is_dataset(attr))
+                .count();
+            if count >= limits.max_data_attrs {
+                return Err(Error::LimitExceeded(Limit::DataAttrs));
+            }
+        }
+        let value = attr.deserialize_attribute_value_with_limits(value, limits)?;
+        self.attrs.insert(attr, value);
+        Ok(())
+    }
+
+    pub fn remove_attr(&mut self, attr: &web::Attr) -> Option<AttributeValue> {
+        self.attrs.remove(attr)
+    }
+
+    pub fn attr(&self, attr: &web::Attr) -> Option<&AttributeValue> {
+        self.attrs.get(attr)
+    }
+
+    ///
+    /// Look up an attribute value by its case-insensitive local name.
+    ///
+    pub fn attr_by_name(&self, local_name: &str) -> Option<&AttributeValue> {
+        let attr: web::Attr = self.tag.attr_by_local_name(local_name).ok()?;
+        self.attrs.get(&attr)
+    }
+
+    ///
+    /// Whether the attribute is present, and not `False`.
+    ///
+    pub fn has_attr(&self, attr: &web::Attr) -> bool {
+        match self.attrs.get(attr) {
+            Some(AttributeValue::False) | None => false,
+            Some(_) => true,
+        }
+    }
+
+    ///
+    /// The value of an attribute with a single string value.
+    ///
+    pub fn attr_str(&self, attr: &web::Attr) -> Option<&str> {
+        as_str(self.attrs.get(attr)?)
+    }
+
+    pub fn attr_str_by_name(&self, local_name: &str) -> Option<&str> {
+        as_str(self.attr_by_name(local_name)?)
+    }
+
+    ///
+    /// The tokens of a token list attribute, e.g. `class`.
+    ///
+    pub fn attr_tokens(&self, attr: &web::Attr) -> Vec<&str> {
+        match self.attrs.get(attr) {
+            Some(AttributeValue::String(string)) => string.split_ascii_whitespace().collect(),
+            Some(AttributeValue::Multi(strings)) => strings.iter().map(String::as_str).collect(),
+            _ => vec![],
+        }
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.attr_str_by_name("id")
+    }
+
+    pub fn classes(&self) -> Vec<&str> {
+        match self.tag.attr_by_local_name("class") {
+            Ok(attr) => self.attr_tokens(&attr),
+            Err(_) => vec![],
+        }
+    }
+
+    ///
+    /// Serialize the start tag, omitting attributes that serialize as [SerializedAttributeValue::Omitted].
+    ///
+    pub fn start_tag(&self) -> String {
+        let mut output = format!("<{}", self.tag.local_name());
+        for (attr, value) in self.attrs.iter() {
+            match attr.serialize_attribute_value(value) {
+                SerializedAttributeValue::Omitted => {}
+                SerializedAttributeValue::Empty => {
+                    output.push(' ');
+                    output.push_str(attr.local_name());
+                }
+                SerializedAttributeValue::String(string) => {
+                    output.push(' ');
+                    output.push_str(attr.local_name());
+                    output.push_str("=\"");
+                    output.push_str(&escape_attribute_value(&string));
+                    output.push('"');
+                }
+            }
+        }
+        output.push('>');
+        output
+    }
+
+    ///
+    /// Serialize the end tag, which void elements do not have.
+    ///
+    pub fn end_tag(&self) -> Option<String> {
+        if self.tag.is_void() {
+            None
+        } else {
+            Some(format!("</{}>", self.tag.local_name()))
+        }
+    }
+}
+
+///
+/// Serializes an empty element. Foreign elements are self-closing.
+///
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let start_tag = self.start_tag();
+        match self.end_tag() {
+            Some(_)
+                if self
+                    .tag
+                    .tag_type()
+                    .any(crate::tag::tag_type::flags::FOREIGN) =>
+            {
+                write!(f, "{}/>", &start_tag[..start_tag.len() - 1])
+            }
+            Some(end_tag) => write!(f, "{}{}", start_tag, end_tag),
+            None => write!(f, "{}", start_tag),
+        }
+    }
+}
+
+fn is_dataset(attr: &web::Attr) -> bool {
+    matches!(attr, web::Attr::Html5(HtmlAttr::Dataset(_)))
+}
+
+fn as_str(value: &AttributeValue) -> Option<&str> {
+    match value {
+        AttributeValue::String(string) => Some(string),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html5::{HtmlAttr, HtmlTag};
+    use crate::svg::{SvgAttr, SvgTag};
+
+    #[test]
+    fn attr_map_keeps_order() {
+        let id = web::Attr::Html5(HtmlAttr::Id);
+        let title = web::Attr::Html5(HtmlAttr::Title);
+        let lang = web::Attr::Html5(HtmlAttr::Lang);
+        let string = |s: &str| AttributeValue::String(s.to_string());
+
+        let mut map: AttrMap = vec![
+            (id.clone(), string("a")),
+            (title.clone(), string("t")),
+            (lang.clone(), string("en")),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(map.insert(id.clone(), string("b")), Some(string("a")));
+        assert_eq!(map.remove(&title), Some(string("t")));
+        assert_eq!(map.remove(&title), None);
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![(&id, &string("b")), (&lang, &string("en"))]
+        );
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&lang), Some(&string("en")));
+
+        map.insert(title.clone(), string("u"));
+        assert_eq!(map.remove(&id), Some(string("b")));
+        assert_eq!(map.get(&title), Some(&string("u")));
+        assert_eq!(
+            map,
+            vec![(lang, string("en")), (title, string("u"))]
+                .into_iter()
+                .collect()
+        );
+    }
+
+    #[test]
+    fn typed_getters() {
+        let input = Element::new(web::Tag::Html5(HtmlTag::Input))
+            .with_attr_str("Disabled", None)
+            .unwrap()
+            .with_attr(web::Attr::Html5(HtmlAttr::Checked), AttributeValue::False)
+            .unwrap()
+            .with_attr_str("ID", Some("name"))
+            .unwrap();
+
+        assert!(input.has_attr(&web::Attr::Html5(HtmlAttr::Disabled)));
+        assert!(!input.has_attr(&web::Attr::Html5(HtmlAttr::Checked)));
+        assert_eq!(input.id(), Some("name"));
+        assert_eq!(input.attr_by_name("missing"), None);
+        assert!(input.classes().is_empty());
+        assert_eq!(input.to_string(), r#"<input disabled id="name">"#);
+        assert_eq!(input.end_tag(), None);
+    }
+
+    #[test]
+    fn namespaces() {
+        let rect = Element::new(web::Tag::Svg(SvgTag::Rect))
+            .with_attr_str("viewbox", Some("0 0 1 1"))
+            .unwrap();
+        assert_eq!(rect.to_string(), r#"<rect viewBox="0 0 1 1"/>"#);
+
+        assert!(matches!(
+            rect.with_attr(
+                web::Attr::Html5(HtmlAttr::Id),
+                AttributeValue::String("x".to_string())
+            ),
+            Err(Error::NamespaceMismatch)
+        ));
+        assert!(Element::new(web::Tag::Html5(HtmlTag::Div))
+            .with_attr(web::Attr::Svg(SvgAttr::Cx), AttributeValue::True)
+            .is_err());
+    }
+}
@@ -0,0 +1,489 @@
+//!
+//! Escaping of text content and attribute values for HTML serialization.
+//!
+//! See: https://html.spec.whatwg.org/#serialising-html-fragments
+//!
+
+use std::borrow::Cow;
+
+use crate::attr::attr_type::flags;
+use crate::attr::update::{update_strategy, UpdateStrategy};
+use crate::attr::Attribute;
+use crate::html5::HtmlAttr;
+use crate::tag::{Tag, TextKind};
+use crate::{url, web, Error, LocalName};
+
+///
+/// Escape text content for serialization as a child of `parent`.
+///
+/// The content of raw text elements is written verbatim, which fails if the
+/// text would end the element prematurely.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+///
+/// let div = html5::HtmlTag::Div;
+/// assert_eq!(escape::escape_text(&div, "a < b").unwrap(), "a &lt; b");
+///
+/// let script = html5::HtmlTag::Script;
+/// assert_eq!(escape::escape_text(&script, "a < b").unwrap(), "a < b");
+/// assert!(escape::escape_text(&script, "</script>").is_err());
+///
+/// let svg_script = svg::SvgTag::Script;
+/// assert_eq!(escape::escape_text(&svg_script, "a < b").unwrap(), "a &lt; b");
+/// ```
+///
+pub fn escape_text<'a, T>(parent: &T, text: &'a str) -> Result<Cow<'a, str>, Error>
+where
+    T: Tag + LocalName,
+{
+    match parent.text_kind() {
+        TextKind::RawText => {
+            if contains_end_tag(text, parent.local_name()) {
+                Err(Error::InvalidText)
+            } else {
+                Ok(Cow::Borrowed(text))
+            }
+        }
+        TextKind::PlainText => Ok(Cow::Borrowed(text)),
+        TextKind::Void => {
+            if text.is_empty() {
+                Ok(Cow::Borrowed(text))
+            } else {
+                Err(Error::InvalidText)
+            }
+        }
+        TextKind::EscapableRawText | TextKind::Template | TextKind::Foreign | TextKind::Normal => {
+            Ok(escape(text, false))
+        }
+    }
+}
+
+///
+/// Check the text of a comment for serialization between `<!--` and `-->`.
+///
+/// Comment text cannot be escaped, so this fails if the text would end the
+/// comment early or differently in a browser, e.g. `<!-->` or `--!>`.
+///
+/// See: https://html.spec.whatwg.org/#comments
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+///
+/// assert_eq!(escape::escape_comment(" a - b ").unwrap(), " a - b ");
+/// assert!(escape::escape_comment(">x<img>").is_err());
+/// assert!(escape::escape_comment("a--!><img>").is_err());
+/// ```
+///
+pub fn escape_comment(text: &str) -> Result<&str, Error> {
+    let is_valid = !text.starts_with('>')
+        && !text.starts_with("->")
+        && !text.contains("<!--")
+        && !text.contains("-->")
+        && !text.contains("--!>")
+        && !text.ends_with("<!-");
+    if is_valid {
+        Ok(text)
+    } else {
+        Err(Error::InvalidText)
+    }
+}
+
+///
+/// Escape an attribute value for serialization inside double quotes.
+///
+/// Example:
+/// ```
+/// assert_eq!(
+///     web_ns::escape::escape_attribute_value("\"a\" & <b>"),
+///     "&quot;a&quot; &amp; &lt;b&gt;"
+/// );
+/// ```
+///
+pub fn escape_attribute_value(value: &str) -> Cow<'_, str> {
+    escape(value, true)
+}
+
+fn escape(input: &str, attribute_mode: bool) -> Cow<'_, str> {
+    let needs_escape = |c: char| match c {
+        '&' | '\u{a0}' | '<' | '>' => true,
+        '"' => attribute_mode,
+        _ => false,
+    };
+
+    let first = match input.find(needs_escape) {
+        Some(index) => index,
+        None => return Cow::Borrowed(input),
+    };
+
+    let mut output = String::with_capacity(input.len() + 8);
+    output.push_str(&input[..first]);
+
+    for c in input[first..].chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '\u{a0}' => output.push_str("&nbsp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' if attribute_mode => output.push_str("&quot;"),
+            c => output.push(c),
+        }
+    }
+
+    Cow::Owned(output)
+}
+
+///
+/// The language of an attribute value, which decides how interpolated values are escaped.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AttrContext {
+    /// Plain text, which only needs HTML escaping.
+    Text,
+    /// A URL, e.g. `href`.
+    Url,
+    /// Space-separated URLs, e.g. `ping`.
+    UrlList,
+    /// Image candidate strings, i.e. `srcset` and `imagesrcset`.
+    Srcset,
+    /// JavaScript, i.e. event handler attributes.
+    Script,
+    /// CSS declarations, i.e. `style`.
+    Css,
+}
+
+///
+/// URL schemes considered safe by [escape_url]. Relative URLs are always safe.
+///
+pub const SAFE_URL_SCHEMES: &[&str] = &["http", "https", "mailto", "tel"];
+
+///
+/// The escaping context of values of `attr` on `tag`.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::escape::{attr_context, AttrContext};
+///
+/// let img: web::Tag = html5::HTML5_NS.tag_by_local_name("img").unwrap();
+/// let context = |name| attr_context(&img, &img.attr_by_local_name(name).unwrap());
+///
+/// assert_eq!(context("alt"), AttrContext::Text);
+/// assert_eq!(context("src"), AttrContext::Url);
+/// assert_eq!(context("srcset"), AttrContext::Srcset);
+/// assert_eq!(context("onload"), AttrContext::Script);
+/// assert_eq!(context("style"), AttrContext::Css);
+/// ```
+///
+pub fn attr_context(tag: &web::Tag, attr: &web::Attr) -> AttrContext {
+    if update_strategy(tag, attr) == UpdateStrategy::EventHandler {
+        return AttrContext::Script;
+    }
+    match attr {
+        web::Attr::Html5(HtmlAttr::Srcset) | web::Attr::Html5(HtmlAttr::Imagesrcset) => {
+            AttrContext::Srcset
+        }
+        attr if attr.local_name() == "style" => AttrContext::Css,
+        attr if url::is_url_attr(attr) => {
+            if attr.attr_type().any(flags::SPACE_SEP) {
+                AttrContext::UrlList
+            } else {
+                AttrContext::Url
+            }
+        }
+        _ => AttrContext::Text,
+    }
+}
+
+///
+/// Escape an interpolated value for the context of `attr` on `tag`,
+/// for serialization inside double quotes.
+///
+/// Fails with [Error::InvalidAttributeValue] for URLs with unsafe schemes,
+/// and image candidates with invalid descriptors.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::escape::escape_attribute_in_context;
+///
+/// let a: web::Tag = html5::HTML5_NS.tag_by_local_name("a").unwrap();
+/// let escape = |name, value| escape_attribute_in_context(&a, &a.attr_by_local_name(name).unwrap(), value);
+///
+/// assert_eq!(escape("title", "\"x\" & y").unwrap(), "&quot;x&quot; &amp; y");
+/// assert_eq!(escape("href", "/search?q=a b&c=\"").unwrap(), "/search?q=a%20b&amp;c=%22");
+/// assert!(escape("href", "javascript:alert(1)").is_err());
+/// assert_eq!(escape("onclick", "'); alert(1); ('").unwrap(), r"'\x27); alert(1); (\x27'");
+/// assert_eq!(escape("style", "red; background: url(x)").unwrap(), r"red\3b  background\3a  url\28 x\29 ");
+/// ```
+///
+pub fn escape_attribute_in_context(
+    tag: &web::Tag,
+    attr: &web::Attr,
+    value: &str,
+) -> Result<String, Error> {
+    match attr_context(tag, attr) {
+        AttrContext::Text => Ok(escape_attribute_value(value).into_owned()),
+        AttrContext::Url => escape_url(value),
+        AttrContext::UrlList => escape_url_list(value),
+        AttrContext::Srcset => escape_srcset(value),
+        AttrContext::Script => Ok(escape_script_value(value)),
+        AttrContext::Css => Ok(escape_css_value(value)),
+    }
+}
+
+///
+/// Escape a URL: fail for unsafe schemes, and percent-encode what is not allowed in URLs.
+///
+pub fn escape_url(value: &str) -> Result<String, Error> {
+    escape_url_with(value, "")
+}
+
+///
+/// Escape space-separated URLs, e.g. for `ping`.
+///
+pub fn escape_url_list(value: &str) -> Result<String, Error> {
+    let urls = value
+        .split_ascii_whitespace()
+        .map(escape_url)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(urls.join(" "))
+}
+
+///
+/// Escape comma-separated image candidates, each a URL and an optional
+/// width (`100w`) or pixel density (`2x`) descriptor.
+///
+pub fn escape_srcset(value: &str) -> Result<String, Error> {
+    let mut candidates = vec![];
+    for candidate in value.split(',') {
+        let mut parts = candidate.split_ascii_whitespace();
+        let url = match parts.next() {
+            Some(url) => url,
+            None => continue,
+        };
+        let mut escaped = escape_url_with(url, ",")?;
+        match (parts.next(), parts.next()) {
+            (None, _) => {}
+            (Some(descriptor), None) if is_image_descriptor(descriptor) => {
+                escaped.push(' ');
+                escaped.push_str(descriptor);
+            }
+            _ => return Err(Error::InvalidAttributeValue),
+        }
+        candidates.push(escaped);
+    }
+    Ok(candidates.join(", "))
+}
+
+///
+/// Escape a value as a single-quoted JavaScript string literal,
+/// which contains no characters special to HTML.
+///
+pub fn escape_script_value(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('\'');
+    for c in value.chars() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\u{2028}' => output.push_str("\\u2028"),
+            '\u{2029}' => output.push_str("\\u2029"),
+            '\'' | '"' | '&' | '<' | '>' | '`' | '/' => {
+                output.push_str(&format!("\\x{:02x}", c as u32))
+            }
+            c if c.is_ascii_control() => output.push_str(&format!("\\x{:02x}", c as u32)),
+            c => output.push(c),
+        }
+    }
+    output.push('\'');
+    output
+}
+
+///
+/// Escape a value for a CSS declaration value. Everything that could end the
+/// value, start a comment or a function, or is special to HTML, is written as a
+/// CSS escape.
+///
+pub fn escape_css_value(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_ascii_alphanumeric()
+            || matches!(c, ' ' | '-' | '_' | '.' | '#' | '%' | ',')
+            || !c.is_ascii()
+        {
+            output.push(c);
+        } else {
+            // The space ends the escape
+            output.push_str(&format!("\\{:x} ", c as u32));
+        }
+    }
+    output
+}
+
+fn escape_url_with(value: &str, also_encode: &str) -> Result<String, Error> {
+    if let Some(scheme) = url::scheme(value) {
+        if !SAFE_URL_SCHEMES.contains(&scheme.as_str()) {
+            return Err(Error::InvalidAttributeValue);
+        }
+    }
+
+    let bytes = value
+        .trim_matches(|c: char| c.is_ascii_whitespace())
+        .as_bytes();
+    let mut output = String::with_capacity(bytes.len());
+    for (index, &byte) in bytes.iter().enumerate() {
+        let percent_encoded = byte == b'%'
+            && bytes.len() > index + 2
+            && bytes[index + 1].is_ascii_hexdigit()
+            && bytes[index + 2].is_ascii_hexdigit();
+        let allowed = byte.is_ascii_alphanumeric()
+            || b"-._~:/?#[]@!$'()*+,;=".contains(&byte)
+            || percent_encoded;
+
+        if byte == b'&' {
+            output.push_str("&amp;");
+        } else if allowed && !also_encode.as_bytes().contains(&byte) {
+            output.push(byte as char);
+        } else {
+            output.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    Ok(output)
+}
+
+fn is_image_descriptor(descriptor: &str) -> bool {
+    match descriptor.char_indices().last() {
+        Some((index, 'w')) => {
+            !descriptor[..index].is_empty()
+                && descriptor[..index].bytes().all(|b| b.is_ascii_digit())
+        }
+        Some((index, 'x')) => descriptor[..index]
+            .parse::<f64>()
+            .map(|x| x > 0.0)
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+///
+/// Whether `text` contains something the tokenizer would take as the end tag
+/// of the raw text element `local_name`.
+///
+fn contains_end_tag(text: &str, local_name: &str) -> bool {
+    let bytes = text.as_bytes();
+    let name = local_name.as_bytes();
+
+    text.match_indices("</").any(|(index, _)| {
+        let after = &bytes[index + 2..];
+        after.len() >= name.len()
+            && after[..name.len()].eq_ignore_ascii_case(name)
+            && match after.get(name.len()) {
+                None => true,
+                Some(b'\t') | Some(b'\n') | Some(b'\x0c') | Some(b'\r') | Some(b' ')
+                | Some(b'/') | Some(b'>') => true,
+                Some(_) => false,
+            }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html5::{HtmlAttr, HtmlTag};
+
+    #[test]
+    fn escape_normal_text() {
+        assert_eq!(escape_text(&HtmlTag::P, "plain").unwrap(), "plain");
+        assert_eq!(
+            escape_text(&HtmlTag::P, "a&b\u{a0}<c>\"").unwrap(),
+            "a&amp;b&nbsp;&lt;c&gt;\""
+        );
+        assert_eq!(
+            escape_text(&HtmlTag::Textarea, "</textarea>").unwrap(),
+            "&lt;/textarea&gt;"
+        );
+    }
+
+    #[test]
+    fn raw_text_end_tags() {
+        assert!(escape_text(&HtmlTag::Style, "a > b").is_ok());
+        assert!(escape_text(&HtmlTag::Style, "</style").is_err());
+        assert!(escape_text(&HtmlTag::Style, "</STYLE >").is_err());
+        assert!(escape_text(&HtmlTag::Style, "</styles>").is_ok());
+        assert!(escape_text(&HtmlTag::Script, "</style>").is_ok());
+        assert!(escape_text(&HtmlTag::Plaintext, "</plaintext>").is_ok());
+    }
+
+    #[test]
+    fn urls() {
+        assert_eq!(
+            escape_url("https://x.y/a b/ä?q=%41%4").unwrap(),
+            "https://x.y/a%20b/%C3%A4?q=%41%254"
+        );
+        assert_eq!(escape_url(" mailto:a@b.c ").unwrap(), "mailto:a@b.c");
+        assert_eq!(escape_url("a<b>\\\"").unwrap(), "a%3Cb%3E%5C%22");
+        assert!(escape_url("data:text/html,x").is_err());
+        assert!(escape_url("\tjava\nscript:x").is_err());
+        assert_eq!(escape_url_list("/a  /b c").unwrap(), "/a /b c");
+        assert!(escape_url_list("/a vbscript:x").is_err());
+    }
+
+    #[test]
+    fn url_attr_contexts() {
+        let context = |tag: HtmlTag, attr: HtmlAttr| {
+            attr_context(&web::Tag::Html5(tag), &web::Attr::Html5(attr))
+        };
+        assert_eq!(
+            context(HtmlTag::Body, HtmlAttr::Background),
+            AttrContext::Url
+        );
+        assert_eq!(
+            context(HtmlTag::Object, HtmlAttr::Codebase),
+            AttrContext::Url
+        );
+        assert_eq!(context(HtmlTag::Html, HtmlAttr::Manifest), AttrContext::Url);
+        assert_eq!(context(HtmlTag::Div, HtmlAttr::Itemid), AttrContext::Url);
+        assert_eq!(
+            context(HtmlTag::Div, HtmlAttr::Itemtype),
+            AttrContext::UrlList
+        );
+        assert!(escape_attribute_in_context(
+            &web::Tag::Html5(HtmlTag::Body),
+            &web::Attr::Html5(HtmlAttr::Background),
+            "javascript:x"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn srcset() {
+        assert_eq!(
+            escape_srcset("a.png, b,c.png 2x,c.png 100w,").unwrap(),
+            "a.png, b, c.png 2x, c.png 100w"
+        );
+        assert!(escape_srcset("a.png 2y").is_err());
+        assert!(escape_srcset("a.png 1x 2x").is_err());
+        assert!(escape_srcset("javascript:x 1x").is_err());
+    }
+
+    #[test]
+    fn script_and_css() {
+        assert_eq!(
+            escape_script_value("</script>\n\u{2028}\\"),
+            r"'\x3c\x2fscript\x3e\n\u2028\\'"
+        );
+        assert_eq!(escape_css_value("1.5em"), "1.5em");
+        assert_eq!(escape_css_value("a\"}/*"), r"a\22 \7d \2f \2a ");
+    }
+
+    #[test]
+    fn void_has_no_text() {
+        assert!(escape_text(&HtmlTag::Br, "").is_ok());
+        assert!(escape_text(&HtmlTag::Br, "text").is_err());
+    }
+}
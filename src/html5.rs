@@ -15,6 +15,11 @@ mod attributes {
     include!(concat!(env!("OUT_DIR"), "/codegen_html_attr_enum.rs"));
 }
 
+pub mod content_model;
+pub mod forms;
+pub mod optional_tags;
+pub mod reflect;
+
 pub use attributes::HtmlAttr;
 pub use tags::HtmlTag;
 
@@ -34,8 +39,8 @@ impl crate::TagByLocalName<tags::HtmlTag> for Html5Namespace {
     fn tag_by_local_name(&self, local_name: &str) -> Result<tags::HtmlTag, Error> {
         tags::STATIC_LOCAL_NAME_LOOKUP
             .get(&unicase::UniCase::ascii(local_name))
-            .map(Clone::clone)
-            .ok_or_else(|| Error::InvalidAttribute)
+            .cloned()
+            .ok_or(Error::InvalidAttribute)
     }
 }
 
@@ -53,8 +58,8 @@ impl crate::AttrByLocalName<attributes::HtmlAttr> for tags::HtmlTag {
     fn attr_by_local_name(&self, local_name: &str) -> Result<attributes::HtmlAttr, Error> {
         attributes::STATIC_LOCAL_NAME_LOOKUP
             .get(&unicase::UniCase::ascii(local_name))
-            .map(Clone::clone)
-            .ok_or_else(|| Error::InvalidAttribute)
+            .cloned()
+            .ok_or(Error::InvalidAttribute)
             .or_else(|_| {
                 DataAttr::parse_attribute(local_name)
                     .map(|attr| attributes::HtmlAttr::Dataset(Box::new(attr)))
@@ -66,8 +71,8 @@ impl crate::AttrByProperty<attributes::HtmlAttr> for tags::HtmlTag {
     fn attr_by_property(&self, property: &str) -> Result<attributes::HtmlAttr, Error> {
         attributes::STATIC_PROPERTY_LOOKUP
             .get(property)
-            .map(Clone::clone)
-            .ok_or_else(|| Error::InvalidAttribute)
+            .cloned()
+            .ok_or(Error::InvalidAttribute)
             .or_else(|_| {
                 DataAttr::parse_property(property)
                     .map(|data| attributes::HtmlAttr::Dataset(Box::new(data)))
@@ -78,14 +83,13 @@ impl crate::AttrByProperty<attributes::HtmlAttr> for tags::HtmlTag {
 impl crate::AttrByLocalName<crate::web::Attr> for tags::HtmlTag {
     fn attr_by_local_name(&self, local_name: &str) -> Result<crate::web::Attr, Error> {
         self.attr_by_local_name(local_name)
-            .map(|attr| super::web::Attr::Html5(attr))
+            .map(super::web::Attr::Html5)
     }
 }
 
 impl crate::AttrByProperty<crate::web::Attr> for tags::HtmlTag {
     fn attr_by_property(&self, property: &str) -> Result<crate::web::Attr, Error> {
-        self.attr_by_property(property)
-            .map(|attr| super::web::Attr::Html5(attr))
+        self.attr_by_property(property).map(super::web::Attr::Html5)
     }
 }
 
@@ -151,6 +155,114 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn text_kinds() {
+        assert_eq!(tags::HtmlTag::Xmp.text_kind(), TextKind::RawText);
+        assert_eq!(
+            tags::HtmlTag::Textarea.text_kind(),
+            TextKind::EscapableRawText
+        );
+        assert_eq!(tags::HtmlTag::Plaintext.text_kind(), TextKind::PlainText);
+        assert_eq!(tags::HtmlTag::Template.text_kind(), TextKind::Template);
+        assert_eq!(tags::HtmlTag::Img.text_kind(), TextKind::Void);
+        assert_eq!(tags::HtmlTag::Div.text_kind(), TextKind::Normal);
+
+        let svg: web::Tag = HTML5_NS.tag_by_local_name("svg").unwrap();
+        assert_eq!(svg.text_kind(), TextKind::Foreign);
+        let style: web::Tag = HTML5_NS.tag_by_local_name("style").unwrap();
+        assert_eq!(style.text_kind(), TextKind::RawText);
+    }
+
+    #[test]
+    fn tree_construction_categories() {
+        assert!(tags::HtmlTag::Address.is_special());
+        assert!(!tags::HtmlTag::Span.is_special());
+        assert!(tags::HtmlTag::Nobr.is_formatting());
+        assert!(!tags::HtmlTag::Div.is_formatting());
+
+        assert!(tags::HtmlTag::Button.is_scope_boundary(Scope::Button));
+        assert!(!tags::HtmlTag::Button.is_scope_boundary(Scope::Default));
+        assert!(!tags::HtmlTag::Td.is_scope_boundary(Scope::Table));
+        assert!(tags::HtmlTag::Template.is_scope_boundary(Scope::Table));
+        assert!(tags::HtmlTag::Div.is_scope_boundary(Scope::Select));
+        assert!(!tags::HtmlTag::Option.is_scope_boundary(Scope::Select));
+
+        let foreign_object: web::Tag = svg::SVG_NS.tag_by_local_name("foreignObject").unwrap();
+        assert!(foreign_object.is_special());
+        assert!(foreign_object.is_scope_boundary(Scope::Button));
+        assert!(foreign_object.is_scope_boundary(Scope::Select));
+        assert!(!foreign_object.is_scope_boundary(Scope::Table));
+    }
+
+    #[test]
+    fn statuses() {
+        use crate::status::Status;
+
+        assert_eq!(tags::HtmlTag::Acronym.status(), Status::Obsolete);
+        assert_eq!(
+            tags::HtmlTag::Acronym.replacement_hint(),
+            Some("Use `<abbr>`")
+        );
+        assert!(tags::HtmlTag::Keygen.is_obsolete());
+        assert!(!tags::HtmlTag::Abbr.is_obsolete());
+        assert_eq!(attributes::HtmlAttr::Bgcolor.status(), Status::Obsolete);
+        assert_eq!(
+            attributes::HtmlAttr::Topmargin.status(),
+            Status::NonStandard
+        );
+        assert!(!attributes::HtmlAttr::Topmargin.status().is_conforming());
+        assert_eq!(attributes::HtmlAttr::Class.status(), Status::Current);
+        assert_eq!(attributes::HtmlAttr::Border.status(), Status::Deprecated);
+        assert!(attributes::HtmlAttr::Language.status().is_conforming());
+
+        let data: web::Attr = tags::HtmlTag::Div.attr_by_local_name("data-x").unwrap();
+        assert_eq!(data.status(), Status::Current);
+        assert_eq!(data.replacement_hint(), None);
+    }
+
+    #[test]
+    fn interface_names() {
+        assert_eq!(tags::HtmlTag::H3.interface_name(), "HTMLHeadingElement");
+        assert_eq!(tags::HtmlTag::Xmp.interface_name(), "HTMLPreElement");
+        assert_eq!(tags::HtmlTag::Section.interface_name(), "HTMLElement");
+        assert_eq!(tags::HtmlTag::Keygen.interface_name(), "HTMLUnknownElement");
+        assert_eq!(tags::HtmlTag::Thead.tag_name(), "THEAD");
+
+        let svg: web::Tag = HTML5_NS.tag_by_local_name("svg").unwrap();
+        assert_eq!(svg.interface_name(), "SVGSVGElement");
+        assert_eq!(svg.tag_name(), "svg");
+
+        let tspan: web::Tag = svg::SVG_NS.tag_by_local_name("tspan").unwrap();
+        assert_eq!(tspan.interface_name(), "SVGTSpanElement");
+        assert_eq!(tspan.tag_name(), "tspan");
+    }
+
+    #[test]
+    fn idl_types() {
+        use crate::attr::idl_type::IdlType;
+
+        let idl_type = |attr: &str| -> Option<IdlType> {
+            let attr: web::Attr = tags::HtmlTag::Div.attr_by_local_name(attr).unwrap();
+            attr.idl_type()
+        };
+
+        assert_eq!(idl_type("href"), Some(IdlType::UsvString));
+        assert_eq!(idl_type("rowspan"), Some(IdlType::UnsignedLong));
+        assert_eq!(idl_type("autoplay"), Some(IdlType::Boolean));
+        assert_eq!(idl_type("download"), Some(IdlType::DomString));
+        assert_eq!(idl_type("onclick"), Some(IdlType::EventHandler));
+        assert_eq!(idl_type("sandbox"), Some(IdlType::DomTokenList));
+        assert_eq!(idl_type("itemscope"), Some(IdlType::Boolean));
+        assert_eq!(idl_type("itemprop"), None);
+        assert_eq!(idl_type("data-foo"), Some(IdlType::DomString));
+        assert_eq!(IdlType::UnsignedLong.idl_name(), "unsigned long");
+
+        let view_box: web::Attr = svg::SvgTag::Svg.attr_by_local_name("viewBox").unwrap();
+        assert_eq!(view_box.idl_type(), Some(IdlType::SvgAnimatedRect));
+        let onclick: web::Attr = svg::SvgTag::Svg.attr_by_local_name("onclick").unwrap();
+        assert_eq!(onclick.idl_type(), Some(IdlType::EventHandler));
+    }
+
     #[test]
     fn properties_in_hashmap() {
         let mut hashmap = std::collections::HashMap::new();
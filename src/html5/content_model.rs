@@ -0,0 +1,397 @@
+//!
+//! Content categories and content models of HTML elements.
+//!
+//! See: https://html.spec.whatwg.org/#content-models
+//!
+
+use super::{HtmlAttr, HtmlTag};
+use crate::attr::AttributeValue;
+use crate::tag::tag_type::{flags::*, Flags, TagType};
+use crate::tag::Tag;
+use crate::web;
+
+///
+/// A child node, as seen by the content model checker.
+///
+#[derive(Clone, Copy, Debug)]
+pub enum Child<'a> {
+    /// An element with its attributes.
+    Element(&'a web::Tag, &'a [(web::Attr, AttributeValue)]),
+    /// A text node.
+    Text(&'a str),
+}
+
+///
+/// The content model of an element: what it may contain.
+///
+#[derive(Clone, Copy, Debug)]
+pub enum ContentModel {
+    /// No content at all.
+    Nothing,
+    /// Text only.
+    Text,
+    /// Anything, e.g. `<template>`, or elements without a defined content model.
+    Any,
+    /// Elements from the given content categories and explicitly listed elements.
+    Elements {
+        /// Allowed content categories, as tag type flags.
+        categories: Flags,
+        /// Allowed elements besides those in `categories`.
+        elements: &'static [HtmlTag],
+        /// Elements in these categories are not allowed, even if otherwise matching.
+        except_categories: Flags,
+        /// These elements are not allowed, even if otherwise matching.
+        except_elements: &'static [HtmlTag],
+    },
+}
+
+impl ContentModel {
+    const fn of(categories: Flags, elements: &'static [HtmlTag]) -> Self {
+        Self::Elements {
+            categories,
+            elements,
+            except_categories: 0,
+            except_elements: &[],
+        }
+    }
+
+    const fn except(
+        categories: Flags,
+        except_categories: Flags,
+        except_elements: &'static [HtmlTag],
+    ) -> Self {
+        Self::Elements {
+            categories,
+            elements: &[],
+            except_categories,
+            except_elements,
+        }
+    }
+
+    ///
+    /// Whether text that is not inter-element whitespace is allowed.
+    ///
+    pub fn allows_text(&self) -> bool {
+        match self {
+            Self::Nothing => false,
+            Self::Text | Self::Any => true,
+            Self::Elements { categories, .. } => categories & (FLOW | PHRASING) > 0,
+        }
+    }
+}
+
+///
+/// The content model of `tag`.
+///
+/// Transparent elements like `<a>` and `<ins>` take the content model of their
+/// parent, which is approximated as flow content.
+///
+pub fn content_model(tag: &HtmlTag) -> ContentModel {
+    use HtmlTag::*;
+
+    const SECTIONING_AND_HEADING: Flags = SECTIONING | HEADING;
+
+    match tag {
+        Area | Base | Br | Col | Embed | Hr | Img | Input | Link | Meta | Source | Track | Wbr
+        | Iframe => ContentModel::Nothing,
+        Script | Style | Title | Textarea | Option | Rp => ContentModel::Text,
+        Template => ContentModel::Any,
+        Html => ContentModel::of(0, &[Head, Body]),
+        Head => ContentModel::of(METADATA, &[]),
+        Body | Article | Aside | Nav | Section | Blockquote | Div | Li | Dd | Figcaption | Main
+        | Dialog | Td | Noscript | Slot | Canvas | Ins | Del => ContentModel::of(FLOW, &[]),
+        Address => ContentModel::except(FLOW, SECTIONING_AND_HEADING, &[Header, Footer, Address]),
+        Header | Footer => ContentModel::except(FLOW, 0, &[Header, Footer, Main]),
+        Form => ContentModel::except(FLOW, 0, &[Form]),
+        Caption => ContentModel::except(FLOW, 0, &[Table]),
+        Dt | Th => ContentModel::except(FLOW, SECTIONING_AND_HEADING, &[Header, Footer]),
+        Fieldset => ContentModel::of(FLOW, &[Legend]),
+        Details => ContentModel::of(FLOW, &[Summary]),
+        Figure => ContentModel::of(FLOW, &[Figcaption]),
+        A => ContentModel::except(FLOW, INTERACTIVE, &[A]),
+        Audio | Video => ContentModel::Elements {
+            categories: FLOW,
+            elements: &[Source, Track],
+            except_categories: 0,
+            except_elements: &[Audio, Video],
+        },
+        Object => ContentModel::of(FLOW, &[Param]),
+        Map => ContentModel::of(FLOW, &[Area]),
+        P | H1 | H2 | H3 | H4 | H5 | H6 | Pre | Span | Em | Strong | Small | S | Cite | Q | Dfn
+        | Abbr | Data | Time | Code | Var | Samp | Kbd | Sub | Sup | I | B | U | Mark | Bdi
+        | Bdo | Rt | Output => ContentModel::of(PHRASING, &[]),
+        Legend | Summary => ContentModel::of(PHRASING | HEADING, &[]),
+        Label => ContentModel::except(PHRASING, 0, &[Label]),
+        Button => ContentModel::except(PHRASING, INTERACTIVE, &[]),
+        Progress => ContentModel::except(PHRASING, 0, &[Progress]),
+        Meter => ContentModel::except(PHRASING, 0, &[Meter]),
+        Ruby => ContentModel::of(PHRASING, &[Rt, Rp]),
+        Datalist => ContentModel::of(PHRASING, &[Option]),
+        Ul | Ol | Menu => ContentModel::of(SCRIPT_SUPPORTING, &[Li]),
+        Dl => ContentModel::of(SCRIPT_SUPPORTING, &[Dt, Dd, Div]),
+        Table => ContentModel::of(
+            SCRIPT_SUPPORTING,
+            &[Caption, Colgroup, Thead, Tbody, Tfoot, Tr],
+        ),
+        Colgroup => ContentModel::of(0, &[Col, Template]),
+        Thead | Tbody | Tfoot => ContentModel::of(SCRIPT_SUPPORTING, &[Tr]),
+        Tr => ContentModel::of(SCRIPT_SUPPORTING, &[Td, Th]),
+        Select => ContentModel::of(SCRIPT_SUPPORTING, &[Option, Optgroup, Hr]),
+        Optgroup => ContentModel::of(SCRIPT_SUPPORTING, &[Option]),
+        Picture => ContentModel::of(SCRIPT_SUPPORTING, &[Source, Img]),
+        Hgroup => ContentModel::of(SCRIPT_SUPPORTING | HEADING, &[P]),
+        _ => ContentModel::Any,
+    }
+}
+
+///
+/// The content categories of an element, taking attribute-dependent categories into account.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::html5::{HtmlAttr, HtmlTag};
+/// use web_ns::html5::content_model::categories;
+/// use web_ns::tag_type::flags::INTERACTIVE;
+///
+/// let href = vec![(web::Attr::Html5(HtmlAttr::Href), AttributeValue::String("/".into()))];
+///
+/// assert!(!categories(&HtmlTag::A, &[]).any(INTERACTIVE));
+/// assert!(categories(&HtmlTag::A, &href).any(INTERACTIVE));
+/// ```
+///
+pub fn categories(tag: &HtmlTag, attrs: &[(web::Attr, AttributeValue)]) -> TagType {
+    let mut flags = tag.tag_type().0;
+
+    let has = |attr| find_attr(attrs, attr).is_some();
+
+    match tag {
+        HtmlTag::A if has(HtmlAttr::Href) => flags |= INTERACTIVE,
+        HtmlTag::Audio | HtmlTag::Video if has(HtmlAttr::Controls) => {
+            flags |= INTERACTIVE | PALPABLE
+        }
+        HtmlTag::Img if has(HtmlAttr::Usemap) => flags |= INTERACTIVE,
+        HtmlTag::Input if is_hidden_input(attrs) => flags &= !(INTERACTIVE | PALPABLE | LABELABLE),
+        HtmlTag::Meta if has(HtmlAttr::Itemprop) => flags |= FLOW | PHRASING,
+        HtmlTag::Link if has(HtmlAttr::Itemprop) || is_body_ok_link(attrs) => {
+            flags |= FLOW | PHRASING
+        }
+        _ => {}
+    }
+
+    TagType(flags)
+}
+
+///
+/// Check whether `child` is allowed as a child of `parent`.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::html5::{HtmlAttr, HtmlTag};
+/// use web_ns::html5::content_model::{is_child_allowed, Child};
+///
+/// let div = web::Tag::Html5(HtmlTag::Div);
+/// let li = web::Tag::Html5(HtmlTag::Li);
+/// let a = web::Tag::Html5(HtmlTag::A);
+///
+/// assert!(!is_child_allowed(&HtmlTag::P, Child::Element(&div, &[])));
+/// assert!(is_child_allowed(&HtmlTag::Ul, Child::Element(&li, &[])));
+/// assert!(!is_child_allowed(&HtmlTag::Div, Child::Element(&li, &[])));
+///
+/// let href = vec![(web::Attr::Html5(HtmlAttr::Href), AttributeValue::String("/".into()))];
+/// assert!(is_child_allowed(&HtmlTag::Button, Child::Element(&a, &[])));
+/// assert!(!is_child_allowed(&HtmlTag::Button, Child::Element(&a, &href)));
+/// ```
+///
+pub fn is_child_allowed(parent: &HtmlTag, child: Child) -> bool {
+    let model = content_model(parent);
+
+    let (child_tag, child_attrs) = match child {
+        Child::Text(text) => {
+            return match model {
+                ContentModel::Nothing => text.is_empty(),
+                _ => model.allows_text() || is_inter_element_whitespace(text),
+            };
+        }
+        Child::Element(web::Tag::Html5(tag), attrs) => (tag, attrs),
+        // The `<svg>` root element is the only SVG element allowed in HTML content:
+        Child::Element(web::Tag::Svg(crate::svg::SvgTag::Svg), attrs) => (&HtmlTag::Svg, attrs),
+        Child::Element(web::Tag::Svg(_), _) => return false,
+    };
+
+    match model {
+        ContentModel::Nothing | ContentModel::Text => false,
+        ContentModel::Any => true,
+        ContentModel::Elements {
+            categories: allowed_categories,
+            elements,
+            except_categories,
+            except_elements,
+        } => {
+            let child_categories = categories(child_tag, child_attrs);
+
+            if child_categories.any(except_categories) || except_elements.contains(child_tag) {
+                return false;
+            }
+
+            // Interactive content may not have descendants with a tabindex attribute:
+            if matches!(parent, HtmlTag::A | HtmlTag::Button)
+                && find_attr(child_attrs, HtmlAttr::Tabindex).is_some()
+            {
+                return false;
+            }
+
+            child_categories.any(allowed_categories) || elements.contains(child_tag)
+        }
+    }
+}
+
+fn find_attr(attrs: &[(web::Attr, AttributeValue)], attr: HtmlAttr) -> Option<&AttributeValue> {
+    let attr = web::Attr::Html5(attr);
+    attrs
+        .iter()
+        .find(|(candidate, _)| candidate == &attr)
+        .map(|(_, value)| value)
+}
+
+fn is_hidden_input(attrs: &[(web::Attr, AttributeValue)]) -> bool {
+    match find_attr(attrs, HtmlAttr::Type) {
+        Some(AttributeValue::String(input_type)) => input_type.eq_ignore_ascii_case("hidden"),
+        _ => false,
+    }
+}
+
+fn is_body_ok_link(attrs: &[(web::Attr, AttributeValue)]) -> bool {
+    const BODY_OK: &[&str] = &[
+        "dns-prefetch",
+        "modulepreload",
+        "pingback",
+        "preconnect",
+        "prefetch",
+        "preload",
+        "stylesheet",
+    ];
+    let is_body_ok = |keyword: &String| {
+        BODY_OK
+            .iter()
+            .any(|body_ok| body_ok.eq_ignore_ascii_case(keyword))
+    };
+
+    match find_attr(attrs, HtmlAttr::Rel) {
+        Some(AttributeValue::String(keyword)) => is_body_ok(keyword),
+        Some(AttributeValue::Multi(keywords)) => keywords.iter().all(is_body_ok),
+        _ => false,
+    }
+}
+
+fn is_inter_element_whitespace(text: &str) -> bool {
+    text.chars()
+        .all(|c| matches!(c, ' ' | '\t' | '\n' | '\x0c' | '\r'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(tag: HtmlTag) -> web::Tag {
+        web::Tag::Html5(tag)
+    }
+
+    fn attr(attr: HtmlAttr, value: &str) -> (web::Attr, AttributeValue) {
+        (
+            web::Attr::Html5(attr),
+            AttributeValue::String(value.to_string()),
+        )
+    }
+
+    #[test]
+    fn flow_and_phrasing() {
+        let div = element(HtmlTag::Div);
+        let span = element(HtmlTag::Span);
+
+        assert!(is_child_allowed(&HtmlTag::Div, Child::Element(&div, &[])));
+        assert!(is_child_allowed(&HtmlTag::Div, Child::Element(&span, &[])));
+        assert!(is_child_allowed(&HtmlTag::Span, Child::Element(&span, &[])));
+        assert!(!is_child_allowed(&HtmlTag::Span, Child::Element(&div, &[])));
+        assert!(!is_child_allowed(&HtmlTag::H1, Child::Element(&div, &[])));
+    }
+
+    #[test]
+    fn text_children() {
+        assert!(is_child_allowed(&HtmlTag::P, Child::Text("text")));
+        assert!(is_child_allowed(&HtmlTag::Ul, Child::Text("\n  ")));
+        assert!(!is_child_allowed(&HtmlTag::Ul, Child::Text("text")));
+        assert!(!is_child_allowed(&HtmlTag::Br, Child::Text(" ")));
+        assert!(is_child_allowed(&HtmlTag::Title, Child::Text("<b>")));
+    }
+
+    #[test]
+    fn exceptions() {
+        let header = element(HtmlTag::Header);
+        let form = element(HtmlTag::Form);
+        let h1 = element(HtmlTag::H1);
+
+        assert!(!is_child_allowed(
+            &HtmlTag::Footer,
+            Child::Element(&header, &[])
+        ));
+        assert!(!is_child_allowed(
+            &HtmlTag::Form,
+            Child::Element(&form, &[])
+        ));
+        assert!(!is_child_allowed(
+            &HtmlTag::Address,
+            Child::Element(&h1, &[])
+        ));
+        assert!(is_child_allowed(&HtmlTag::Legend, Child::Element(&h1, &[])));
+    }
+
+    #[test]
+    fn attribute_dependent_categories() {
+        let input = element(HtmlTag::Input);
+        let hidden = vec![attr(HtmlAttr::Type, "HIDDEN")];
+        let text = vec![attr(HtmlAttr::Type, "text")];
+
+        assert!(!is_child_allowed(
+            &HtmlTag::A,
+            Child::Element(&input, &text)
+        ));
+        assert!(is_child_allowed(
+            &HtmlTag::A,
+            Child::Element(&input, &hidden)
+        ));
+
+        let span = element(HtmlTag::Span);
+        let tabindex = vec![attr(HtmlAttr::Tabindex, "0")];
+        assert!(!is_child_allowed(
+            &HtmlTag::Button,
+            Child::Element(&span, &tabindex)
+        ));
+
+        let link = element(HtmlTag::Link);
+        let stylesheet = vec![attr(HtmlAttr::Rel, "stylesheet")];
+        let icon = vec![attr(HtmlAttr::Rel, "icon")];
+        assert!(is_child_allowed(
+            &HtmlTag::Div,
+            Child::Element(&link, &stylesheet)
+        ));
+        assert!(!is_child_allowed(
+            &HtmlTag::Div,
+            Child::Element(&link, &icon)
+        ));
+        assert!(is_child_allowed(
+            &HtmlTag::Head,
+            Child::Element(&link, &icon)
+        ));
+    }
+
+    #[test]
+    fn foreign_children() {
+        let svg = web::Tag::Svg(crate::svg::SvgTag::Svg);
+        let circle = web::Tag::Svg(crate::svg::SvgTag::Circle);
+
+        assert!(is_child_allowed(&HtmlTag::P, Child::Element(&svg, &[])));
+        assert!(!is_child_allowed(&HtmlTag::P, Child::Element(&circle, &[])));
+    }
+}
@@ -0,0 +1,188 @@
+//!
+//! Form-associated elements and their form owner.
+//!
+//! See: https://html.spec.whatwg.org/#form-associated-element
+//!
+
+use super::{HtmlAttr, HtmlTag};
+use crate::tag::tag_type::flags::*;
+use crate::tag::Tag;
+
+///
+/// Attributes of submit buttons that override attributes of their form owner
+/// when the form is submitted by that button, as `(button attribute, form attribute)`.
+///
+/// See: https://html.spec.whatwg.org/#form-submission-attributes
+///
+pub const SUBMISSION_OVERRIDES: &[(HtmlAttr, HtmlAttr)] = &[
+    (HtmlAttr::Formaction, HtmlAttr::Action),
+    (HtmlAttr::Formenctype, HtmlAttr::Enctype),
+    (HtmlAttr::Formmethod, HtmlAttr::Method),
+    (HtmlAttr::Formnovalidate, HtmlAttr::Novalidate),
+    (HtmlAttr::Formtarget, HtmlAttr::Target),
+];
+
+impl HtmlTag {
+    ///
+    /// Whether this is a form-associated element, which can have a form owner.
+    ///
+    pub fn is_form_associated(&self) -> bool {
+        self.tag_type().any(FORM_ASSOCIATED)
+    }
+
+    ///
+    /// Whether this is a listed element, included in `form.elements`.
+    ///
+    pub fn is_listed(&self) -> bool {
+        self.tag_type().any(LISTED)
+    }
+
+    ///
+    /// Whether this is a labelable element.
+    /// `<input type="hidden">` is not, see [super::content_model::categories].
+    ///
+    pub fn is_labelable(&self) -> bool {
+        self.tag_type().any(LABELABLE)
+    }
+
+    ///
+    /// Whether this is a submittable element.
+    ///
+    pub fn is_submittable(&self) -> bool {
+        self.tag_type().any(SUBMITTABLE)
+    }
+
+    ///
+    /// Whether this is a resettable element.
+    ///
+    pub fn is_resettable(&self) -> bool {
+        self.tag_type().any(RESETTABLE)
+    }
+
+    ///
+    /// Whether this element inherits `autocapitalize` from its form owner.
+    ///
+    pub fn is_autocapitalize_inheriting(&self) -> bool {
+        self.tag_type().any(AUTOCAPITALIZE_INHERITING)
+    }
+
+    ///
+    /// The attribute that explicitly sets the form owner of this element, by id.
+    ///
+    /// Example:
+    /// ```
+    /// use web_ns::html5::{HtmlAttr, HtmlTag};
+    ///
+    /// assert_eq!(HtmlTag::Input.form_owner_attr(), Some(HtmlAttr::Form));
+    /// assert_eq!(HtmlTag::Img.form_owner_attr(), None);
+    /// ```
+    ///
+    pub fn form_owner_attr(&self) -> Option<HtmlAttr> {
+        if self.is_listed() {
+            Some(HtmlAttr::Form)
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// The attributes of this element that override attributes of its form owner,
+    /// see [SUBMISSION_OVERRIDES].
+    ///
+    /// For `<input>`, the overrides only apply to the `submit` and `image` types.
+    ///
+    /// Example:
+    /// ```
+    /// use web_ns::html5::{HtmlAttr, HtmlTag};
+    ///
+    /// assert!(HtmlTag::Button
+    ///     .form_overrides()
+    ///     .contains(&(HtmlAttr::Formmethod, HtmlAttr::Method)));
+    /// assert!(HtmlTag::Select.form_overrides().is_empty());
+    /// ```
+    ///
+    pub fn form_overrides(&self) -> &'static [(HtmlAttr, HtmlAttr)] {
+        match self {
+            HtmlTag::Button | HtmlTag::Input => SUBMISSION_OVERRIDES,
+            _ => &[],
+        }
+    }
+}
+
+///
+/// The form attribute overridden by the submit button attribute `attr`, if any.
+///
+/// Example:
+/// ```
+/// use web_ns::html5::HtmlAttr;
+/// use web_ns::html5::forms::overridden_form_attr;
+///
+/// assert_eq!(overridden_form_attr(&HtmlAttr::Formaction), Some(HtmlAttr::Action));
+/// assert_eq!(overridden_form_attr(&HtmlAttr::Action), None);
+/// ```
+///
+pub fn overridden_form_attr(attr: &HtmlAttr) -> Option<HtmlAttr> {
+    SUBMISSION_OVERRIDES
+        .iter()
+        .find(|(button_attr, _)| button_attr == attr)
+        .map(|(_, form_attr)| form_attr.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn categories() {
+        let tags = [
+            HtmlTag::Button,
+            HtmlTag::Fieldset,
+            HtmlTag::Img,
+            HtmlTag::Input,
+            HtmlTag::Meter,
+            HtmlTag::Output,
+            HtmlTag::Select,
+        ];
+        let summary: Vec<_> = tags
+            .iter()
+            .map(|tag| {
+                (
+                    tag.is_form_associated(),
+                    tag.is_listed(),
+                    tag.is_labelable(),
+                    tag.is_submittable(),
+                    tag.is_resettable(),
+                    tag.is_autocapitalize_inheriting(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (true, true, true, true, false, true),
+                (true, true, false, false, false, true),
+                (true, false, false, false, false, false),
+                (true, true, true, true, true, true),
+                (false, false, true, false, false, false),
+                (true, true, true, false, true, true),
+                (true, true, true, true, true, true),
+            ]
+        );
+        assert!(!HtmlTag::Div.is_form_associated());
+        assert!(!HtmlTag::Label.is_labelable());
+    }
+
+    #[test]
+    fn hidden_input_is_not_labelable() {
+        use crate::attr::AttributeValue;
+        use crate::web;
+
+        let hidden = vec![(
+            web::Attr::Html5(HtmlAttr::Type),
+            AttributeValue::String("hidden".to_string()),
+        )];
+        assert!(crate::html5::content_model::categories(&HtmlTag::Input, &[]).any(LABELABLE));
+        assert!(!crate::html5::content_model::categories(&HtmlTag::Input, &hidden).any(LABELABLE));
+    }
+}
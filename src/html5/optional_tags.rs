@@ -0,0 +1,353 @@
+//!
+//! Optional start and end tags.
+//!
+//! See: https://html.spec.whatwg.org/#optional-tags
+//!
+
+use super::HtmlTag;
+use crate::web;
+
+///
+/// A node next to an element, as seen by the optional tag rules.
+///
+#[derive(Clone, Copy, Debug)]
+pub enum Adjacent<'a> {
+    /// There is no such node, e.g. there is no more content in the parent.
+    Nothing,
+    /// An element.
+    Element(&'a web::Tag),
+    /// A text node.
+    Text(&'a str),
+    /// A comment.
+    Comment,
+}
+
+///
+/// The condition under which an end tag may be omitted.
+///
+#[derive(Clone, Copy, Debug)]
+pub enum EndTagRule {
+    /// Omissible unless immediately followed by a comment.
+    UnlessFollowedByComment,
+    /// Omissible unless immediately followed by ASCII whitespace or a comment.
+    UnlessFollowedByWhitespaceOrComment,
+    /// Omissible when immediately followed by one of the given elements,
+    /// or, depending on [ParentEnd], when there is no more content in the parent.
+    FollowedBy(&'static [HtmlTag], ParentEnd),
+}
+
+///
+/// Whether an end tag may be omitted when there is no more content in the parent.
+///
+#[derive(Clone, Copy, Debug)]
+pub enum ParentEnd {
+    Never,
+    Always,
+    /// Omissible unless the parent is one of the given elements.
+    UnlessParentIn(&'static [HtmlTag]),
+}
+
+const P_FOLLOWERS: &[HtmlTag] = &[
+    HtmlTag::Address,
+    HtmlTag::Article,
+    HtmlTag::Aside,
+    HtmlTag::Blockquote,
+    HtmlTag::Details,
+    HtmlTag::Dialog,
+    HtmlTag::Div,
+    HtmlTag::Dl,
+    HtmlTag::Fieldset,
+    HtmlTag::Figcaption,
+    HtmlTag::Figure,
+    HtmlTag::Footer,
+    HtmlTag::Form,
+    HtmlTag::H1,
+    HtmlTag::H2,
+    HtmlTag::H3,
+    HtmlTag::H4,
+    HtmlTag::H5,
+    HtmlTag::H6,
+    HtmlTag::Header,
+    HtmlTag::Hgroup,
+    HtmlTag::Hr,
+    HtmlTag::Main,
+    HtmlTag::Menu,
+    HtmlTag::Nav,
+    HtmlTag::Ol,
+    HtmlTag::P,
+    HtmlTag::Pre,
+    HtmlTag::Section,
+    HtmlTag::Table,
+    HtmlTag::Ul,
+];
+
+const P_PARENT_EXCEPTIONS: &[HtmlTag] = &[
+    HtmlTag::A,
+    HtmlTag::Audio,
+    HtmlTag::Del,
+    HtmlTag::Ins,
+    HtmlTag::Map,
+    HtmlTag::Noscript,
+    HtmlTag::Video,
+];
+
+///
+/// The end tag omission rule of a tag, if its end tag is optional.
+///
+pub fn end_tag_rule(tag: &HtmlTag) -> Option<EndTagRule> {
+    use EndTagRule::*;
+
+    match tag {
+        HtmlTag::Html | HtmlTag::Body => Some(UnlessFollowedByComment),
+        HtmlTag::Head | HtmlTag::Colgroup | HtmlTag::Caption => {
+            Some(UnlessFollowedByWhitespaceOrComment)
+        }
+        HtmlTag::Li => Some(FollowedBy(&[HtmlTag::Li], ParentEnd::Always)),
+        HtmlTag::Dt => Some(FollowedBy(&[HtmlTag::Dt, HtmlTag::Dd], ParentEnd::Never)),
+        HtmlTag::Dd => Some(FollowedBy(&[HtmlTag::Dd, HtmlTag::Dt], ParentEnd::Always)),
+        HtmlTag::P => Some(FollowedBy(
+            P_FOLLOWERS,
+            ParentEnd::UnlessParentIn(P_PARENT_EXCEPTIONS),
+        )),
+        HtmlTag::Rt | HtmlTag::Rp => {
+            Some(FollowedBy(&[HtmlTag::Rt, HtmlTag::Rp], ParentEnd::Always))
+        }
+        HtmlTag::Optgroup => Some(FollowedBy(
+            &[HtmlTag::Optgroup, HtmlTag::Hr],
+            ParentEnd::Always,
+        )),
+        HtmlTag::Option => Some(FollowedBy(
+            &[HtmlTag::Option, HtmlTag::Optgroup, HtmlTag::Hr],
+            ParentEnd::Always,
+        )),
+        HtmlTag::Thead => Some(FollowedBy(
+            &[HtmlTag::Tbody, HtmlTag::Tfoot],
+            ParentEnd::Never,
+        )),
+        HtmlTag::Tbody => Some(FollowedBy(
+            &[HtmlTag::Tbody, HtmlTag::Tfoot],
+            ParentEnd::Always,
+        )),
+        HtmlTag::Tfoot => Some(FollowedBy(&[], ParentEnd::Always)),
+        HtmlTag::Tr => Some(FollowedBy(&[HtmlTag::Tr], ParentEnd::Always)),
+        HtmlTag::Td | HtmlTag::Th => {
+            Some(FollowedBy(&[HtmlTag::Td, HtmlTag::Th], ParentEnd::Always))
+        }
+        _ => None,
+    }
+}
+
+///
+/// Check whether the end tag of `tag` may be omitted, given its next sibling
+/// and its parent element (if known).
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::html5::HtmlTag;
+/// use web_ns::html5::optional_tags::*;
+///
+/// let li = web::Tag::Html5(HtmlTag::Li);
+/// let ul = web::Tag::Html5(HtmlTag::Ul);
+///
+/// assert!(may_omit_end_tag(&HtmlTag::Li, Adjacent::Element(&li), Some(&ul)));
+/// assert!(may_omit_end_tag(&HtmlTag::Li, Adjacent::Nothing, Some(&ul)));
+/// assert!(!may_omit_end_tag(&HtmlTag::Li, Adjacent::Text("text"), Some(&ul)));
+/// assert!(!may_omit_end_tag(&HtmlTag::Div, Adjacent::Nothing, None));
+/// ```
+///
+pub fn may_omit_end_tag(tag: &HtmlTag, next_sibling: Adjacent, parent: Option<&web::Tag>) -> bool {
+    match end_tag_rule(tag) {
+        None => false,
+        Some(EndTagRule::UnlessFollowedByComment) => !matches!(next_sibling, Adjacent::Comment),
+        Some(EndTagRule::UnlessFollowedByWhitespaceOrComment) => match next_sibling {
+            Adjacent::Comment => false,
+            Adjacent::Text(text) => !starts_with_ascii_whitespace(text),
+            Adjacent::Nothing | Adjacent::Element(_) => true,
+        },
+        Some(EndTagRule::FollowedBy(siblings, parent_end)) => match next_sibling {
+            Adjacent::Element(web::Tag::Html5(sibling)) => siblings.contains(sibling),
+            Adjacent::Element(_) | Adjacent::Text(_) | Adjacent::Comment => false,
+            Adjacent::Nothing => match parent_end {
+                ParentEnd::Never => false,
+                ParentEnd::Always => true,
+                ParentEnd::UnlessParentIn(exceptions) => match parent {
+                    Some(web::Tag::Html5(parent)) => !exceptions.contains(parent),
+                    _ => true,
+                },
+            },
+        },
+    }
+}
+
+///
+/// Check whether the start tag of `tag` may be omitted.
+///
+/// A start tag with attributes may never be omitted.
+/// `previous_sibling` is the element immediately preceding `tag`, if that element
+/// had its end tag omitted. This is relevant for `<colgroup>` and `<tbody>`.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::html5::HtmlTag;
+/// use web_ns::html5::optional_tags::*;
+///
+/// let tr = web::Tag::Html5(HtmlTag::Tr);
+///
+/// assert!(may_omit_start_tag(&HtmlTag::Tbody, false, Adjacent::Element(&tr), None));
+/// assert!(!may_omit_start_tag(&HtmlTag::Tbody, true, Adjacent::Element(&tr), None));
+/// assert!(!may_omit_start_tag(&HtmlTag::Tbody, false, Adjacent::Nothing, None));
+/// ```
+///
+pub fn may_omit_start_tag(
+    tag: &HtmlTag,
+    has_attributes: bool,
+    first_child: Adjacent,
+    previous_sibling: Option<&web::Tag>,
+) -> bool {
+    if has_attributes {
+        return false;
+    }
+
+    match tag {
+        HtmlTag::Html => !matches!(first_child, Adjacent::Comment),
+        HtmlTag::Head => matches!(first_child, Adjacent::Nothing | Adjacent::Element(_)),
+        HtmlTag::Body => match first_child {
+            Adjacent::Nothing => true,
+            Adjacent::Comment => false,
+            Adjacent::Text(text) => !starts_with_ascii_whitespace(text),
+            Adjacent::Element(web::Tag::Html5(child)) => !matches!(
+                child,
+                HtmlTag::Meta
+                    | HtmlTag::Noscript
+                    | HtmlTag::Link
+                    | HtmlTag::Script
+                    | HtmlTag::Style
+                    | HtmlTag::Template
+            ),
+            Adjacent::Element(_) => true,
+        },
+        HtmlTag::Colgroup => {
+            matches!(
+                first_child,
+                Adjacent::Element(web::Tag::Html5(HtmlTag::Col))
+            ) && !matches!(previous_sibling, Some(web::Tag::Html5(HtmlTag::Colgroup)))
+        }
+        HtmlTag::Tbody => {
+            matches!(first_child, Adjacent::Element(web::Tag::Html5(HtmlTag::Tr)))
+                && !matches!(
+                    previous_sibling,
+                    Some(web::Tag::Html5(HtmlTag::Tbody))
+                        | Some(web::Tag::Html5(HtmlTag::Thead))
+                        | Some(web::Tag::Html5(HtmlTag::Tfoot))
+                )
+        }
+        _ => false,
+    }
+}
+
+fn starts_with_ascii_whitespace(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn html(tag: HtmlTag) -> web::Tag {
+        web::Tag::Html5(tag)
+    }
+
+    #[test]
+    fn p_end_tag() {
+        let div = html(HtmlTag::Div);
+        let span = html(HtmlTag::Span);
+        let a = html(HtmlTag::A);
+
+        assert!(may_omit_end_tag(&HtmlTag::P, Adjacent::Element(&div), None));
+        assert!(!may_omit_end_tag(
+            &HtmlTag::P,
+            Adjacent::Element(&span),
+            None
+        ));
+        assert!(may_omit_end_tag(&HtmlTag::P, Adjacent::Nothing, Some(&div)));
+        assert!(!may_omit_end_tag(&HtmlTag::P, Adjacent::Nothing, Some(&a)));
+    }
+
+    #[test]
+    fn whitespace_sensitive_end_tags() {
+        assert!(may_omit_end_tag(&HtmlTag::Head, Adjacent::Text("x"), None));
+        assert!(!may_omit_end_tag(
+            &HtmlTag::Head,
+            Adjacent::Text(" x"),
+            None
+        ));
+        assert!(!may_omit_end_tag(&HtmlTag::Head, Adjacent::Comment, None));
+        assert!(may_omit_end_tag(&HtmlTag::Body, Adjacent::Text(" "), None));
+        assert!(!may_omit_end_tag(&HtmlTag::Body, Adjacent::Comment, None));
+    }
+
+    #[test]
+    fn table_sections() {
+        let tbody = html(HtmlTag::Tbody);
+        let tr = html(HtmlTag::Tr);
+
+        assert!(may_omit_end_tag(
+            &HtmlTag::Thead,
+            Adjacent::Element(&tbody),
+            None
+        ));
+        assert!(!may_omit_end_tag(&HtmlTag::Thead, Adjacent::Nothing, None));
+        assert!(may_omit_end_tag(&HtmlTag::Tfoot, Adjacent::Nothing, None));
+        assert!(!may_omit_end_tag(
+            &HtmlTag::Tfoot,
+            Adjacent::Element(&tr),
+            None
+        ));
+
+        assert!(!may_omit_start_tag(
+            &HtmlTag::Tbody,
+            false,
+            Adjacent::Element(&tr),
+            Some(&html(HtmlTag::Thead))
+        ));
+    }
+
+    #[test]
+    fn body_start_tag() {
+        let script = html(HtmlTag::Script);
+        let div = html(HtmlTag::Div);
+
+        assert!(may_omit_start_tag(
+            &HtmlTag::Body,
+            false,
+            Adjacent::Nothing,
+            None
+        ));
+        assert!(may_omit_start_tag(
+            &HtmlTag::Body,
+            false,
+            Adjacent::Element(&div),
+            None
+        ));
+        assert!(!may_omit_start_tag(
+            &HtmlTag::Body,
+            false,
+            Adjacent::Element(&script),
+            None
+        ));
+        assert!(!may_omit_start_tag(
+            &HtmlTag::Body,
+            false,
+            Adjacent::Text("\n"),
+            None
+        ));
+        assert!(!may_omit_start_tag(
+            &HtmlTag::Div,
+            false,
+            Adjacent::Nothing,
+            None
+        ));
+    }
+}
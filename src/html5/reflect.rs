@@ -0,0 +1,907 @@
+//!
+//! Reflection of content attributes in IDL attributes: what a DOM property
+//! returns for an attribute value, and what attribute value setting it produces.
+//!
+//! See: https://html.spec.whatwg.org/#reflecting-content-attributes-in-idl-attributes
+//!
+
+use super::{HtmlAttr, HtmlTag};
+use crate::attr::idl_type::IdlType;
+use crate::attr::Attribute;
+use crate::Error;
+use std::convert::TryFrom;
+
+///
+/// The value of an IDL attribute.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum IdlValue {
+    Null,
+    String(String),
+    Boolean(bool),
+    Long(i32),
+    UnsignedLong(u32),
+    Double(f64),
+    TokenList(Vec<String>),
+}
+
+///
+/// How an IDL attribute reflects a content attribute.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reflection {
+    /// Not reflected, e.g. event handlers and obsolete attributes.
+    NotReflected,
+    /// The attribute value, or the empty string.
+    String,
+    /// The attribute value resolved as a URL.
+    Url {
+        /// Whether a missing value returns the document URL, e.g. `<form action>`.
+        missing_is_document_url: bool,
+    },
+    /// Whether the attribute is present.
+    Boolean,
+    /// A boolean reflected as keywords, e.g. `draggable="true"`.
+    BooleanKeywords {
+        on: &'static str,
+        off: &'static str,
+        /// Whether the empty string means `on`.
+        empty_is_on: bool,
+        /// The value when missing or invalid.
+        default: bool,
+    },
+    /// A signed integer.
+    Long {
+        default: i32,
+        /// "limited to only non-negative numbers"
+        non_negative: bool,
+    },
+    /// A non-negative integer.
+    UnsignedLong {
+        default: u32,
+        /// "limited to only positive numbers"
+        positive: bool,
+        /// "with fallback": setting zero sets the default instead of failing.
+        fallback: bool,
+        /// "clamped to the range"
+        clamp: Option<(u32, u32)>,
+    },
+    /// A floating point number.
+    Double {
+        default: f64,
+        /// "limited to only positive numbers"
+        positive: bool,
+    },
+    /// An enumerated attribute "limited to only known values".
+    Enumerated {
+        keywords: &'static [&'static str],
+        /// The keyword the empty string maps to, if not itself a keyword.
+        empty: Option<&'static str>,
+        /// The missing value default, `None` if the IDL attribute is nullable.
+        missing: Option<&'static str>,
+        /// The invalid value default.
+        invalid: &'static str,
+    },
+    /// A `DOMTokenList` of space separated tokens.
+    TokenList,
+}
+
+pub(crate) const INPUT_TYPES: &[&str] = &[
+    "hidden",
+    "text",
+    "search",
+    "tel",
+    "url",
+    "email",
+    "password",
+    "date",
+    "month",
+    "week",
+    "time",
+    "datetime-local",
+    "number",
+    "range",
+    "color",
+    "checkbox",
+    "radio",
+    "file",
+    "submit",
+    "image",
+    "reset",
+    "button",
+];
+
+const ENCTYPES: &[&str] = &[
+    "application/x-www-form-urlencoded",
+    "multipart/form-data",
+    "text/plain",
+];
+
+const REFERRER_POLICIES: &[&str] = &[
+    "",
+    "no-referrer",
+    "no-referrer-when-downgrade",
+    "same-origin",
+    "origin",
+    "strict-origin",
+    "origin-when-cross-origin",
+    "strict-origin-when-cross-origin",
+    "unsafe-url",
+];
+
+///
+/// How the IDL attribute of `attr` on `tag` reflects the content attribute.
+///
+/// Example:
+/// ```
+/// use web_ns::html5::{HtmlAttr, HtmlTag};
+/// use web_ns::html5::reflect::{reflection, Reflection};
+///
+/// assert_eq!(
+///     reflection(&HtmlTag::Button, &HtmlAttr::Tabindex),
+///     Reflection::Long { default: 0, non_negative: false }
+/// );
+/// assert_eq!(
+///     reflection(&HtmlTag::Div, &HtmlAttr::Tabindex),
+///     Reflection::Long { default: -1, non_negative: false }
+/// );
+/// assert_eq!(reflection(&HtmlTag::Iframe, &HtmlAttr::Width), Reflection::String);
+/// ```
+///
+pub fn reflection(tag: &HtmlTag, attr: &HtmlAttr) -> Reflection {
+    use HtmlAttr as A;
+    use HtmlTag as T;
+
+    let unsigned_long = |default| Reflection::UnsignedLong {
+        default,
+        positive: false,
+        fallback: false,
+        clamp: None,
+    };
+    let positive_with_fallback = |default| Reflection::UnsignedLong {
+        default,
+        positive: true,
+        fallback: true,
+        clamp: None,
+    };
+    let clamped = |default, min, max| Reflection::UnsignedLong {
+        default,
+        positive: false,
+        fallback: false,
+        clamp: Some((min, max)),
+    };
+    let double = |default| Reflection::Double {
+        default,
+        positive: false,
+    };
+    let enumerated = |keywords, missing, invalid| Reflection::Enumerated {
+        keywords,
+        empty: None,
+        missing: Some(missing),
+        invalid,
+    };
+
+    match (tag, attr) {
+        (_, A::Tabindex) => Reflection::Long {
+            default: if is_focusable_by_default(tag) { 0 } else { -1 },
+            non_negative: false,
+        },
+        (_, A::Maxlength) | (_, A::Minlength) => Reflection::Long {
+            default: -1,
+            non_negative: true,
+        },
+        (T::Ol, A::Start) => Reflection::Long {
+            default: 1,
+            non_negative: false,
+        },
+        (T::Li, A::Value) => Reflection::Long {
+            default: 0,
+            non_negative: false,
+        },
+        (T::Meter, A::Value) | (T::Meter, A::Min) => double(0.0),
+        (T::Meter, A::Low) | (T::Meter, A::High) | (T::Meter, A::Optimum) => double(0.0),
+        (T::Meter, A::Max) => double(1.0),
+        (T::Progress, A::Value) => double(0.0),
+        (T::Progress, A::Max) => Reflection::Double {
+            default: 1.0,
+            positive: true,
+        },
+        (T::Textarea, A::Cols) | (T::Input, A::Size) => positive_with_fallback(20),
+        (T::Textarea, A::Rows) => positive_with_fallback(2),
+        (T::Select, A::Size) => unsigned_long(0),
+        (T::Td, A::Colspan) | (T::Th, A::Colspan) => clamped(1, 1, 1000),
+        (T::Td, A::Rowspan) | (T::Th, A::Rowspan) => clamped(1, 0, 65534),
+        (T::Col, A::Span) | (T::Colgroup, A::Span) => clamped(1, 1, 1000),
+        (T::Canvas, A::Width) => unsigned_long(300),
+        (T::Canvas, A::Height) => unsigned_long(150),
+        (T::Img, A::Width)
+        | (T::Img, A::Height)
+        | (T::Input, A::Width)
+        | (T::Input, A::Height)
+        | (T::Source, A::Width)
+        | (T::Source, A::Height)
+        | (T::Video, A::Width)
+        | (T::Video, A::Height) => unsigned_long(0),
+        (T::Img, A::Hspace) | (T::Img, A::Vspace) => unsigned_long(0),
+        (T::Object, A::Hspace) | (T::Object, A::Vspace) => unsigned_long(0),
+        (_, A::Crossorigin) => Reflection::Enumerated {
+            keywords: &["anonymous", "use-credentials"],
+            empty: Some("anonymous"),
+            missing: None,
+            invalid: "anonymous",
+        },
+        (_, A::Dir) => enumerated(&["ltr", "rtl", "auto"], "", ""),
+        (_, A::Referrerpolicy) => enumerated(REFERRER_POLICIES, "", ""),
+        (_, A::Loading) => enumerated(&["lazy", "eager"], "eager", "eager"),
+        (_, A::Decoding) => enumerated(&["sync", "async", "auto"], "auto", "auto"),
+        (T::Audio, A::Preload) | (T::Video, A::Preload) => Reflection::Enumerated {
+            keywords: &["none", "metadata", "auto"],
+            empty: Some("auto"),
+            missing: Some("metadata"),
+            invalid: "metadata",
+        },
+        (T::Track, A::Kind) => enumerated(
+            &[
+                "subtitles",
+                "captions",
+                "descriptions",
+                "chapters",
+                "metadata",
+            ],
+            "subtitles",
+            "metadata",
+        ),
+        (T::Form, A::Method) => enumerated(&["get", "post", "dialog"], "get", "get"),
+        (_, A::Formmethod) => enumerated(&["get", "post", "dialog"], "", "get"),
+        (T::Form, A::Enctype) => enumerated(ENCTYPES, ENCTYPES[0], ENCTYPES[0]),
+        (_, A::Formenctype) => enumerated(ENCTYPES, "", ENCTYPES[0]),
+        (T::Form, A::Autocomplete) => enumerated(&["on", "off"], "on", "on"),
+        (T::Button, A::Type) => enumerated(&["submit", "reset", "button"], "submit", "submit"),
+        (T::Input, A::Type) => enumerated(INPUT_TYPES, "text", "text"),
+        (T::Th, A::Scope) => enumerated(&["row", "col", "rowgroup", "colgroup"], "", ""),
+        (_, A::Inputmode) => enumerated(
+            &[
+                "none", "text", "tel", "url", "email", "numeric", "decimal", "search",
+            ],
+            "",
+            "",
+        ),
+        (_, A::Enterkeyhint) => enumerated(
+            &["enter", "done", "go", "next", "previous", "search", "send"],
+            "",
+            "",
+        ),
+        (_, A::Contenteditable) => Reflection::Enumerated {
+            keywords: &["true", "false", "plaintext-only"],
+            empty: Some("true"),
+            missing: Some("inherit"),
+            invalid: "inherit",
+        },
+        (_, A::Draggable) => Reflection::BooleanKeywords {
+            on: "true",
+            off: "false",
+            empty_is_on: false,
+            default: matches!(tag, T::Img),
+        },
+        (_, A::Spellcheck) => Reflection::BooleanKeywords {
+            on: "true",
+            off: "false",
+            empty_is_on: true,
+            default: true,
+        },
+        (_, A::Translate) => Reflection::BooleanKeywords {
+            on: "yes",
+            off: "no",
+            empty_is_on: true,
+            default: true,
+        },
+        (T::Form, A::Action) | (_, A::Formaction) => Reflection::Url {
+            missing_is_document_url: true,
+        },
+        (_, A::Ping) | (_, A::Srcset) | (_, A::Imagesrcset) => Reflection::String,
+        _ => match attr.idl_type() {
+            Some(IdlType::Boolean) => Reflection::Boolean,
+            Some(IdlType::UsvString) => Reflection::Url {
+                missing_is_document_url: false,
+            },
+            Some(IdlType::DomTokenList) => Reflection::TokenList,
+            // Numeric attributes of other elements than the ones above are strings,
+            // e.g. `<iframe width>`.
+            Some(IdlType::DomString)
+            | Some(IdlType::Long)
+            | Some(IdlType::UnsignedLong)
+            | Some(IdlType::Double) => Reflection::String,
+            _ => Reflection::NotReflected,
+        },
+    }
+}
+
+///
+/// Get the IDL attribute value reflecting the content attribute `value`
+/// of `attr` on `tag`, where `None` means the attribute is missing.
+///
+/// URLs are resolved relative to `base_url`, if given.
+///
+/// Example:
+/// ```
+/// use web_ns::html5::{HtmlAttr, HtmlTag};
+/// use web_ns::html5::reflect::{reflect_get, IdlValue};
+///
+/// assert_eq!(
+///     reflect_get(&HtmlTag::Input, &HtmlAttr::Maxlength, Some("-5"), None),
+///     IdlValue::Long(-1)
+/// );
+/// assert_eq!(
+///     reflect_get(&HtmlTag::Input, &HtmlAttr::Type, Some("CHECKBOX"), None),
+///     IdlValue::String("checkbox".to_string())
+/// );
+/// assert_eq!(
+///     reflect_get(&HtmlTag::Input, &HtmlAttr::Type, Some("foo"), None),
+///     IdlValue::String("text".to_string())
+/// );
+/// assert_eq!(
+///     reflect_get(&HtmlTag::A, &HtmlAttr::Href, Some("../b"), Some("https://x.org/a/c")),
+///     IdlValue::String("https://x.org/b".to_string())
+/// );
+/// assert_eq!(
+///     reflect_get(&HtmlTag::Input, &HtmlAttr::Disabled, Some(""), None),
+///     IdlValue::Boolean(true)
+/// );
+/// ```
+///
+pub fn reflect_get(
+    tag: &HtmlTag,
+    attr: &HtmlAttr,
+    value: Option<&str>,
+    base_url: Option<&str>,
+) -> IdlValue {
+    match reflection(tag, attr) {
+        Reflection::NotReflected => IdlValue::Null,
+        Reflection::String => IdlValue::String(value.unwrap_or("").to_string()),
+        Reflection::Url {
+            missing_is_document_url,
+        } => {
+            // A present but empty value resolves to the base URL itself.
+            let value = match value {
+                Some(value) => value,
+                None if missing_is_document_url => base_url.unwrap_or(""),
+                None => return IdlValue::String(String::new()),
+            };
+            IdlValue::String(
+                base_url
+                    .and_then(|base_url| crate::url::resolve(base_url, value))
+                    .unwrap_or_else(|| value.to_string()),
+            )
+        }
+        Reflection::Boolean => IdlValue::Boolean(value.is_some()),
+        Reflection::BooleanKeywords {
+            on,
+            off,
+            empty_is_on,
+            default,
+        } => IdlValue::Boolean(match value {
+            Some(value) if value.eq_ignore_ascii_case(on) => true,
+            Some(value) if value.eq_ignore_ascii_case(off) => false,
+            Some("") if empty_is_on => true,
+            _ => default,
+        }),
+        Reflection::Long {
+            default,
+            non_negative,
+        } => IdlValue::Long(
+            value
+                .and_then(parse_integer)
+                .and_then(|value| i32::try_from(value).ok())
+                .filter(|value| !non_negative || *value >= 0)
+                .unwrap_or(default),
+        ),
+        Reflection::UnsignedLong {
+            default,
+            positive,
+            clamp,
+            ..
+        } => {
+            let parsed = value.and_then(parse_integer).filter(|value| *value >= 0);
+            IdlValue::UnsignedLong(match (parsed, clamp) {
+                (None, _) => default,
+                (Some(value), Some((min, max))) => value.max(min as i64).min(max as i64) as u32,
+                (Some(value), None) => match u32::try_from(value) {
+                    Ok(value) if value > i32::MAX as u32 => default,
+                    Ok(0) if positive => default,
+                    Ok(value) => value,
+                    Err(_) => default,
+                },
+            })
+        }
+        Reflection::Double { default, positive } => IdlValue::Double(
+            value
+                .and_then(parse_double)
+                .filter(|value| !positive || *value > 0.0)
+                .unwrap_or(default),
+        ),
+        Reflection::Enumerated {
+            keywords,
+            empty,
+            missing,
+            invalid,
+        } => {
+            let keyword = match value {
+                None => missing,
+                Some("") if empty.is_some() => empty,
+                Some(value) => Some(
+                    keywords
+                        .iter()
+                        .find(|keyword| keyword.eq_ignore_ascii_case(value))
+                        .copied()
+                        .unwrap_or(invalid),
+                ),
+            };
+            match keyword {
+                Some(keyword) => IdlValue::String(keyword.to_string()),
+                None => IdlValue::Null,
+            }
+        }
+        Reflection::TokenList => IdlValue::TokenList(
+            value
+                .unwrap_or("")
+                .split_ascii_whitespace()
+                .map(str::to_string)
+                .collect(),
+        ),
+    }
+}
+
+///
+/// Set the IDL attribute of `attr` on `tag` to `value`, and get the resulting
+/// content attribute value, where `None` means the attribute is removed.
+///
+/// Values of other types than the IDL attribute's are converted like in JavaScript.
+/// Returns an error when a browser would throw an exception or ignore the value,
+/// e.g. for negative values of attributes limited to non-negative numbers.
+///
+/// Example:
+/// ```
+/// use web_ns::html5::{HtmlAttr, HtmlTag};
+/// use web_ns::html5::reflect::{reflect_set, IdlValue};
+///
+/// let set = |tag, attr, value| reflect_set(&tag, &attr, value).unwrap();
+///
+/// assert_eq!(set(HtmlTag::Input, HtmlAttr::Disabled, IdlValue::Boolean(false)), None);
+/// assert_eq!(set(HtmlTag::Input, HtmlAttr::Disabled, IdlValue::Boolean(true)), Some("".to_string()));
+/// assert_eq!(set(HtmlTag::Textarea, HtmlAttr::Cols, IdlValue::UnsignedLong(0)), Some("20".to_string()));
+/// assert_eq!(set(HtmlTag::Div, HtmlAttr::Tabindex, IdlValue::String("3".into())), Some("3".to_string()));
+///
+/// assert!(reflect_set(&HtmlTag::Input, &HtmlAttr::Maxlength, IdlValue::Long(-1)).is_err());
+/// ```
+///
+pub fn reflect_set(
+    tag: &HtmlTag,
+    attr: &HtmlAttr,
+    value: IdlValue,
+) -> Result<Option<String>, Error> {
+    match reflection(tag, attr) {
+        Reflection::NotReflected => Err(Error::InvalidAttribute),
+        Reflection::String | Reflection::Url { .. } | Reflection::TokenList => {
+            Ok(Some(value.to_idl_string()))
+        }
+        Reflection::Boolean => Ok(if value.to_boolean() {
+            Some(String::new())
+        } else {
+            None
+        }),
+        Reflection::BooleanKeywords { on, off, .. } => {
+            Ok(Some(if value.to_boolean() { on } else { off }.to_string()))
+        }
+        Reflection::Long { non_negative, .. } => {
+            let value = value.to_long();
+            if non_negative && value < 0 {
+                Err(Error::InvalidAttributeValue)
+            } else {
+                Ok(Some(value.to_string()))
+            }
+        }
+        Reflection::UnsignedLong {
+            default,
+            positive,
+            fallback,
+            ..
+        } => {
+            let value = value.to_unsigned_long();
+            let value = match value {
+                0 if positive && fallback => default,
+                0 if positive => return Err(Error::InvalidAttributeValue),
+                value if value > i32::MAX as u32 => default,
+                value => value,
+            };
+            Ok(Some(value.to_string()))
+        }
+        Reflection::Double { positive, .. } => {
+            let value = value.to_number();
+            if !value.is_finite() || (positive && value <= 0.0) {
+                Err(Error::InvalidAttributeValue)
+            } else {
+                Ok(Some(format_number(value)))
+            }
+        }
+        Reflection::Enumerated { missing, .. } => match value {
+            IdlValue::Null if missing.is_none() => Ok(None),
+            value if matches!(attr, HtmlAttr::Contenteditable) => {
+                let value = value.to_idl_string().to_ascii_lowercase();
+                match value.as_str() {
+                    "inherit" => Ok(None),
+                    "true" | "false" | "plaintext-only" => Ok(Some(value)),
+                    _ => Err(Error::InvalidAttributeValue),
+                }
+            }
+            value => Ok(Some(value.to_idl_string())),
+        },
+    }
+}
+
+impl IdlValue {
+    ///
+    /// Convert to a string, like JavaScript's `String(value)`.
+    ///
+    pub fn to_idl_string(&self) -> String {
+        match self {
+            Self::Null => "null".to_string(),
+            Self::String(value) => value.clone(),
+            Self::Boolean(value) => value.to_string(),
+            Self::Long(value) => value.to_string(),
+            Self::UnsignedLong(value) => value.to_string(),
+            Self::Double(value) => format_number(*value),
+            Self::TokenList(tokens) => tokens.join(" "),
+        }
+    }
+
+    ///
+    /// Convert to a boolean, like JavaScript's `Boolean(value)`.
+    ///
+    pub fn to_boolean(&self) -> bool {
+        match self {
+            Self::Null => false,
+            Self::String(value) => !value.is_empty(),
+            Self::Boolean(value) => *value,
+            Self::Long(value) => *value != 0,
+            Self::UnsignedLong(value) => *value != 0,
+            Self::Double(value) => *value != 0.0 && !value.is_nan(),
+            Self::TokenList(_) => true,
+        }
+    }
+
+    ///
+    /// Convert to a number, like JavaScript's `Number(value)`.
+    ///
+    pub fn to_number(&self) -> f64 {
+        match self {
+            Self::Null => 0.0,
+            Self::String(value) => {
+                let value = value.trim();
+                if value.is_empty() {
+                    0.0
+                } else {
+                    value.parse().unwrap_or(f64::NAN)
+                }
+            }
+            Self::Boolean(value) => f64::from(u8::from(*value)),
+            Self::Long(value) => f64::from(*value),
+            Self::UnsignedLong(value) => f64::from(*value),
+            Self::Double(value) => *value,
+            Self::TokenList(_) => f64::NAN,
+        }
+    }
+
+    ///
+    /// Convert to a WebIDL `long`.
+    ///
+    pub fn to_long(&self) -> i32 {
+        self.to_unsigned_long() as i32
+    }
+
+    ///
+    /// Convert to a WebIDL `unsigned long`.
+    ///
+    pub fn to_unsigned_long(&self) -> u32 {
+        let value = self.to_number();
+        if value.is_finite() {
+            value.trunc().rem_euclid(4294967296.0) as u32
+        } else {
+            0
+        }
+    }
+}
+
+fn is_focusable_by_default(tag: &HtmlTag) -> bool {
+    use HtmlTag::*;
+    matches!(
+        tag,
+        A | Area | Button | Frame | Iframe | Input | Object | Select | Summary | Textarea
+    )
+}
+
+///
+/// See: https://html.spec.whatwg.org/#rules-for-parsing-integers
+///
+fn parse_integer(input: &str) -> Option<i64> {
+    let input = input.trim_start_matches(|c: char| c.is_ascii_whitespace());
+    let (negative, input) = match input.as_bytes().first() {
+        Some(b'-') => (true, &input[1..]),
+        Some(b'+') => (false, &input[1..]),
+        _ => (false, input),
+    };
+    let digits = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    if digits == 0 {
+        return None;
+    }
+    // Values too large for an i64 are out of range for any IDL integer.
+    let value: i64 = input[..digits].parse().unwrap_or(i64::MAX);
+    Some(if negative { -value } else { value })
+}
+
+///
+/// See: https://html.spec.whatwg.org/#rules-for-parsing-floating-point-number-values
+///
+fn parse_double(input: &str) -> Option<f64> {
+    let input = input.trim_start_matches(|c: char| c.is_ascii_whitespace());
+    let bytes = input.as_bytes();
+    let mut end = 0;
+
+    if matches!(bytes.first(), Some(b'-') | Some(b'+')) {
+        end += 1;
+    }
+    let digits_start = end;
+    while end < bytes.len() && bytes[end].is_ascii_digit() {
+        end += 1;
+    }
+    if end < bytes.len() && bytes[end] == b'.' {
+        let fraction_start = end + 1;
+        let mut fraction_end = fraction_start;
+        while fraction_end < bytes.len() && bytes[fraction_end].is_ascii_digit() {
+            fraction_end += 1;
+        }
+        if fraction_end > fraction_start {
+            end = fraction_end;
+        }
+    }
+    if end == digits_start || (end == digits_start + 1 && bytes[digits_start] == b'.') {
+        return None;
+    }
+    if end < bytes.len() && (bytes[end] == b'e' || bytes[end] == b'E') {
+        let mut exponent_end = end + 1;
+        if matches!(bytes.get(exponent_end), Some(b'-') | Some(b'+')) {
+            exponent_end += 1;
+        }
+        let exponent_digits = exponent_end;
+        while exponent_end < bytes.len() && bytes[exponent_end].is_ascii_digit() {
+            exponent_end += 1;
+        }
+        if exponent_end > exponent_digits {
+            end = exponent_end;
+        }
+    }
+
+    input[..end]
+        .trim_start_matches('+')
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+}
+
+///
+/// Format a number like JavaScript's `String(number)`, for the common cases.
+///
+fn format_number(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+    } else if value == 0.0 {
+        "0".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(tag: HtmlTag, attr: HtmlAttr, value: Option<&str>) -> IdlValue {
+        reflect_get(&tag, &attr, value, None)
+    }
+
+    fn string(value: &str) -> IdlValue {
+        IdlValue::String(value.to_string())
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(
+            get(HtmlTag::Div, HtmlAttr::Tabindex, None),
+            IdlValue::Long(-1)
+        );
+        assert_eq!(
+            get(HtmlTag::A, HtmlAttr::Tabindex, Some("x")),
+            IdlValue::Long(0)
+        );
+        assert_eq!(
+            get(HtmlTag::Div, HtmlAttr::Tabindex, Some(" 7px")),
+            IdlValue::Long(7)
+        );
+        assert_eq!(
+            get(HtmlTag::Div, HtmlAttr::Tabindex, Some("99999999999")),
+            IdlValue::Long(-1)
+        );
+        assert_eq!(
+            get(HtmlTag::Td, HtmlAttr::Colspan, Some("5000")),
+            IdlValue::UnsignedLong(1000)
+        );
+        assert_eq!(
+            get(HtmlTag::Td, HtmlAttr::Colspan, Some("0")),
+            IdlValue::UnsignedLong(1)
+        );
+        assert_eq!(
+            get(HtmlTag::Textarea, HtmlAttr::Rows, Some("0")),
+            IdlValue::UnsignedLong(2)
+        );
+        assert_eq!(
+            get(HtmlTag::Canvas, HtmlAttr::Width, Some("-1")),
+            IdlValue::UnsignedLong(300)
+        );
+        assert_eq!(
+            get(HtmlTag::Li, HtmlAttr::Value, Some("-3")),
+            IdlValue::Long(-3)
+        );
+        assert_eq!(
+            get(HtmlTag::Input, HtmlAttr::Value, Some("-3")),
+            string("-3")
+        );
+    }
+
+    #[test]
+    fn doubles() {
+        assert_eq!(
+            get(HtmlTag::Meter, HtmlAttr::Value, Some("0.5e1x")),
+            IdlValue::Double(5.0)
+        );
+        assert_eq!(
+            get(HtmlTag::Progress, HtmlAttr::Max, Some("-1")),
+            IdlValue::Double(1.0)
+        );
+        assert_eq!(
+            reflect_set(&HtmlTag::Meter, &HtmlAttr::Low, IdlValue::Double(0.25)).unwrap(),
+            Some("0.25".to_string())
+        );
+        assert!(reflect_set(&HtmlTag::Meter, &HtmlAttr::Low, IdlValue::Double(f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn enumerated() {
+        assert_eq!(
+            get(HtmlTag::Img, HtmlAttr::Crossorigin, None),
+            IdlValue::Null
+        );
+        assert_eq!(
+            get(HtmlTag::Img, HtmlAttr::Crossorigin, Some("")),
+            string("anonymous")
+        );
+        assert_eq!(
+            get(HtmlTag::Img, HtmlAttr::Crossorigin, Some("USE-CREDENTIALS")),
+            string("use-credentials")
+        );
+        assert_eq!(
+            get(HtmlTag::Div, HtmlAttr::Dir, Some("sideways")),
+            string("")
+        );
+        assert_eq!(get(HtmlTag::Form, HtmlAttr::Method, None), string("get"));
+        assert_eq!(get(HtmlTag::Button, HtmlAttr::Formmethod, None), string(""));
+        assert_eq!(
+            get(HtmlTag::Video, HtmlAttr::Preload, Some("")),
+            string("auto")
+        );
+        assert_eq!(
+            get(HtmlTag::Div, HtmlAttr::Contenteditable, None),
+            string("inherit")
+        );
+
+        assert_eq!(
+            reflect_set(&HtmlTag::Img, &HtmlAttr::Crossorigin, IdlValue::Null).unwrap(),
+            None
+        );
+        assert_eq!(
+            reflect_set(&HtmlTag::Div, &HtmlAttr::Contenteditable, string("Inherit")).unwrap(),
+            None
+        );
+        assert!(reflect_set(&HtmlTag::Div, &HtmlAttr::Contenteditable, string("yes")).is_err());
+    }
+
+    #[test]
+    fn booleans() {
+        assert_eq!(
+            get(HtmlTag::Img, HtmlAttr::Draggable, None),
+            IdlValue::Boolean(true)
+        );
+        assert_eq!(
+            get(HtmlTag::Div, HtmlAttr::Draggable, Some("TRUE")),
+            IdlValue::Boolean(true)
+        );
+        assert_eq!(
+            get(HtmlTag::Div, HtmlAttr::Translate, Some("no")),
+            IdlValue::Boolean(false)
+        );
+        assert_eq!(
+            reflect_set(
+                &HtmlTag::Div,
+                &HtmlAttr::Translate,
+                IdlValue::Boolean(false)
+            )
+            .unwrap(),
+            Some("no".to_string())
+        );
+        assert_eq!(
+            reflect_set(&HtmlTag::Div, &HtmlAttr::Hidden, string("")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn urls_and_strings() {
+        let base = Some("https://example.com/dir/page");
+
+        assert_eq!(
+            reflect_get(&HtmlTag::Form, &HtmlAttr::Action, None, base),
+            string("https://example.com/dir/page")
+        );
+        assert_eq!(
+            reflect_get(&HtmlTag::A, &HtmlAttr::Href, None, base),
+            string("")
+        );
+        assert_eq!(
+            reflect_get(&HtmlTag::Img, &HtmlAttr::Src, Some("x.png"), base),
+            string("https://example.com/dir/x.png")
+        );
+        assert_eq!(
+            reflect_get(&HtmlTag::Img, &HtmlAttr::Srcset, Some("x.png 2x"), base),
+            string("x.png 2x")
+        );
+        assert_eq!(
+            get(
+                HtmlTag::Iframe,
+                HtmlAttr::Sandbox,
+                Some(" allow-forms  allow-popups")
+            ),
+            IdlValue::TokenList(vec!["allow-forms".to_string(), "allow-popups".to_string()])
+        );
+        assert_eq!(
+            get(HtmlTag::Div, HtmlAttr::Onclick, Some("f()")),
+            IdlValue::Null
+        );
+        assert_eq!(
+            reflect_get(&HtmlTag::A, &HtmlAttr::Href, Some(""), base),
+            string("https://example.com/dir/page")
+        );
+        assert_eq!(
+            reflect_get(&HtmlTag::Form, &HtmlAttr::Action, Some(""), base),
+            string("https://example.com/dir/page")
+        );
+        assert_eq!(
+            get(HtmlTag::Div, HtmlAttr::Itemscope, Some("")),
+            IdlValue::Boolean(true)
+        );
+        assert_eq!(
+            reflect_set(&HtmlTag::Div, &HtmlAttr::Itemscope, IdlValue::Boolean(true)).unwrap(),
+            Some(String::new())
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(IdlValue::Double(-1.0).to_unsigned_long(), 4294967295);
+        assert_eq!(IdlValue::Double(4294967297.5).to_long(), 1);
+        assert_eq!(IdlValue::String(" 12 ".to_string()).to_long(), 12);
+        assert_eq!(IdlValue::Double(1.0).to_idl_string(), "1");
+        assert_eq!(IdlValue::Double(f64::INFINITY).to_idl_string(), "Infinity");
+    }
+}
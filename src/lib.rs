@@ -31,7 +31,21 @@
 
 #![forbid(unsafe_code)]
 
+pub mod a11y;
 pub mod attr;
+pub mod csp;
+pub mod dom;
+pub mod element;
+pub mod escape;
+pub mod limits;
+pub mod rewriter;
+pub mod sanitize;
+pub mod selector;
+pub mod status;
+pub mod tag;
+pub mod tokenizer;
+pub mod url;
+pub mod validate;
 pub mod web;
 
 pub mod html5;
@@ -40,6 +54,7 @@ pub mod svg;
 mod static_unicase;
 
 pub use attr::*;
+pub use tag::*;
 
 struct Private;
 
@@ -57,6 +72,41 @@ pub trait PropertyName {
     fn property_name(&self) -> &str;
 }
 
+///
+/// Trait for anything that has a DOM interface, for tags the name of the
+/// interface of the element created for the tag.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+///
+/// assert_eq!(html5::HtmlTag::A.interface_name(), "HTMLAnchorElement");
+/// assert_eq!(html5::HtmlTag::Td.interface_name(), "HTMLTableCellElement");
+/// assert_eq!(html5::HtmlTag::Blink.interface_name(), "HTMLUnknownElement");
+/// assert_eq!(svg::SvgTag::FeBlend.interface_name(), "SVGFEBlendElement");
+/// ```
+///
+pub trait InterfaceName {
+    fn interface_name(&self) -> &str;
+}
+
+///
+/// Trait for the DOM `tagName` of tags, the qualified name of the element
+/// which is uppercased for HTML elements.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+///
+/// assert_eq!(html5::HtmlTag::Div.tag_name(), "DIV");
+/// assert_eq!(html5::HtmlTag::Svg.tag_name(), "svg");
+/// assert_eq!(svg::SvgTag::ClipPath.tag_name(), "clipPath");
+/// ```
+///
+pub trait TagName {
+    fn tag_name(&self) -> &str;
+}
+
 ///
 /// Trait for accessing "voidness" of tag names.
 /// a tag being "Void" means that it must be self closing:
@@ -78,6 +128,35 @@ pub trait IsVoid {
     fn is_void(&self) -> bool;
 }
 
+///
+/// Trait for the status of tags and attributes in their specification.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::status::Status;
+///
+/// assert_eq!(html5::HtmlTag::Center.status(), Status::Obsolete);
+/// assert_eq!(html5::HtmlTag::Center.replacement_hint(), Some("Use CSS `text-align` or `margin: auto`"));
+/// assert!(html5::HtmlAttr::Vlink.is_obsolete());
+/// assert_eq!(svg::SvgAttr::ZoomAndPan.status(), Status::Deprecated);
+/// assert_eq!(web::Tag::Html5(html5::HtmlTag::Div).status(), Status::Current);
+/// assert_eq!(web::Tag::Html5(html5::HtmlTag::Div).replacement_hint(), None);
+/// ```
+///
+pub trait Conformance {
+    fn status(&self) -> status::Status;
+
+    ///
+    /// A hint on what to use instead, for tags and attributes that are not current.
+    ///
+    fn replacement_hint(&self) -> Option<&'static str>;
+
+    fn is_obsolete(&self) -> bool {
+        self.status() == status::Status::Obsolete
+    }
+}
+
 ///
 /// Tag lookup by the tag's local name.
 /// name being local means that this trait should be implemented by namespaces.
@@ -118,7 +197,7 @@ pub trait TagByLocalName<T> {
 ///
 pub trait AttrByLocalName<A> {
     ///
-    ///
+    /// Do attribute lookup by local name.
     ///
     fn attr_by_local_name(&self, local_name: &str) -> Result<A, Error>;
 }
@@ -131,5 +210,8 @@ pub trait AttrByProperty<A> {
 pub enum Error {
     InvalidAttribute,
     InvalidAttributeValue,
+    InvalidSelector,
+    InvalidText,
+    LimitExceeded(limits::Limit),
     NamespaceMismatch,
 }
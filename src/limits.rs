@@ -0,0 +1,108 @@
+//!
+//! Resource limits for parsing untrusted names and values.
+//!
+//! The parsing APIs allocate in proportion to their input. Services parsing
+//! untrusted markup can pass [Limits] to the `_with_limits` variants to cap
+//! that, which fail with [Error::LimitExceeded] before parsing a name or
+//! value any further. Callers still own the input, so limiting its total
+//! size up front is left to them.
+//!
+
+use crate::{AttrByLocalName, Error};
+
+///
+/// A limit that was exceeded.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+    NameLength,
+    ValueLength,
+    Tokens,
+    DataAttrs,
+}
+
+///
+/// Limits on names and values, by default unlimited.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::limits::{Limit, Limits};
+///
+/// let limits = Limits { max_tokens: 2, ..Limits::default() };
+/// let class = web::Attr::Html5(html5::HtmlAttr::Class);
+///
+/// assert!(class.deserialize_attribute_value_with_limits(Some("a b"), &limits).is_ok());
+/// assert!(matches!(
+///     class.deserialize_attribute_value_with_limits(Some("a b c"), &limits),
+///     Err(Error::LimitExceeded(Limit::Tokens))
+/// ));
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum length of a tag or attribute name, in bytes.
+    pub max_name_len: usize,
+    /// The maximum length of an attribute value, in bytes.
+    pub max_value_len: usize,
+    /// The maximum number of tokens in a list value, e.g. `class`.
+    pub max_tokens: usize,
+    /// The maximum number of `data-*` attributes on an element.
+    pub max_data_attrs: usize,
+}
+
+impl Limits {
+    pub const UNLIMITED: Limits = Limits {
+        max_name_len: usize::MAX,
+        max_value_len: usize::MAX,
+        max_tokens: usize::MAX,
+        max_data_attrs: usize::MAX,
+    };
+
+    pub fn check_name(&self, name: &str) -> Result<(), Error> {
+        if name.len() > self.max_name_len {
+            Err(Error::LimitExceeded(Limit::NameLength))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn check_value(&self, value: &str) -> Result<(), Error> {
+        if value.len() > self.max_value_len {
+            Err(Error::LimitExceeded(Limit::ValueLength))
+        } else {
+            Ok(())
+        }
+    }
+
+    ///
+    /// Attribute lookup by local name, checking the length of the name first.
+    ///
+    /// Example:
+    /// ```
+    /// use web_ns::*;
+    /// use web_ns::limits::{Limit, Limits};
+    ///
+    /// let limits = Limits { max_name_len: 8, ..Limits::default() };
+    /// let div = web::Tag::Html5(html5::HtmlTag::Div);
+    ///
+    /// let attr: Result<web::Attr, Error> = limits.attr_by_local_name(&div, "data-id");
+    /// assert!(attr.is_ok());
+    /// let attr: Result<web::Attr, Error> = limits.attr_by_local_name(&div, "data-aaaaaaaaaa");
+    /// assert!(matches!(attr, Err(Error::LimitExceeded(Limit::NameLength))));
+    /// ```
+    ///
+    pub fn attr_by_local_name<T, A>(&self, tag: &T, local_name: &str) -> Result<A, Error>
+    where
+        T: AttrByLocalName<A>,
+    {
+        self.check_name(local_name)?;
+        tag.attr_by_local_name(local_name)
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
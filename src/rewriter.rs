@@ -0,0 +1,955 @@
+//!
+//! HTML rewriting without building a tree.
+//!
+//! A [Rewriter] runs the input through the [crate::tokenizer] and calls the
+//! registered handlers for matching start tags, without building a tree.
+//! Handlers see typed attributes, and may change them, insert content around
+//! and inside the element, or remove it.
+//!
+//! Everything not touched by a handler is written exactly as in the input.
+//! Changed attribute values and inserted text are escaped by [crate::escape].
+//! Input arriving in chunks can be rewritten with [Rewriter::stream].
+//!
+
+use crate::attr::{
+    AttributeValue, DeserializeAttributeValue, SerializeAttributeValue, SerializedAttributeValue,
+};
+use crate::dom::closes;
+use crate::escape::{escape_attribute_value, escape_text};
+use crate::html5::HtmlTag;
+use crate::svg::SvgTag;
+use crate::tokenizer::char_ref::is_ascii_whitespace;
+use crate::tokenizer::{Checkpoint, Resolved, Span, StartTag, TokenKind, Tokenizer};
+use crate::{web, AttrByLocalName, Error, IsVoid, LocalName};
+
+///
+/// How inserted content is written.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContentType {
+    /// Text, escaped for the element it is inserted into.
+    Text,
+    /// Markup, written verbatim.
+    Html,
+}
+
+type ElementHandler<'h> = Box<dyn FnMut(&mut RewriteElement<'_>) -> Result<(), Error> + 'h>;
+
+enum Target {
+    Tag(web::Tag),
+    Attr(web::Attr),
+}
+
+///
+/// An HTML rewriter, with handlers registered by tag or attribute.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::rewriter::{ContentType, Rewriter};
+///
+/// let mut rewriter = Rewriter::new()
+///     .on_attr(web::Attr::Html5(html5::HtmlAttr::Src), |img| {
+///         if let Some(AttributeValue::String(src)) = img.attr(&web::Attr::Html5(html5::HtmlAttr::Src)) {
+///             let src = format!("/proxy?url={}&w=100", src);
+///             img.set_attr(web::Attr::Html5(html5::HtmlAttr::Src), AttributeValue::String(src))?;
+///         }
+///         Ok(())
+///     })
+///     .on_tag(web::Tag::Html5(html5::HtmlTag::Script), |script| {
+///         script.remove();
+///         script.after("<removed>", ContentType::Text)
+///     });
+///
+/// assert_eq!(
+///     rewriter.rewrite("<P>A <IMG alt=x SRC='a.png'><script>alert(1)</script>").unwrap(),
+///     r#"<P>A <IMG alt=x src="/proxy?url=a.png&amp;w=100">&lt;removed&gt;"#
+/// );
+/// ```
+///
+#[derive(Default)]
+pub struct Rewriter<'h> {
+    handlers: Vec<(Target, ElementHandler<'h>)>,
+}
+
+impl<'h> Rewriter<'h> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Register a handler for start tags of `tag`.
+    ///
+    pub fn on_tag<F>(mut self, tag: web::Tag, handler: F) -> Self
+    where
+        F: FnMut(&mut RewriteElement<'_>) -> Result<(), Error> + 'h,
+    {
+        self.handlers.push((Target::Tag(tag), Box::new(handler)));
+        self
+    }
+
+    ///
+    /// Register a handler for start tags that have `attr`.
+    ///
+    pub fn on_attr<F>(mut self, attr: web::Attr, handler: F) -> Self
+    where
+        F: FnMut(&mut RewriteElement<'_>) -> Result<(), Error> + 'h,
+    {
+        self.handlers.push((Target::Attr(attr), Box::new(handler)));
+        self
+    }
+
+    ///
+    /// Rewrite `input`, collecting the output into a string.
+    ///
+    pub fn rewrite(&mut self, input: &str) -> Result<String, Error> {
+        let mut output = String::with_capacity(input.len());
+        self.rewrite_to(input, |chunk| output.push_str(chunk))?;
+        Ok(output)
+    }
+
+    ///
+    /// Rewrite `input`, passing the output to `sink` in chunks as it is produced.
+    ///
+    /// `input` must be the complete document or fragment: content inserted at the
+    /// end of elements still open at its end is written last, see [Rewriter::stream]
+    /// otherwise. Stops at the first error returned by a handler.
+    ///
+    pub fn rewrite_to<F>(&mut self, input: &str, mut sink: F) -> Result<(), Error>
+    where
+        F: FnMut(&str),
+    {
+        let mut output = Output::new(&mut sink);
+        self.rewrite_tokens(&mut output, input, &mut Checkpoint::default(), true)?;
+        output.finish();
+        Ok(())
+    }
+
+    ///
+    /// Rewrite input arriving in chunks, e.g. from the network, passing the
+    /// output to `sink` as it is produced.
+    ///
+    /// Example:
+    /// ```
+    /// use web_ns::*;
+    /// use web_ns::rewriter::Rewriter;
+    ///
+    /// let mut output = String::new();
+    /// let mut rewriter = Rewriter::new().on_tag(web::Tag::Html5(html5::HtmlTag::A), |a| {
+    ///     a.set_attr_str("rel", Some("nofollow"))
+    /// });
+    ///
+    /// let mut stream = rewriter.stream(|chunk| output.push_str(chunk));
+    /// stream.write("<p>a <a hr").unwrap();
+    /// stream.write("ef=/x>b</a></p>").unwrap();
+    /// stream.end().unwrap();
+    ///
+    /// assert_eq!(output, r#"<p>a <a href=/x rel="nofollow">b</a></p>"#);
+    /// ```
+    ///
+    pub fn stream<'r, F>(&'r mut self, sink: F) -> RewriteStream<'r, 'h>
+    where
+        F: FnMut(&str) + 'r,
+    {
+        RewriteStream {
+            rewriter: self,
+            output: Output::new(sink),
+            buffer: String::new(),
+            checkpoint: Checkpoint::default(),
+        }
+    }
+
+    ///
+    /// Rewrite the tokens of `input`, tokenizing from `checkpoint`.
+    ///
+    /// Unless `at_end`, stops before the first token that more input could still
+    /// change, and returns the offset to continue from, with `checkpoint` moved there.
+    ///
+    fn rewrite_tokens(
+        &mut self,
+        output: &mut Output<'_>,
+        input: &str,
+        checkpoint: &mut Checkpoint,
+        at_end: bool,
+    ) -> Result<usize, Error> {
+        let mut tokenizer = Tokenizer::resume(input, checkpoint.clone());
+
+        // Input not covered by any token, e.g. `</>`, is copied as is, and so is text.
+        let mut copied = 0;
+
+        while let Some(token) = tokenizer.next() {
+            if let TokenKind::Text(_) = token.kind {
+                continue;
+            }
+            if !at_end {
+                match tokenizer.checkpoint() {
+                    Some(next) => *checkpoint = next,
+                    None => return Ok(copied),
+                }
+            }
+            output.write(&input[copied..token.span.start]);
+            copied = token.span.end;
+
+            let source = &input[token.span.clone()];
+            match token.kind {
+                TokenKind::StartTag(start_tag) => {
+                    self.start_tag(output, input, token.span, start_tag)?;
+                }
+                TokenKind::EndTag(end_tag) => output.end_tag(end_tag.name.local_name(), source),
+                _ => output.write(source),
+            }
+        }
+
+        if at_end {
+            output.write(&input[copied..]);
+            copied = input.len();
+        }
+        Ok(copied)
+    }
+
+    fn start_tag(
+        &mut self,
+        output: &mut Output<'_>,
+        input: &str,
+        span: Span,
+        start_tag: StartTag,
+    ) -> Result<(), Error> {
+        let source = &input[span];
+        if let Resolved::Known(web::Tag::Html5(next)) = &start_tag.name {
+            while let Some(web::Tag::Html5(open)) = output.open.last().and_then(|o| o.tag.as_ref())
+            {
+                if !closes(open, next) {
+                    break;
+                }
+                let open = output.open.pop().expect("open element");
+                output.close(open, None);
+            }
+        }
+
+        let parent = output.open.last().and_then(|open| open.tag.clone());
+        let has_content = has_content(&start_tag, parent.as_ref());
+        let mut open = OpenElement {
+            name: start_tag.name.local_name().to_string(),
+            tag: None,
+            append: String::new(),
+            after: String::new(),
+            drop_content: false,
+            drop_end_tag: false,
+        };
+
+        let tag = match (&start_tag.name, output.suppressed) {
+            (Resolved::Known(tag), 0) => tag.clone(),
+            _ => {
+                if let Resolved::Known(tag) = start_tag.name {
+                    open.tag = Some(tag);
+                }
+                output.write(source);
+                if has_content {
+                    output.open.push(open);
+                }
+                return Ok(());
+            }
+        };
+
+        let mut element = RewriteElement::new(tag, parent, input, source, start_tag, has_content);
+        for (target, handler) in self.handlers.iter_mut() {
+            let matches = match target {
+                Target::Tag(tag) => tag == &element.tag,
+                Target::Attr(attr) => element.attr(attr).is_some(),
+            };
+            if matches {
+                handler(&mut element)?;
+            }
+        }
+
+        output.write(&element.before);
+        match element.removal {
+            Removal::None if element.modified => output.write(&element.serialize_start_tag()),
+            Removal::None => output.write(source),
+            Removal::Element | Removal::KeepContent => {}
+        }
+
+        if has_content {
+            if element.removal != Removal::Element {
+                output.write(&element.prepend);
+                open.append = element.append;
+            }
+            open.tag = Some(element.tag);
+            open.after = element.after;
+            open.drop_content = element.removal == Removal::Element || element.replace_content;
+            open.drop_end_tag = element.removal != Removal::None;
+            if open.drop_content {
+                output.suppressed += 1;
+            }
+            output.open.push(open);
+        } else {
+            output.write(&element.after);
+        }
+        Ok(())
+    }
+}
+
+///
+/// A rewrite of input arriving in chunks, started by [Rewriter::stream].
+///
+/// Input is held back until the tokens in it are complete: a tag split across
+/// chunks is rewritten once its `>` arrives, and text is written with the next
+/// tag, comment or doctype.
+///
+pub struct RewriteStream<'r, 'h> {
+    rewriter: &'r mut Rewriter<'h>,
+    output: Output<'r>,
+    buffer: String,
+    checkpoint: Checkpoint,
+}
+
+impl<'r, 'h> RewriteStream<'r, 'h> {
+    ///
+    /// Rewrite the next chunk of input.
+    ///
+    /// Stops at the first error returned by a handler. The stream must not be
+    /// written to after an error.
+    ///
+    pub fn write(&mut self, chunk: &str) -> Result<(), Error> {
+        self.buffer.push_str(chunk);
+        // Only a `>` can complete a token
+        if !chunk.contains('>') {
+            return Ok(());
+        }
+        let consumed = self.rewriter.rewrite_tokens(
+            &mut self.output,
+            &self.buffer,
+            &mut self.checkpoint,
+            false,
+        )?;
+        self.buffer.drain(..consumed);
+        Ok(())
+    }
+
+    ///
+    /// Rewrite the rest of the input, and close the elements still open.
+    ///
+    pub fn end(mut self) -> Result<(), Error> {
+        self.rewriter
+            .rewrite_tokens(&mut self.output, &self.buffer, &mut self.checkpoint, true)?;
+        self.output.finish();
+        Ok(())
+    }
+}
+
+///
+/// Whether an element gets content and an end tag, or is complete with its start tag.
+///
+fn has_content(start_tag: &StartTag, parent: Option<&web::Tag>) -> bool {
+    let in_foreign_content = match parent {
+        Some(web::Tag::Svg(svg_tag)) => !matches!(
+            svg_tag,
+            SvgTag::ForeignObject | SvgTag::Desc | SvgTag::Title
+        ),
+        _ => false,
+    };
+    match &start_tag.name {
+        Resolved::Known(tag) if tag.is_void() => false,
+        Resolved::Known(web::Tag::Svg(_)) => !start_tag.self_closing,
+        _ => !(start_tag.self_closing && in_foreign_content),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Removal {
+    None,
+    Element,
+    KeepContent,
+}
+
+enum AttrEntry<'i> {
+    /// A known attribute. The source is `None` once changed.
+    Typed(web::Attr, AttributeValue, Option<&'i str>),
+    /// An unknown attribute, or one with an invalid value, kept as written,
+    /// with its local name.
+    Raw(String, &'i str),
+}
+
+///
+/// A start tag being rewritten, as passed to the handlers of a [Rewriter].
+///
+pub struct RewriteElement<'i> {
+    tag: web::Tag,
+    parent: Option<web::Tag>,
+    source: &'i str,
+    attrs: Vec<AttrEntry<'i>>,
+    self_closing: bool,
+    has_content: bool,
+    modified: bool,
+    before: String,
+    after: String,
+    prepend: String,
+    append: String,
+    replace_content: bool,
+    removal: Removal,
+}
+
+impl<'i> RewriteElement<'i> {
+    fn new(
+        tag: web::Tag,
+        parent: Option<web::Tag>,
+        input: &'i str,
+        source: &'i str,
+        start_tag: StartTag,
+        has_content: bool,
+    ) -> Self {
+        let attrs = start_tag
+            .attrs
+            .into_iter()
+            .map(|attr| {
+                let attr_source = &input[attr.span];
+                let value_source = if attr_source.contains('=') {
+                    Some(attr.value)
+                } else {
+                    None
+                };
+                match attr.name {
+                    Resolved::Known(known) => match known.deserialize_attribute_value(value_source)
+                    {
+                        Ok(value) => AttrEntry::Typed(known, value, Some(attr_source)),
+                        Err(_) => AttrEntry::Raw(known.local_name().to_string(), attr_source),
+                    },
+                    Resolved::Unknown(name) => AttrEntry::Raw(name, attr_source),
+                }
+            })
+            .collect();
+
+        Self {
+            tag,
+            parent,
+            source,
+            attrs,
+            self_closing: start_tag.self_closing,
+            has_content,
+            modified: false,
+            before: String::new(),
+            after: String::new(),
+            prepend: String::new(),
+            append: String::new(),
+            replace_content: false,
+            removal: Removal::None,
+        }
+    }
+
+    pub fn tag(&self) -> &web::Tag {
+        &self.tag
+    }
+
+    pub fn attr(&self, attr: &web::Attr) -> Option<&AttributeValue> {
+        self.attrs()
+            .find(|(key, _)| *key == attr)
+            .map(|(_, value)| value)
+    }
+
+    ///
+    /// The known attributes with valid values, in source order.
+    /// The others are in [RewriteElement::raw_attrs].
+    ///
+    pub fn attrs(&self) -> impl Iterator<Item = (&web::Attr, &AttributeValue)> {
+        self.attrs.iter().filter_map(|entry| match entry {
+            AttrEntry::Typed(attr, value, _) => Some((attr, value)),
+            AttrEntry::Raw(..) => None,
+        })
+    }
+
+    ///
+    /// The unknown attributes and those with invalid values, in source order,
+    /// as local names and the attributes as written.
+    ///
+    /// Example:
+    /// ```
+    /// use web_ns::*;
+    /// use web_ns::rewriter::Rewriter;
+    ///
+    /// let mut rewriter = Rewriter::new().on_tag(web::Tag::Html5(html5::HtmlTag::Div), |div| {
+    ///     let raw: Vec<(&str, &str)> = div.raw_attrs().collect();
+    ///     assert_eq!(raw, vec![("x-y", "X-Y='1'"), ("hidden", "hidden=no")]);
+    ///     Ok(())
+    /// });
+    /// rewriter.rewrite("<div id=a X-Y='1' hidden=no>").unwrap();
+    /// ```
+    ///
+    pub fn raw_attrs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attrs.iter().filter_map(|entry| match entry {
+            AttrEntry::Typed(..) => None,
+            AttrEntry::Raw(name, source) => Some((name.as_str(), *source)),
+        })
+    }
+
+    ///
+    /// Set a typed attribute, returning the old value.
+    ///
+    /// Fails with [Error::NamespaceMismatch] if `attr` is not in the namespace of the tag.
+    ///
+    pub fn set_attr(
+        &mut self,
+        attr: web::Attr,
+        value: AttributeValue,
+    ) -> Result<Option<AttributeValue>, Error> {
+        match (&self.tag, &attr) {
+            (web::Tag::Html5(_), web::Attr::Html5(_)) | (web::Tag::Svg(_), web::Attr::Svg(_)) => {}
+            _ => return Err(Error::NamespaceMismatch),
+        }
+
+        self.modified = true;
+        let position = self.attrs.iter().position(|entry| match entry {
+            AttrEntry::Typed(key, _, _) => key == &attr,
+            AttrEntry::Raw(name, _) => name == attr.local_name(),
+        });
+        let entry = AttrEntry::Typed(attr, value, None);
+        match position {
+            Some(index) => match std::mem::replace(&mut self.attrs[index], entry) {
+                AttrEntry::Typed(_, old, _) => Ok(Some(old)),
+                AttrEntry::Raw(..) => Ok(None),
+            },
+            None => {
+                self.attrs.push(entry);
+                Ok(None)
+            }
+        }
+    }
+
+    ///
+    /// Set an attribute by its case-insensitive local name, parsing the value.
+    ///
+    pub fn set_attr_str(&mut self, local_name: &str, value: Option<&str>) -> Result<(), Error> {
+        let attr: web::Attr = self.tag.attr_by_local_name(local_name)?;
+        let value = attr.deserialize_attribute_value(value)?;
+        self.set_attr(attr, value)?;
+        Ok(())
+    }
+
+    ///
+    /// Remove a typed attribute, returning its value.
+    /// The attribute is also removed if its value was invalid, returning `None`.
+    ///
+    pub fn remove_attr(&mut self, attr: &web::Attr) -> Option<AttributeValue> {
+        let index = self.attrs.iter().position(|entry| match entry {
+            AttrEntry::Typed(key, _, _) => key == attr,
+            AttrEntry::Raw(name, _) => name == attr.local_name(),
+        })?;
+        self.modified = true;
+        match self.attrs.remove(index) {
+            AttrEntry::Typed(_, value, _) => Some(value),
+            AttrEntry::Raw(..) => None,
+        }
+    }
+
+    ///
+    /// Remove an attribute by its case-insensitive local name, whether known or not.
+    /// Returns whether the element had the attribute.
+    ///
+    pub fn remove_attr_str(&mut self, local_name: &str) -> bool {
+        let index = self.attrs.iter().position(|entry| match entry {
+            AttrEntry::Typed(attr, _, _) => attr.local_name().eq_ignore_ascii_case(local_name),
+            AttrEntry::Raw(name, _) => name.eq_ignore_ascii_case(local_name),
+        });
+        match index {
+            Some(index) => {
+                self.modified = true;
+                self.attrs.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    ///
+    /// Insert content before the element.
+    ///
+    pub fn before(&mut self, content: &str, content_type: ContentType) -> Result<(), Error> {
+        let content = self.content(self.parent.as_ref(), content, content_type)?;
+        self.before.push_str(&content);
+        Ok(())
+    }
+
+    ///
+    /// Insert content after the element, before content inserted by earlier calls.
+    ///
+    pub fn after(&mut self, content: &str, content_type: ContentType) -> Result<(), Error> {
+        let content = self.content(self.parent.as_ref(), content, content_type)?;
+        self.after.insert_str(0, &content);
+        Ok(())
+    }
+
+    ///
+    /// Insert content at the start of the element, before content inserted by earlier calls.
+    ///
+    /// Fails with [Error::InvalidText] for elements without content.
+    ///
+    pub fn prepend(&mut self, content: &str, content_type: ContentType) -> Result<(), Error> {
+        let content = self.inner_content(content, content_type)?;
+        self.prepend.insert_str(0, &content);
+        Ok(())
+    }
+
+    ///
+    /// Insert content at the end of the element.
+    ///
+    /// Fails with [Error::InvalidText] for elements without content.
+    ///
+    pub fn append(&mut self, content: &str, content_type: ContentType) -> Result<(), Error> {
+        let content = self.inner_content(content, content_type)?;
+        self.append.push_str(&content);
+        Ok(())
+    }
+
+    ///
+    /// Replace the content of the element, and content inserted into it so far.
+    ///
+    pub fn set_inner_content(
+        &mut self,
+        content: &str,
+        content_type: ContentType,
+    ) -> Result<(), Error> {
+        let content = self.inner_content(content, content_type)?;
+        self.prepend = content;
+        self.append.clear();
+        self.replace_content = true;
+        Ok(())
+    }
+
+    ///
+    /// Remove the element and its content.
+    /// Content inserted before and after it is still written.
+    ///
+    pub fn remove(&mut self) {
+        self.removal = Removal::Element;
+    }
+
+    ///
+    /// Remove the start and end tags of the element, keeping its content.
+    ///
+    pub fn remove_and_keep_content(&mut self) {
+        self.removal = Removal::KeepContent;
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.removal == Removal::Element
+    }
+
+    fn inner_content(&self, content: &str, content_type: ContentType) -> Result<String, Error> {
+        if !self.has_content {
+            return Err(Error::InvalidText);
+        }
+        self.content(Some(&self.tag), content, content_type)
+    }
+
+    fn content(
+        &self,
+        parent: Option<&web::Tag>,
+        content: &str,
+        content_type: ContentType,
+    ) -> Result<String, Error> {
+        match content_type {
+            ContentType::Html => Ok(content.to_string()),
+            ContentType::Text => {
+                let text = match parent {
+                    Some(parent) => escape_text(parent, content)?,
+                    // At the top level, text is escaped like in a body
+                    None => escape_text(&HtmlTag::Body, content)?,
+                };
+                Ok(text.into_owned())
+            }
+        }
+    }
+
+    fn serialize_start_tag(&self) -> String {
+        let name_end = self.source[1..]
+            .find(|c| is_ascii_whitespace(c) || c == '/' || c == '>')
+            .map(|index| index + 1)
+            .unwrap_or_else(|| self.source.len());
+
+        let mut output = self.source[..name_end].to_string();
+        for entry in self.attrs.iter() {
+            match entry {
+                AttrEntry::Typed(_, _, Some(source)) | AttrEntry::Raw(_, source) => {
+                    output.push(' ');
+                    output.push_str(source);
+                }
+                AttrEntry::Typed(attr, value, None) => {
+                    match attr.serialize_attribute_value(value) {
+                        SerializedAttributeValue::Omitted => {}
+                        SerializedAttributeValue::Empty => {
+                            output.push(' ');
+                            output.push_str(attr.local_name());
+                        }
+                        SerializedAttributeValue::String(string) => {
+                            output.push(' ');
+                            output.push_str(attr.local_name());
+                            output.push_str("=\"");
+                            output.push_str(&escape_attribute_value(&string));
+                            output.push('"');
+                        }
+                    }
+                }
+            }
+        }
+        output.push_str(if self.self_closing { "/>" } else { ">" });
+        output
+    }
+}
+
+///
+/// An element whose end tag has not been seen yet.
+///
+struct OpenElement {
+    name: String,
+    tag: Option<web::Tag>,
+    append: String,
+    after: String,
+    drop_content: bool,
+    drop_end_tag: bool,
+}
+
+struct Output<'s> {
+    sink: Box<dyn FnMut(&str) + 's>,
+    open: Vec<OpenElement>,
+    /// The number of open elements whose content is dropped
+    suppressed: usize,
+}
+
+impl<'s> Output<'s> {
+    fn new<F>(sink: F) -> Self
+    where
+        F: FnMut(&str) + 's,
+    {
+        Self {
+            sink: Box::new(sink),
+            open: vec![],
+            suppressed: 0,
+        }
+    }
+
+    fn write(&mut self, chunk: &str) {
+        if self.suppressed == 0 && !chunk.is_empty() {
+            (self.sink)(chunk);
+        }
+    }
+
+    fn end_tag(&mut self, local_name: &str, source: &str) {
+        let index = self
+            .open
+            .iter()
+            .rposition(|open| open.name.eq_ignore_ascii_case(local_name));
+
+        match index {
+            Some(index) => {
+                while self.open.len() > index + 1 {
+                    let open = self.open.pop().expect("open element");
+                    self.close(open, None);
+                }
+                let open = self.open.pop().expect("open element");
+                self.close(open, Some(source));
+            }
+            None => self.write(source),
+        }
+    }
+
+    ///
+    /// Close an element, at its end tag or where the end tag is implied.
+    ///
+    fn close(&mut self, open: OpenElement, end_tag: Option<&str>) {
+        if open.drop_content {
+            self.suppressed -= 1;
+        }
+        self.write(&open.append);
+        if let (Some(end_tag), false) = (end_tag, open.drop_end_tag) {
+            self.write(end_tag);
+        }
+        self.write(&open.after);
+    }
+
+    ///
+    /// Close the elements still open at the end of the input.
+    ///
+    fn finish(&mut self) {
+        while let Some(open) = self.open.pop() {
+            self.close(open, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html5::HtmlAttr;
+    use crate::svg::SvgAttr;
+
+    fn html_tag(tag: HtmlTag) -> web::Tag {
+        web::Tag::Html5(tag)
+    }
+
+    #[test]
+    fn unchanged_input_is_copied() {
+        let input = "<!DOCTYPE html>\r\n<P Class = 'a'  id=b>x &amp y</><li>1<li>2</ul><div id=a";
+        let mut rewriter = Rewriter::new().on_tag(html_tag(HtmlTag::P), |_| Ok(()));
+        assert_eq!(rewriter.rewrite(input).unwrap(), input);
+    }
+
+    #[test]
+    fn attributes() {
+        let mut rewriter = Rewriter::new()
+            .on_tag(html_tag(HtmlTag::A), |a| {
+                a.remove_attr(&web::Attr::Html5(HtmlAttr::Onclick));
+                a.set_attr_str("rel", Some("noopener"))?;
+                a.set_attr(
+                    web::Attr::Html5(HtmlAttr::Title),
+                    AttributeValue::String("\"q\"".to_string()),
+                )?;
+                Ok(())
+            })
+            .on_attr(web::Attr::Svg(SvgAttr::ViewBox), |svg| {
+                assert!(svg
+                    .set_attr(web::Attr::Html5(HtmlAttr::Id), AttributeValue::True)
+                    .is_err());
+                svg.set_attr_str("viewbox", Some("0 0 2 2"))?;
+                Ok(())
+            });
+
+        assert_eq!(
+            rewriter
+                .rewrite("<A HREF=/x x-y ONCLICK=f() title=t><svg VIEWBOX='0 0 1 1'/></A>")
+                .unwrap(),
+            r#"<A HREF=/x x-y title="&quot;q&quot;" rel="noopener"><svg viewBox="0 0 2 2"/></A>"#
+        );
+    }
+
+    #[test]
+    fn content() {
+        let mut rewriter = Rewriter::new()
+            .on_tag(html_tag(HtmlTag::Li), |li| {
+                li.before("<", ContentType::Text)?;
+                li.after("!", ContentType::Html)?;
+                li.prepend("[", ContentType::Text)?;
+                li.append("]", ContentType::Text)
+            })
+            .on_tag(html_tag(HtmlTag::Title), |title| {
+                title.set_inner_content("<b>", ContentType::Text)
+            })
+            .on_tag(html_tag(HtmlTag::Br), |br| {
+                assert!(matches!(
+                    br.append("x", ContentType::Text),
+                    Err(Error::InvalidText)
+                ));
+                Ok(())
+            });
+
+        assert_eq!(
+            rewriter
+                .rewrite("<title>x</title><ul><li>a<li>b</ul><br>")
+                .unwrap(),
+            "<title>&lt;b&gt;</title><ul>&lt;<li>[a]!&lt;<li>[b]!</ul><br>"
+        );
+    }
+
+    #[test]
+    fn removal() {
+        let mut removed = vec![];
+        let mut rewriter = Rewriter::new()
+            .on_tag(html_tag(HtmlTag::Div), |div| {
+                div.remove();
+                div.after("-", ContentType::Text)
+            })
+            .on_tag(html_tag(HtmlTag::Span), |span| {
+                span.remove_and_keep_content();
+                span.append(".", ContentType::Text)
+            })
+            .on_tag(html_tag(HtmlTag::P), |p| {
+                removed.push(p.tag().clone());
+                Ok(())
+            });
+
+        assert_eq!(
+            rewriter
+                .rewrite(
+                    "a<div><p><div>b</div>c</div>d<span>e<i>f</i></span><script>'</div>'</script>"
+                )
+                .unwrap(),
+            "a-de<i>f</i>.<script>'</div>'</script>"
+        );
+        drop(rewriter);
+        assert!(removed.is_empty());
+    }
+
+    #[test]
+    fn handler_errors() {
+        let mut rewriter = Rewriter::new().on_tag(html_tag(HtmlTag::Script), |script| {
+            script.append("</script>", ContentType::Text)
+        });
+        assert!(matches!(
+            rewriter.rewrite("<script></script>"),
+            Err(Error::InvalidText)
+        ));
+    }
+
+    #[test]
+    fn raw_attributes() {
+        let mut rewriter = Rewriter::new()
+            .on_tag(html_tag(HtmlTag::Div), |div| {
+                assert!(div.remove_attr_str("X-Y"));
+                assert!(!div.remove_attr_str("x-y"));
+                assert_eq!(div.remove_attr(&web::Attr::Html5(HtmlAttr::Hidden)), None);
+                Ok(())
+            })
+            .on_tag(html_tag(HtmlTag::Td), |td| {
+                assert_eq!(
+                    td.raw_attrs().collect::<Vec<_>>(),
+                    vec![("tabindex", "tabindex")]
+                );
+                td.set_attr_str("tabindex", Some("2"))?;
+                assert!(td.raw_attrs().next().is_none());
+                assert!(td.remove_attr_str("ID"));
+                Ok(())
+            });
+
+        assert_eq!(
+            rewriter
+                .rewrite("<div x-y=1 hidden=no class=a><td id=b tabindex>")
+                .unwrap(),
+            r#"<div class=a><td tabindex="2">"#
+        );
+    }
+
+    #[test]
+    fn streaming() {
+        let inputs = [
+            "<!DOCTYPE html><title>a &amp b</title><ul><li>1<li class=x>2</ul><!-- <li> -->",
+            "<p>x<script>if (a<b) { '<li>' }</script><svg><title><li/></title></svg><li>",
+            "<textarea></li></textarea>a<!-->b<li>",
+        ];
+        let rewriter = || {
+            Rewriter::new().on_tag(html_tag(HtmlTag::Li), |li| {
+                li.set_attr_str("class", Some("y"))?;
+                li.append(".", ContentType::Text)
+            })
+        };
+
+        for input in inputs.iter() {
+            let expected = rewriter().rewrite(input).unwrap();
+            for split in (0..input.len()).filter(|i| input.is_char_boundary(*i)) {
+                let mut output = String::new();
+                let mut rewriter = rewriter();
+                let mut stream = rewriter.stream(|chunk| output.push_str(chunk));
+                stream.write(&input[..split]).unwrap();
+                stream.write(&input[split..]).unwrap();
+                stream.end().unwrap();
+                assert_eq!(output, expected, "split at {}", split);
+            }
+
+            let mut output = String::new();
+            let mut rewriter = rewriter();
+            let mut stream = rewriter.stream(|chunk| output.push_str(chunk));
+            for c in input.chars() {
+                stream.write(&c.to_string()).unwrap();
+            }
+            stream.end().unwrap();
+            assert_eq!(output, expected);
+        }
+    }
+}
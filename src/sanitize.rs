@@ -0,0 +1,543 @@
+//!
+//! Allowlist-based HTML sanitizing.
+//!
+//! The input is parsed with [crate::dom::parse_fragment], everything not
+//! allowed by a [Policy] is removed from the tree, and the tree is serialized
+//! again, so the output is always well-formed and escaped.
+//!
+
+use std::collections::{HashMap, HashSet};
+
+use crate::attr::update::{update_strategy, UpdateStrategy};
+use crate::attr::{AttributeValue, DeserializeAttributeValue};
+use crate::dom::{parse_fragment, serialize, ElementNode, Node};
+use crate::element::Element;
+use crate::escape::escape_comment;
+use crate::html5::{HtmlAttr, HtmlTag};
+use crate::tag::{Tag, TextKind};
+use crate::{url, web, Error, LocalName};
+
+type ValueFilter = Box<dyn Fn(&web::Tag, &web::Attr, AttributeValue) -> Option<AttributeValue>>;
+
+///
+/// What a [Policy] does with a tag that is not allowed.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Disallowed {
+    /// Remove the element, keeping its sanitized children.
+    Unwrap,
+    /// Remove the element and its children.
+    Remove,
+}
+
+///
+/// An allowlist of tags and attributes, and rules for attribute values.
+///
+/// The policy is applied after the allowlists:
+/// - Event handler attributes, e.g. `onclick`, are always removed.
+/// - URL attributes, e.g. `href` and `srcset`, are removed unless every URL in
+///   them is relative or has an allowed scheme.
+/// - With [Policy::require_noopener], `<a>`, `<area>` and `<form>` elements
+///   with a `target` get `noopener` added to their `rel`.
+/// - Value filters run last, and may change or remove the value.
+/// - With [Policy::allow_comments], comments are kept unless their text could
+///   end the comment early, e.g. `a--!>b`.
+///
+/// Disallowed elements are unwrapped, except raw text elements, `<template>`,
+/// `<script>` and `<style>` in any namespace, and the `<svg>` and `<math>` roots
+/// of foreign content, whose content is removed too.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::sanitize::Policy;
+///
+/// let policy = Policy::new()
+///     .allow_tags(&[web::Tag::Html5(html5::HtmlTag::A), web::Tag::Html5(html5::HtmlTag::B)])
+///     .allow_tag_attrs(
+///         web::Tag::Html5(html5::HtmlTag::A),
+///         &[web::Attr::Html5(html5::HtmlAttr::Href), web::Attr::Html5(html5::HtmlAttr::Target)],
+///     )
+///     .allow_url_schemes(&["https"])
+///     .require_noopener(true);
+///
+/// assert_eq!(
+///     policy.sanitize(r#"<a href="https://x.y" target=_blank onclick="f()"><b>b</b><i>i</i></a>"#)?,
+///     r#"<a href="https://x.y" target="_blank" rel="noopener"><b>b</b>i</a>"#
+/// );
+/// assert_eq!(
+///     policy.sanitize("<a href=' javascript:alert(1)'>x</a><script>alert(2)</script>")?,
+///     "<a>x</a>"
+/// );
+/// # Ok::<(), Error>(())
+/// ```
+///
+pub struct Policy {
+    tags: HashSet<web::Tag>,
+    global_attrs: HashSet<web::Attr>,
+    tag_attrs: HashMap<web::Tag, HashSet<web::Attr>>,
+    data_attrs: bool,
+    url_schemes: HashSet<String>,
+    relative_urls: bool,
+    noopener: bool,
+    comments: bool,
+    filters: Vec<(Option<web::Attr>, ValueFilter)>,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            tags: HashSet::new(),
+            global_attrs: HashSet::new(),
+            tag_attrs: HashMap::new(),
+            data_attrs: false,
+            url_schemes: HashSet::new(),
+            relative_urls: true,
+            noopener: false,
+            comments: false,
+            filters: vec![],
+        }
+    }
+}
+
+impl Policy {
+    ///
+    /// A policy allowing nothing but text, and relative URLs.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// A policy for formatted user-generated content: text-level and grouping
+    /// elements, links, images and tables, without `id`, `class` or `style`.
+    ///
+    pub fn basic() -> Self {
+        use HtmlAttr as A;
+        use HtmlTag as T;
+
+        let html_tags = |tags: &[HtmlTag]| -> Vec<web::Tag> {
+            tags.iter().cloned().map(web::Tag::Html5).collect()
+        };
+        let html_attrs = |attrs: &[HtmlAttr]| -> Vec<web::Attr> {
+            attrs.iter().cloned().map(web::Attr::Html5).collect()
+        };
+
+        Self::new()
+            .allow_tags(&html_tags(&[
+                T::A,
+                T::Abbr,
+                T::B,
+                T::Blockquote,
+                T::Br,
+                T::Caption,
+                T::Cite,
+                T::Code,
+                T::Dd,
+                T::Del,
+                T::Dfn,
+                T::Div,
+                T::Dl,
+                T::Dt,
+                T::Em,
+                T::Figcaption,
+                T::Figure,
+                T::H1,
+                T::H2,
+                T::H3,
+                T::H4,
+                T::H5,
+                T::H6,
+                T::Hr,
+                T::I,
+                T::Img,
+                T::Ins,
+                T::Kbd,
+                T::Li,
+                T::Mark,
+                T::Ol,
+                T::P,
+                T::Pre,
+                T::Q,
+                T::S,
+                T::Small,
+                T::Span,
+                T::Strong,
+                T::Sub,
+                T::Sup,
+                T::Table,
+                T::Tbody,
+                T::Td,
+                T::Tfoot,
+                T::Th,
+                T::Thead,
+                T::Tr,
+                T::U,
+                T::Ul,
+            ]))
+            .allow_attrs(&html_attrs(&[A::Dir, A::Lang, A::Title]))
+            .allow_tag_attrs(web::Tag::Html5(T::A), &html_attrs(&[A::Href, A::Target]))
+            .allow_tag_attrs(
+                web::Tag::Html5(T::Img),
+                &html_attrs(&[A::Alt, A::Height, A::Src, A::Srcset, A::Width]),
+            )
+            .allow_tag_attrs(web::Tag::Html5(T::Blockquote), &html_attrs(&[A::Cite]))
+            .allow_tag_attrs(web::Tag::Html5(T::Q), &html_attrs(&[A::Cite]))
+            .allow_tag_attrs(
+                web::Tag::Html5(T::Ol),
+                &html_attrs(&[A::Reversed, A::Start]),
+            )
+            .allow_tag_attrs(
+                web::Tag::Html5(T::Td),
+                &html_attrs(&[A::Colspan, A::Rowspan]),
+            )
+            .allow_tag_attrs(
+                web::Tag::Html5(T::Th),
+                &html_attrs(&[A::Colspan, A::Rowspan, A::Scope]),
+            )
+            .allow_url_schemes(&["http", "https", "mailto"])
+            .require_noopener(true)
+    }
+
+    pub fn allow_tags(mut self, tags: &[web::Tag]) -> Self {
+        self.tags.extend(tags.iter().cloned());
+        self
+    }
+
+    ///
+    /// Allow attributes on all allowed tags.
+    ///
+    pub fn allow_attrs(mut self, attrs: &[web::Attr]) -> Self {
+        self.global_attrs.extend(attrs.iter().cloned());
+        self
+    }
+
+    pub fn allow_tag_attrs(mut self, tag: web::Tag, attrs: &[web::Attr]) -> Self {
+        self.tag_attrs
+            .entry(tag)
+            .or_default()
+            .extend(attrs.iter().cloned());
+        self
+    }
+
+    ///
+    /// Allow `data-*` attributes on all allowed HTML tags.
+    ///
+    pub fn allow_data_attrs(mut self, allow: bool) -> Self {
+        self.data_attrs = allow;
+        self
+    }
+
+    ///
+    /// Allow absolute URLs with the given case-insensitive schemes.
+    ///
+    pub fn allow_url_schemes(mut self, schemes: &[&str]) -> Self {
+        self.url_schemes
+            .extend(schemes.iter().map(|scheme| scheme.to_ascii_lowercase()));
+        self
+    }
+
+    pub fn allow_relative_urls(mut self, allow: bool) -> Self {
+        self.relative_urls = allow;
+        self
+    }
+
+    pub fn allow_comments(mut self, allow: bool) -> Self {
+        self.comments = allow;
+        self
+    }
+
+    pub fn require_noopener(mut self, require: bool) -> Self {
+        self.noopener = require;
+        self
+    }
+
+    ///
+    /// Add a filter for values of `attr`, or of all attributes if `None`.
+    /// Returning `None` removes the attribute.
+    ///
+    pub fn filter_attr<F>(mut self, attr: Option<web::Attr>, filter: F) -> Self
+    where
+        F: Fn(&web::Tag, &web::Attr, AttributeValue) -> Option<AttributeValue> + 'static,
+    {
+        self.filters.push((attr, Box::new(filter)));
+        self
+    }
+
+    ///
+    /// Sanitize an HTML fragment.
+    ///
+    pub fn sanitize(&self, input: &str) -> Result<String, Error> {
+        let nodes = self.sanitize_nodes(parse_fragment(input).nodes);
+        serialize(&nodes)
+    }
+
+    ///
+    /// Sanitize parsed nodes.
+    ///
+    pub fn sanitize_nodes(&self, nodes: Vec<Node>) -> Vec<Node> {
+        let mut output = Vec::with_capacity(nodes.len());
+        for node in nodes {
+            match node {
+                Node::Text(_) => output.push(node),
+                // Comments which cannot be serialized as they are would fail the whole output
+                Node::Comment(comment) if self.comments && escape_comment(&comment).is_ok() => {
+                    output.push(Node::Comment(comment))
+                }
+                Node::Comment(_) => {}
+                Node::Element(ElementNode { element, children }) => {
+                    if self.tags.contains(element.tag()) {
+                        output.push(Node::Element(ElementNode {
+                            element: self.sanitize_element(element),
+                            children: self.sanitize_nodes(children),
+                        }));
+                    } else if self.disallowed(element.tag()) == Disallowed::Unwrap {
+                        output.extend(self.sanitize_nodes(children));
+                    }
+                }
+            }
+        }
+        output
+    }
+
+    ///
+    /// What happens to `tag` when it is not allowed.
+    ///
+    pub fn disallowed(&self, tag: &web::Tag) -> Disallowed {
+        // Script and style are also active in SVG, and anything inside a foreign
+        // root would otherwise end up as HTML.
+        if matches!(tag.local_name(), "script" | "style" | "svg" | "math") {
+            return Disallowed::Remove;
+        }
+        match tag.text_kind() {
+            TextKind::RawText
+            | TextKind::EscapableRawText
+            | TextKind::PlainText
+            | TextKind::Template => Disallowed::Remove,
+            TextKind::Void | TextKind::Foreign | TextKind::Normal => Disallowed::Unwrap,
+        }
+    }
+
+    ///
+    /// Whether `attr` is allowed on `tag`, before looking at its value.
+    ///
+    pub fn is_attr_allowed(&self, tag: &web::Tag, attr: &web::Attr) -> bool {
+        if update_strategy(tag, attr) == UpdateStrategy::EventHandler {
+            return false;
+        }
+        self.global_attrs.contains(attr)
+            || self
+                .tag_attrs
+                .get(tag)
+                .map(|attrs| attrs.contains(attr))
+                .unwrap_or(false)
+            || (self.data_attrs && matches!(attr, web::Attr::Html5(HtmlAttr::Dataset(_))))
+    }
+
+    fn sanitize_element(&self, element: Element) -> Element {
+        let tag = element.tag().clone();
+        let mut sanitized = Element::new(tag.clone());
+
+        for (attr, value) in element.into_attrs() {
+            if !self.is_attr_allowed(&tag, &attr) || !self.urls_allowed(&attr, &value) {
+                continue;
+            }
+            let value = self
+                .filters
+                .iter()
+                .filter(|(filtered, _)| filtered.as_ref().map(|a| a == &attr).unwrap_or(true))
+                .try_fold(value, |value, (_, filter)| filter(&tag, &attr, value));
+            if let Some(value) = value {
+                // The attribute comes from an element with the same tag
+                let _ = sanitized.set_attr(attr, value);
+            }
+        }
+
+        if self.noopener {
+            add_noopener(&mut sanitized);
+        }
+        sanitized
+    }
+
+    fn urls_allowed(&self, attr: &web::Attr, value: &AttributeValue) -> bool {
+        url::attr_urls(attr, value)
+            .into_iter()
+            .all(|url| match url::scheme(url) {
+                Some(scheme) => self.url_schemes.contains(&scheme),
+                None => self.relative_urls,
+            })
+    }
+}
+
+fn add_noopener(element: &mut Element) {
+    match element.tag() {
+        web::Tag::Html5(HtmlTag::A)
+        | web::Tag::Html5(HtmlTag::Area)
+        | web::Tag::Html5(HtmlTag::Form) => {}
+        _ => return,
+    }
+    let target = web::Attr::Html5(HtmlAttr::Target);
+    let rel = web::Attr::Html5(HtmlAttr::Rel);
+    if !element.has_attr(&target) {
+        return;
+    }
+
+    let mut tokens: Vec<String> = element
+        .attr_tokens(&rel)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    if tokens
+        .iter()
+        .any(|token| token.eq_ignore_ascii_case("noopener"))
+    {
+        return;
+    }
+    tokens.push("noopener".to_string());
+    if let Ok(value) = rel.deserialize_attribute_value(Some(tokens.join(" "))) {
+        let _ = element.set_attr(rel, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg::{SvgAttr, SvgTag};
+
+    fn html_tag(tag: HtmlTag) -> web::Tag {
+        web::Tag::Html5(tag)
+    }
+
+    fn html_attr(attr: HtmlAttr) -> web::Attr {
+        web::Attr::Html5(attr)
+    }
+
+    #[test]
+    fn basic_policy() {
+        let policy = Policy::basic();
+        let sanitize = |input| policy.sanitize(input).unwrap();
+
+        assert_eq!(
+            sanitize("<p id=x class=y style=z title=t onmouseover=f()>a<!-- c --></p>"),
+            r#"<p title="t">a</p>"#
+        );
+        assert_eq!(
+            sanitize("<div><style>p {}</style><form><input value=x>b</form><iframe src=x></iframe></div>"),
+            "<div>b</div>"
+        );
+        assert_eq!(
+            sanitize(
+                r#"<img src="data:image/png;base64,x" alt=a><img srcset="a.png 1x, javascript:x 2x">"#
+            ),
+            r#"<img alt="a"><img>"#
+        );
+        assert_eq!(
+            sanitize(r#"<a href="mailto:a@b.c" target=_blank rel=nofollow>a</a>"#),
+            r#"<a href="mailto:a@b.c" target="_blank" rel="noopener">a</a>"#
+        );
+        assert_eq!(sanitize("<svg><a href=x>y</a></svg>z"), "z");
+        assert_eq!(
+            sanitize("<p>&copy; 2024 &hellip; &eacute;</p>"),
+            "<p>© 2024 … é</p>"
+        );
+        assert_eq!(sanitize("<svg><script>alert(1)</script></svg>"), "");
+        assert_eq!(sanitize("<math><mi>x</mi></math>"), "");
+    }
+
+    #[test]
+    fn comments() {
+        let policy = Policy::basic().allow_comments(true);
+        let sanitize = |input| policy.sanitize(input).unwrap();
+
+        assert_eq!(
+            sanitize("<!-->x<img src=x onerror=alert(1)>-->"),
+            r#"<!---->x<img src="x">--&gt;"#
+        );
+        assert_eq!(
+            sanitize("<!--->x<img src=x onerror=alert(1)>-->"),
+            r#"<!---->x<img src="x">--&gt;"#
+        );
+        assert_eq!(
+            sanitize("<!--a--!><img src=x onerror=alert(1)>-->"),
+            r#"<!--a--><img src="x">--&gt;"#
+        );
+        assert_eq!(sanitize("<!--a<!--b-->c<!--d<!--->"), "c");
+        assert_eq!(sanitize("<!-- ok -->"), "<!-- ok -->");
+    }
+
+    #[test]
+    fn noopener() {
+        let policy = Policy::new()
+            .allow_tags(&[html_tag(HtmlTag::A)])
+            .allow_tag_attrs(
+                html_tag(HtmlTag::A),
+                &[html_attr(HtmlAttr::Target), html_attr(HtmlAttr::Rel)],
+            )
+            .require_noopener(true);
+
+        assert_eq!(
+            policy.sanitize("<a target=x rel=nofollow>a</a>").unwrap(),
+            r#"<a target="x" rel="nofollow noopener">a</a>"#
+        );
+        assert_eq!(
+            policy.sanitize("<a target=x rel=NOOPENER>a</a>").unwrap(),
+            r#"<a target="x" rel="NOOPENER">a</a>"#
+        );
+        assert_eq!(policy.sanitize("<a>a</a>").unwrap(), "<a>a</a>");
+    }
+
+    #[test]
+    fn svg_and_data_attrs() {
+        let svg = |tag| web::Tag::Svg(tag);
+        let policy = Policy::new()
+            .allow_tags(&[html_tag(HtmlTag::Span), svg(SvgTag::Svg), svg(SvgTag::A)])
+            .allow_tag_attrs(svg(SvgTag::A), &[web::Attr::Svg(SvgAttr::Href)])
+            .allow_attrs(&[
+                web::Attr::Svg(SvgAttr::Onclick),
+                web::Attr::Svg(SvgAttr::Onzoom),
+            ])
+            .allow_data_attrs(true)
+            .allow_relative_urls(false)
+            .allow_comments(true);
+
+        assert_eq!(
+            policy
+                .sanitize("<span data-x=1><!--c--><svg onclick=f() onzoom=f()><a href=x>y</a><a href=https://x>z</a></svg></span>")
+                .unwrap(),
+            r#"<span data-x="1"><!--c--><svg><a>y</a><a>z</a></svg></span>"#
+        );
+        assert_eq!(
+            policy
+                .sanitize("<svg><g>x</g><script>alert(1)</script><style>a {}</style></svg>")
+                .unwrap(),
+            "<svg>x</svg>"
+        );
+    }
+
+    #[test]
+    fn value_filters() {
+        let policy = Policy::new()
+            .allow_tags(&[html_tag(HtmlTag::Span)])
+            .allow_attrs(&[html_attr(HtmlAttr::Title), html_attr(HtmlAttr::Lang)])
+            .filter_attr(
+                Some(html_attr(HtmlAttr::Title)),
+                |_, _, value| match value {
+                    AttributeValue::String(title) if title.len() > 3 => None,
+                    value => Some(value),
+                },
+            )
+            .filter_attr(None, |_, _, value| match value {
+                AttributeValue::String(string) => {
+                    Some(AttributeValue::String(string.to_uppercase()))
+                }
+                value => Some(value),
+            });
+
+        assert_eq!(
+            policy
+                .sanitize("<span title=abc lang=en>a</span><span title=abcd>b</span>")
+                .unwrap(),
+            r#"<span title="ABC" lang="EN">a</span><span>b</span>"#
+        );
+    }
+}
@@ -0,0 +1,770 @@
+//!
+//! CSS selectors, matched against typed elements.
+//!
+//! Supported are type, universal, class, id and attribute selectors,
+//! the descendant, child, next-sibling and subsequent-sibling combinators,
+//! and the `:not()` and `:is()` pseudo-classes.
+//!
+//! See: https://drafts.csswg.org/selectors-4/
+//!
+
+use std::str::FromStr;
+
+use crate::attr::{AttributeValue, SerializeAttributeValue, SerializedAttributeValue};
+use crate::element::Element;
+use crate::{web, AttrByLocalName, Error, LocalName};
+
+///
+/// HTML attributes whose values are matched ASCII case-insensitively by
+/// attribute selectors without an `s` flag.
+///
+/// See: https://html.spec.whatwg.org/#case-sensitivity-of-selectors
+///
+pub const CASE_INSENSITIVE_ATTRS: &[&str] = &[
+    "accept",
+    "accept-charset",
+    "align",
+    "alink",
+    "axis",
+    "bgcolor",
+    "charset",
+    "checked",
+    "clear",
+    "codetype",
+    "color",
+    "compact",
+    "declare",
+    "defer",
+    "dir",
+    "direction",
+    "disabled",
+    "enctype",
+    "face",
+    "frame",
+    "hreflang",
+    "http-equiv",
+    "lang",
+    "language",
+    "link",
+    "media",
+    "method",
+    "multiple",
+    "nohref",
+    "noresize",
+    "noshade",
+    "nowrap",
+    "readonly",
+    "rel",
+    "rev",
+    "rules",
+    "scope",
+    "scrolling",
+    "selected",
+    "shape",
+    "target",
+    "text",
+    "type",
+    "valign",
+    "valuetype",
+    "vlink",
+];
+
+///
+/// An element that selectors can be matched against, provided by the caller's DOM.
+///
+pub trait SelectorElement: Sized {
+    fn tag(&self) -> &web::Tag;
+
+    fn attr(&self, attr: &web::Attr) -> Option<&AttributeValue>;
+
+    fn parent_element(&self) -> Option<Self>;
+
+    fn prev_sibling_element(&self) -> Option<Self>;
+}
+
+///
+/// A lone [Element] has no parent or siblings.
+///
+impl SelectorElement for &Element {
+    fn tag(&self) -> &web::Tag {
+        Element::tag(self)
+    }
+
+    fn attr(&self, attr: &web::Attr) -> Option<&AttributeValue> {
+        Element::attr(self, attr)
+    }
+
+    fn parent_element(&self) -> Option<Self> {
+        None
+    }
+
+    fn prev_sibling_element(&self) -> Option<Self> {
+        None
+    }
+}
+
+///
+/// A comma-separated list of selectors, matching if any of them matches.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::element::Element;
+/// use web_ns::selector::SelectorList;
+///
+/// let selector: SelectorList = "a[href^='https:' i].external".parse()?;
+///
+/// let a = Element::new(web::Tag::Html5(html5::HtmlTag::A))
+///     .with_attr_str("href", Some("HTTPS://example.com"))?
+///     .with_attr_str("class", Some("link external"))?;
+/// assert!(selector.matches(&&a));
+///
+/// let input = Element::new(web::Tag::Html5(html5::HtmlTag::Input))
+///     .with_attr_str("type", Some("CheckBox"))?;
+/// assert!(SelectorList::parse("INPUT[type=checkbox]")?.matches(&&input));
+/// assert!(!SelectorList::parse("input[type=checkbox s]")?.matches(&&input));
+///
+/// let clip_path = Element::new(web::Tag::Svg(svg::SvgTag::ClipPath));
+/// assert!(SelectorList::parse("clipPath")?.matches(&&clip_path));
+/// assert!(!SelectorList::parse("clippath")?.matches(&&clip_path));
+/// # Ok::<(), Error>(())
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelectorList {
+    selectors: Vec<Complex>,
+}
+
+impl SelectorList {
+    ///
+    /// Parse a selector list. Fails with [Error::InvalidSelector].
+    ///
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let mut parser = Parser {
+            chars: input.chars().collect(),
+            pos: 0,
+        };
+        let list = parser.selector_list()?;
+        parser.skip_whitespace();
+        if parser.pos < parser.chars.len() {
+            return Err(Error::InvalidSelector);
+        }
+        Ok(list)
+    }
+
+    pub fn matches<E: SelectorElement>(&self, element: &E) -> bool {
+        self.selectors
+            .iter()
+            .any(|complex| complex.matches(complex.compounds.len() - 1, element))
+    }
+}
+
+impl FromStr for SelectorList {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Error> {
+        Self::parse(input)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Combinator {
+    Descendant,
+    Child,
+    NextSibling,
+    SubsequentSibling,
+}
+
+///
+/// Compound selectors, each with the combinator to the compound before it.
+/// The combinator of the first compound is unused.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Complex {
+    compounds: Vec<(Combinator, Compound)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Compound {
+    type_name: Option<String>,
+    conditions: Vec<Condition>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Condition {
+    Id(String),
+    Class(String),
+    Attr(String, Option<AttrMatch>),
+    Not(SelectorList),
+    Is(SelectorList),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct AttrMatch {
+    op: AttrOp,
+    value: String,
+    case_sensitive: Option<bool>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AttrOp {
+    /// `=`
+    Equals,
+    /// `~=`
+    Includes,
+    /// `|=`
+    DashMatch,
+    /// `^=`
+    Prefix,
+    /// `$=`
+    Suffix,
+    /// `*=`
+    Substring,
+}
+
+impl Complex {
+    fn matches<E: SelectorElement>(&self, index: usize, element: &E) -> bool {
+        let (combinator, compound) = &self.compounds[index];
+        if !compound.matches(element) {
+            return false;
+        }
+        if index == 0 {
+            return true;
+        }
+
+        match combinator {
+            Combinator::Child => element
+                .parent_element()
+                .map(|parent| self.matches(index - 1, &parent))
+                .unwrap_or(false),
+            Combinator::Descendant => {
+                let mut ancestor = element.parent_element();
+                while let Some(current) = ancestor {
+                    if self.matches(index - 1, &current) {
+                        return true;
+                    }
+                    ancestor = current.parent_element();
+                }
+                false
+            }
+            Combinator::NextSibling => element
+                .prev_sibling_element()
+                .map(|sibling| self.matches(index - 1, &sibling))
+                .unwrap_or(false),
+            Combinator::SubsequentSibling => {
+                let mut sibling = element.prev_sibling_element();
+                while let Some(current) = sibling {
+                    if self.matches(index - 1, &current) {
+                        return true;
+                    }
+                    sibling = current.prev_sibling_element();
+                }
+                false
+            }
+        }
+    }
+}
+
+impl Compound {
+    fn matches<E: SelectorElement>(&self, element: &E) -> bool {
+        if let Some(type_name) = &self.type_name {
+            if !names_match(element.tag(), element.tag().local_name(), type_name) {
+                return false;
+            }
+        }
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(element))
+    }
+}
+
+impl Condition {
+    fn matches<E: SelectorElement>(&self, element: &E) -> bool {
+        match self {
+            Condition::Id(id) => attr_value(element, "id")
+                .map(|value| &value == id)
+                .unwrap_or(false),
+            Condition::Class(class) => attr_value(element, "class")
+                .map(|value| value.split_ascii_whitespace().any(|token| token == class))
+                .unwrap_or(false),
+            Condition::Attr(name, attr_match) => {
+                let value = match attr_value(element, name) {
+                    Some(value) => value,
+                    None => return false,
+                };
+                match attr_match {
+                    None => true,
+                    Some(attr_match) => {
+                        let case_sensitive = attr_match.case_sensitive.unwrap_or_else(|| {
+                            !(matches!(element.tag(), web::Tag::Html5(_))
+                                && CASE_INSENSITIVE_ATTRS
+                                    .iter()
+                                    .any(|attr| attr.eq_ignore_ascii_case(name)))
+                        });
+                        attr_match.matches(&value, case_sensitive)
+                    }
+                }
+            }
+            Condition::Not(list) => !list.matches(element),
+            Condition::Is(list) => list.matches(element),
+        }
+    }
+}
+
+impl AttrMatch {
+    fn matches(&self, value: &str, case_sensitive: bool) -> bool {
+        let (value, expected) = if case_sensitive {
+            (value.to_string(), self.value.clone())
+        } else {
+            (value.to_ascii_lowercase(), self.value.to_ascii_lowercase())
+        };
+        let expected = expected.as_str();
+
+        match self.op {
+            AttrOp::Equals => value == expected,
+            AttrOp::Includes => {
+                !expected.is_empty()
+                    && !expected.contains(|c: char| c.is_ascii_whitespace())
+                    && value
+                        .split_ascii_whitespace()
+                        .any(|token| token == expected)
+            }
+            AttrOp::DashMatch => {
+                value == expected
+                    || (value.starts_with(expected) && value[expected.len()..].starts_with('-'))
+            }
+            AttrOp::Prefix => !expected.is_empty() && value.starts_with(expected),
+            AttrOp::Suffix => !expected.is_empty() && value.ends_with(expected),
+            AttrOp::Substring => !expected.is_empty() && value.contains(expected),
+        }
+    }
+}
+
+///
+/// Names match ASCII case-insensitively for HTML elements, and case-sensitively for SVG.
+///
+fn names_match(tag: &web::Tag, actual: &str, selector: &str) -> bool {
+    match tag {
+        web::Tag::Html5(_) => actual.eq_ignore_ascii_case(selector),
+        web::Tag::Svg(_) => actual == selector,
+    }
+}
+
+///
+/// The serialized value of the attribute named `name`, if present.
+///
+fn attr_value<E: SelectorElement>(element: &E, name: &str) -> Option<String> {
+    let attr: web::Attr = element.tag().attr_by_local_name(name).ok()?;
+    if !names_match(element.tag(), attr.local_name(), name) {
+        return None;
+    }
+    match attr.serialize_attribute_value(element.attr(&attr)?) {
+        SerializedAttributeValue::Omitted => None,
+        SerializedAttributeValue::Empty => Some(String::new()),
+        SerializedAttributeValue::String(value) => Some(value),
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn selector_list(&mut self) -> Result<SelectorList, Error> {
+        let mut selectors = vec![self.complex()?];
+        loop {
+            self.skip_whitespace();
+            if self.eat(',') {
+                selectors.push(self.complex()?);
+            } else {
+                return Ok(SelectorList { selectors });
+            }
+        }
+    }
+
+    fn complex(&mut self) -> Result<Complex, Error> {
+        self.skip_whitespace();
+        let mut compounds = vec![(Combinator::Descendant, self.compound()?)];
+
+        loop {
+            let had_whitespace = self.skip_whitespace();
+            let combinator = match self.peek() {
+                Some('>') => Combinator::Child,
+                Some('+') => Combinator::NextSibling,
+                Some('~') => Combinator::SubsequentSibling,
+                Some(',') | Some(')') | None => return Ok(Complex { compounds }),
+                Some(_) if had_whitespace => {
+                    compounds.push((Combinator::Descendant, self.compound()?));
+                    continue;
+                }
+                Some(_) => return Err(Error::InvalidSelector),
+            };
+            self.pos += 1;
+            self.skip_whitespace();
+            compounds.push((combinator, self.compound()?));
+        }
+    }
+
+    fn compound(&mut self) -> Result<Compound, Error> {
+        let universal = self.eat('*');
+        let type_name = if !universal && self.at_ident() {
+            Some(self.ident()?)
+        } else {
+            None
+        };
+        let mut conditions = vec![];
+
+        loop {
+            match self.peek() {
+                Some('#') => {
+                    self.pos += 1;
+                    conditions.push(Condition::Id(self.ident()?));
+                }
+                Some('.') => {
+                    self.pos += 1;
+                    conditions.push(Condition::Class(self.ident()?));
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    conditions.push(self.attribute()?);
+                }
+                Some(':') => {
+                    self.pos += 1;
+                    let name = self.ident()?.to_ascii_lowercase();
+                    if !self.eat('(') {
+                        return Err(Error::InvalidSelector);
+                    }
+                    let list = self.selector_list()?;
+                    self.skip_whitespace();
+                    if !self.eat(')') {
+                        return Err(Error::InvalidSelector);
+                    }
+                    conditions.push(match name.as_str() {
+                        "not" => Condition::Not(list),
+                        "is" => Condition::Is(list),
+                        _ => return Err(Error::InvalidSelector),
+                    });
+                }
+                _ => break,
+            }
+        }
+
+        if !universal && type_name.is_none() && conditions.is_empty() {
+            return Err(Error::InvalidSelector);
+        }
+
+        Ok(Compound {
+            type_name,
+            conditions,
+        })
+    }
+
+    fn attribute(&mut self) -> Result<Condition, Error> {
+        self.skip_whitespace();
+        let name = self.ident()?;
+        self.skip_whitespace();
+
+        if self.eat(']') {
+            return Ok(Condition::Attr(name, None));
+        }
+
+        let op = match self.peek() {
+            Some('=') => AttrOp::Equals,
+            Some('~') => AttrOp::Includes,
+            Some('|') => AttrOp::DashMatch,
+            Some('^') => AttrOp::Prefix,
+            Some('$') => AttrOp::Suffix,
+            Some('*') => AttrOp::Substring,
+            _ => return Err(Error::InvalidSelector),
+        };
+        self.pos += 1;
+        if op != AttrOp::Equals && !self.eat('=') {
+            return Err(Error::InvalidSelector);
+        }
+        self.skip_whitespace();
+
+        let value = match self.peek() {
+            Some(quote) if quote == '"' || quote == '\'' => self.string(quote)?,
+            _ => self.ident()?,
+        };
+        self.skip_whitespace();
+
+        let case_sensitive = if self.at_ident() {
+            match self.ident()?.to_ascii_lowercase().as_str() {
+                "i" => Some(false),
+                "s" => Some(true),
+                _ => return Err(Error::InvalidSelector),
+            }
+        } else {
+            None
+        };
+        self.skip_whitespace();
+
+        if !self.eat(']') {
+            return Err(Error::InvalidSelector);
+        }
+
+        Ok(Condition::Attr(
+            name,
+            Some(AttrMatch {
+                op,
+                value,
+                case_sensitive,
+            }),
+        ))
+    }
+
+    fn at_ident(&self) -> bool {
+        match (self.peek(), self.chars.get(self.pos + 1)) {
+            (Some('-'), Some(c)) => is_name_start(*c) || *c == '-' || *c == '\\',
+            (Some('\\'), Some(_)) => true,
+            (Some(c), _) => is_name_start(c),
+            _ => false,
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, Error> {
+        if !self.at_ident() {
+            return Err(Error::InvalidSelector);
+        }
+        let mut output = String::new();
+        while let Some(c) = self.peek() {
+            if c == '\\' {
+                self.pos += 1;
+                output.push(self.escape()?);
+            } else if is_name_start(c) || c.is_ascii_digit() || c == '-' {
+                self.pos += 1;
+                output.push(c);
+            } else {
+                break;
+            }
+        }
+        Ok(output)
+    }
+
+    fn string(&mut self, quote: char) -> Result<String, Error> {
+        self.pos += 1;
+        let mut output = String::new();
+        loop {
+            match self.peek() {
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(output);
+                }
+                Some('\\') => {
+                    self.pos += 1;
+                    if self.eat('\n') {
+                        continue;
+                    }
+                    output.push(self.escape()?);
+                }
+                Some('\n') | None => return Err(Error::InvalidSelector),
+                Some(c) => {
+                    self.pos += 1;
+                    output.push(c);
+                }
+            }
+        }
+    }
+
+    ///
+    /// The character of an escape, after the backslash.
+    ///
+    fn escape(&mut self) -> Result<char, Error> {
+        let mut code = 0;
+        let mut digits = 0;
+        while let Some(digit) = self.peek().and_then(|c| c.to_digit(16)) {
+            if digits == 6 {
+                break;
+            }
+            code = code * 16 + digit;
+            digits += 1;
+            self.pos += 1;
+        }
+
+        if digits > 0 {
+            // A single whitespace character terminates the escape
+            if !self.eat(' ') && !self.eat('\t') {
+                self.eat('\n');
+            }
+            Ok(match code {
+                0 => '\u{fffd}',
+                code => std::char::from_u32(code).unwrap_or('\u{fffd}'),
+            })
+        } else {
+            match self.peek() {
+                Some('\n') | None => Err(Error::InvalidSelector),
+                Some(c) => {
+                    self.pos += 1;
+                    Ok(c)
+                }
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.pos;
+        while matches!(
+            self.peek(),
+            Some(' ') | Some('\t') | Some('\n') | Some('\r') | Some('\x0c')
+        ) {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || !c.is_ascii()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::{parse_fragment, ElementNode, Node};
+
+    ///
+    /// An element in a parsed fragment, with the path of sibling lists leading to it.
+    ///
+    #[derive(Clone)]
+    struct Cursor<'a> {
+        path: Vec<(&'a [Node], usize)>,
+    }
+
+    impl<'a> Cursor<'a> {
+        fn node(&self) -> &'a ElementNode {
+            let (siblings, index) = self.path.last().unwrap();
+            match &siblings[*index] {
+                Node::Element(node) => node,
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    impl<'a> SelectorElement for Cursor<'a> {
+        fn tag(&self) -> &web::Tag {
+            self.node().element.tag()
+        }
+
+        fn attr(&self, attr: &web::Attr) -> Option<&AttributeValue> {
+            self.node().element.attr(attr)
+        }
+
+        fn parent_element(&self) -> Option<Self> {
+            let mut path = self.path.clone();
+            path.pop();
+            if path.is_empty() {
+                None
+            } else {
+                Some(Cursor { path })
+            }
+        }
+
+        fn prev_sibling_element(&self) -> Option<Self> {
+            let mut path = self.path.clone();
+            let (siblings, index) = path.pop().unwrap();
+            let previous = siblings[..index]
+                .iter()
+                .rposition(|node| matches!(node, Node::Element(_)))?;
+            path.push((siblings, previous));
+            Some(Cursor { path })
+        }
+    }
+
+    fn select(html: &str, selector: &str) -> Vec<String> {
+        fn walk<'a>(
+            nodes: &'a [Node],
+            path: &[(&'a [Node], usize)],
+            selector: &SelectorList,
+            output: &mut Vec<String>,
+        ) {
+            for (index, node) in nodes.iter().enumerate() {
+                if let Node::Element(element_node) = node {
+                    let mut path = path.to_vec();
+                    path.push((nodes, index));
+                    let cursor = Cursor { path: path.clone() };
+                    if selector.matches(&cursor) {
+                        output.push(
+                            element_node
+                                .element
+                                .id()
+                                .unwrap_or_else(|| element_node.element.tag().local_name())
+                                .to_string(),
+                        );
+                    }
+                    walk(&element_node.children, &path, selector, output);
+                }
+            }
+        }
+
+        let fragment = parse_fragment(html);
+        let selector = SelectorList::parse(selector).unwrap();
+        let mut output = vec![];
+        walk(&fragment.nodes, &[], &selector, &mut output);
+        output
+    }
+
+    const HTML: &str = r#"<div id=a class="x y"><p id=b lang=en-US><span id=c></span></p><p id=d class=y></p><em id=e></em></div><svg id=f viewBox="0 0 1 1"><foreignObject id=g><p id=h></p></foreignObject></svg>"#;
+
+    #[test]
+    fn combinators() {
+        assert_eq!(select(HTML, "div p"), vec!["b", "d"]);
+        assert_eq!(select(HTML, "div > p"), vec!["b", "d"]);
+        assert_eq!(select(HTML, "svg p"), vec!["h"]);
+        assert_eq!(select(HTML, "div > span"), Vec::<String>::new());
+        assert_eq!(select(HTML, "p + p, #a ~ svg"), vec!["d", "f"]);
+        assert_eq!(select(HTML, "#b ~ *"), vec!["d", "e"]);
+        assert_eq!(select(HTML, "div p>span"), vec!["c"]);
+    }
+
+    #[test]
+    fn attributes() {
+        assert_eq!(select(HTML, ".y"), vec!["a", "d"]);
+        assert_eq!(select(HTML, "[class~=x].y"), vec!["a"]);
+        assert_eq!(select(HTML, "[lang|=EN]"), vec!["b"]);
+        assert_eq!(select(HTML, "[lang|=EN s]"), Vec::<String>::new());
+        assert_eq!(
+            select(HTML, "[id^=a], [id$=\"b\"], [id*='\\63']"),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(select(HTML, "[ID=A]"), Vec::<String>::new());
+        assert_eq!(select(HTML, "[ID=A i]"), vec!["a"]);
+        assert_eq!(select(HTML, "[viewBox]"), vec!["f"]);
+        assert_eq!(select(HTML, "[viewbox]"), Vec::<String>::new());
+        assert_eq!(
+            select(HTML, "foreignobject, FOREIGNOBJECT"),
+            Vec::<String>::new()
+        );
+        assert_eq!(select(HTML, "foreignObject"), vec!["g"]);
+    }
+
+    #[test]
+    fn pseudo_classes() {
+        assert_eq!(select(HTML, "p:not(.y)"), vec!["b", "h"]);
+        assert_eq!(select(HTML, ":is(em, span)"), vec!["c", "e"]);
+        assert_eq!(select(HTML, "div :not(p, :is(span))"), vec!["e"]);
+    }
+
+    #[test]
+    fn invalid() {
+        for selector in &[
+            "", "p,", "[a=]", "[a b]", ":hover", "a >", "#1", "p q)", "[a='b",
+        ] {
+            assert!(SelectorList::parse(selector).is_err(), "{}", selector);
+        }
+    }
+}
@@ -24,28 +24,28 @@ impl AsRef<str> for StaticUniCase {
     }
 }
 
-impl<'a> PartialEq<StaticUniCase> for StaticUniCase {
+impl PartialEq<StaticUniCase> for StaticUniCase {
     fn eq(&self, rhs: &StaticUniCase) -> bool {
         self.0.eq(&rhs.0)
     }
 }
-impl<'a> Eq for StaticUniCase {}
+impl Eq for StaticUniCase {}
 
-impl<'a> std::hash::Hash for StaticUniCase {
+impl std::hash::Hash for StaticUniCase {
     #[inline]
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.0.hash(state);
     }
 }
 
-impl<'a> PhfHash for StaticUniCase {
+impl PhfHash for StaticUniCase {
     #[inline]
     fn phf_hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.0.phf_hash(state);
     }
 }
 
-impl<'a> FmtConst for StaticUniCase {
+impl FmtConst for StaticUniCase {
     #[inline]
     fn fmt_const(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "StaticUniCase::new(\"{}\")", &self.0)
@@ -0,0 +1,33 @@
+//!
+//! The standing of tags and attributes in their specification.
+//!
+//! This file is also included from build.rs.
+//!
+
+///
+/// Whether a tag or attribute is current, on its way out, or gone.
+///
+/// See: https://html.spec.whatwg.org/#obsolete
+///
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Status {
+    /// Part of the current specification.
+    Current,
+    /// Conforming but discouraged, e.g. HTML features that are
+    /// "obsolete but conforming" and features deprecated by SVG 2.
+    Deprecated,
+    /// Non-conforming. Removed from the specification, but possibly still
+    /// recognized by browsers.
+    Obsolete,
+    /// Never part of a specification, e.g. vendor extensions.
+    NonStandard,
+}
+
+impl Status {
+    ///
+    /// Whether documents using this are conforming.
+    ///
+    pub fn is_conforming(self) -> bool {
+        matches!(self, Status::Current | Status::Deprecated)
+    }
+}
@@ -14,6 +14,10 @@ mod attributes {
     include!(concat!(env!("OUT_DIR"), "/codegen_svg_attr_enum.rs"));
 }
 
+pub mod animation;
+pub mod content_model;
+pub mod presentation;
+
 pub use attributes::SvgAttr;
 pub use tags::SvgTag;
 
@@ -33,15 +37,14 @@ impl crate::TagByLocalName<tags::SvgTag> for SvgNamespace {
     fn tag_by_local_name(&self, local_name: &str) -> Result<tags::SvgTag, Error> {
         tags::STATIC_LOCAL_NAME_LOOKUP
             .get(&unicase::UniCase::ascii(local_name))
-            .map(Clone::clone)
-            .ok_or_else(|| Error::InvalidAttribute)
+            .cloned()
+            .ok_or(Error::InvalidAttribute)
     }
 }
 
 impl crate::TagByLocalName<crate::web::Tag> for SvgNamespace {
     fn tag_by_local_name(&self, local_name: &str) -> Result<crate::web::Tag, Error> {
-        self.tag_by_local_name(local_name)
-            .map(|tag| super::web::Tag::Svg(tag))
+        self.tag_by_local_name(local_name).map(super::web::Tag::Svg)
     }
 }
 
@@ -49,8 +52,8 @@ impl crate::AttrByLocalName<attributes::SvgAttr> for tags::SvgTag {
     fn attr_by_local_name(&self, local_name: &str) -> Result<attributes::SvgAttr, Error> {
         attributes::STATIC_LOCAL_NAME_LOOKUP
             .get(&unicase::UniCase::ascii(local_name))
-            .map(Clone::clone)
-            .ok_or_else(|| Error::InvalidAttribute)
+            .cloned()
+            .ok_or(Error::InvalidAttribute)
     }
 }
 
@@ -58,21 +61,20 @@ impl crate::AttrByProperty<attributes::SvgAttr> for tags::SvgTag {
     fn attr_by_property(&self, property: &str) -> Result<attributes::SvgAttr, Error> {
         attributes::STATIC_PROPERTY_LOOKUP
             .get(property)
-            .map(Clone::clone)
-            .ok_or_else(|| Error::InvalidAttribute)
+            .cloned()
+            .ok_or(Error::InvalidAttribute)
     }
 }
 
 impl crate::AttrByLocalName<crate::web::Attr> for tags::SvgTag {
     fn attr_by_local_name(&self, local_name: &str) -> Result<crate::web::Attr, Error> {
         self.attr_by_local_name(local_name)
-            .map(|attr| super::web::Attr::Svg(attr))
+            .map(super::web::Attr::Svg)
     }
 }
 
 impl crate::AttrByProperty<crate::web::Attr> for tags::SvgTag {
     fn attr_by_property(&self, property: &str) -> Result<crate::web::Attr, Error> {
-        self.attr_by_property(property)
-            .map(|attr| super::web::Attr::Svg(attr))
+        self.attr_by_property(property).map(super::web::Attr::Svg)
     }
 }
@@ -0,0 +1,98 @@
+//!
+//! Validation of SVG animation targets.
+//!
+//! See: https://www.w3.org/TR/SVG11/animate.html
+//!
+
+use super::{SvgAttr, SvgTag};
+use crate::attr::attr_type::AnimationType;
+use crate::attr::Attribute;
+use crate::{AttrByLocalName, Error, LocalName};
+
+///
+/// Validate the `attributeName` of the animation element `animation`, which
+/// animates an attribute of the element `target`.
+///
+/// Returns the animated attribute if it can be animated by `animation`.
+/// Attribute names are matched case-sensitively, as in SVG.
+///
+/// Example:
+/// ```
+/// use web_ns::svg::{animation::validate_attribute_name, SvgAttr, SvgTag};
+///
+/// let fill = validate_attribute_name(&SvgTag::Animate, &SvgTag::Rect, "fill");
+/// assert_eq!(fill.unwrap(), SvgAttr::Fill);
+///
+/// assert!(validate_attribute_name(&SvgTag::Animate, &SvgTag::Rect, "transform").is_err());
+/// assert!(validate_attribute_name(&SvgTag::AnimateTransform, &SvgTag::Rect, "transform").is_ok());
+/// assert!(validate_attribute_name(&SvgTag::Set, &SvgTag::Rect, "id").is_err());
+/// assert!(validate_attribute_name(&SvgTag::Set, &SvgTag::Svg, "viewbox").is_err());
+/// ```
+///
+pub fn validate_attribute_name(
+    animation: &SvgTag,
+    target: &SvgTag,
+    attribute_name: &str,
+) -> Result<SvgAttr, Error> {
+    let attr: SvgAttr = target
+        .attr_by_local_name(attribute_name)
+        .map_err(|_| Error::InvalidAttributeValue)?;
+
+    if attr.local_name() != attribute_name {
+        return Err(Error::InvalidAttributeValue);
+    }
+
+    let animation_type = attr
+        .attr_type()
+        .animation_type()
+        .ok_or(Error::InvalidAttributeValue)?;
+
+    let allowed = match animation {
+        SvgTag::Set => true,
+        SvgTag::Animate => animation_type != AnimationType::Transform,
+        SvgTag::AnimateTransform => animation_type == AnimationType::Transform,
+        SvgTag::AnimateColor => animation_type == AnimationType::Color,
+        _ => return Err(Error::InvalidAttribute),
+    };
+
+    if allowed {
+        Ok(attr)
+    } else {
+        Err(Error::InvalidAttributeValue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn animation_types() {
+        let animation_type = |attr: SvgAttr| attr.attr_type().animation_type();
+
+        assert_eq!(animation_type(SvgAttr::Fill), Some(AnimationType::Color));
+        assert_eq!(animation_type(SvgAttr::Cx), Some(AnimationType::Additive));
+        assert_eq!(
+            animation_type(SvgAttr::GradientTransform),
+            Some(AnimationType::Transform)
+        );
+        assert_eq!(
+            animation_type(SvgAttr::FillRule),
+            Some(AnimationType::Discrete)
+        );
+        assert_eq!(animation_type(SvgAttr::AttributeName), None);
+        assert_eq!(animation_type(SvgAttr::Onclick), None);
+    }
+
+    #[test]
+    fn animation_elements() {
+        let validate = |animation, name| validate_attribute_name(&animation, &SvgTag::Circle, name);
+
+        assert!(validate(SvgTag::AnimateColor, "stroke").is_ok());
+        assert!(validate(SvgTag::AnimateColor, "r").is_err());
+        assert!(validate(SvgTag::AnimateTransform, "r").is_err());
+        assert!(validate(SvgTag::Set, "visibility").is_ok());
+        assert!(validate(SvgTag::Animate, "unknown").is_err());
+        assert!(validate(SvgTag::AnimateMotion, "r").is_err());
+    }
+}
@@ -0,0 +1,289 @@
+//!
+//! Element categories and content models of SVG elements.
+//!
+//! See: https://www.w3.org/TR/SVG2/eltindex.html
+//!
+
+use super::SvgTag;
+use crate::tag::tag_type::{flags::*, Flags};
+use crate::tag::Tag;
+use crate::web;
+
+pub use crate::html5::content_model::Child;
+
+///
+/// The content model of an SVG element: what it may contain.
+///
+#[derive(Clone, Copy, Debug)]
+pub enum ContentModel {
+    /// Text only, e.g. `<script>` and `<style>`.
+    Text,
+    /// Anything, including HTML, e.g. `<foreignObject>` and descriptive elements.
+    Any,
+    /// Elements from the given categories and explicitly listed elements.
+    Elements {
+        /// Allowed element categories, as tag type flags.
+        categories: Flags,
+        /// Allowed elements besides those in `categories`.
+        elements: &'static [SvgTag],
+        /// Whether text is allowed, e.g. in text content elements.
+        text: bool,
+    },
+}
+
+impl ContentModel {
+    const fn of(categories: Flags, elements: &'static [SvgTag]) -> Self {
+        Self::Elements {
+            categories,
+            elements,
+            text: false,
+        }
+    }
+
+    const fn text(categories: Flags, elements: &'static [SvgTag]) -> Self {
+        Self::Elements {
+            categories,
+            elements,
+            text: true,
+        }
+    }
+
+    ///
+    /// Whether text that is not whitespace is allowed.
+    ///
+    pub fn allows_text(&self) -> bool {
+        match self {
+            Self::Text | Self::Any => true,
+            Self::Elements { text, .. } => *text,
+        }
+    }
+}
+
+const CONTAINER_CATEGORIES: Flags =
+    SVG_ANIMATION | SVG_DESCRIPTIVE | SVG_PAINT_SERVER | SVG_SHAPE | SVG_STRUCTURAL | SVG_GRADIENT;
+
+const CONTAINER_ELEMENTS: &[SvgTag] = &[
+    SvgTag::A,
+    SvgTag::Audio,
+    SvgTag::Canvas,
+    SvgTag::ClipPath,
+    SvgTag::ColorProfile,
+    SvgTag::Cursor,
+    SvgTag::Filter,
+    SvgTag::Font,
+    SvgTag::FontFace,
+    SvgTag::ForeignObject,
+    SvgTag::Iframe,
+    SvgTag::Image,
+    SvgTag::Marker,
+    SvgTag::Mask,
+    SvgTag::Script,
+    SvgTag::Style,
+    SvgTag::Switch,
+    SvgTag::Text,
+    SvgTag::Video,
+    SvgTag::View,
+];
+
+///
+/// The content model of `tag`.
+///
+pub fn content_model(tag: &SvgTag) -> ContentModel {
+    use SvgTag::*;
+
+    let tag_type = tag.tag_type();
+
+    match tag {
+        Script | Style => ContentModel::Text,
+        ForeignObject | Desc | Title | Metadata => ContentModel::Any,
+        Switch => ContentModel::of(
+            SVG_ANIMATION | SVG_DESCRIPTIVE | SVG_SHAPE,
+            &[A, ForeignObject, G, Image, Svg, Switch, Text, Use],
+        ),
+        ClipPath => ContentModel::of(SVG_ANIMATION | SVG_DESCRIPTIVE | SVG_SHAPE, &[Text, Use]),
+        Text => ContentModel::text(
+            SVG_ANIMATION | SVG_DESCRIPTIVE | SVG_TEXT_CONTENT_CHILD,
+            &[A],
+        ),
+        Tspan | TextPath | Tref | AltGlyph => {
+            ContentModel::text(SVG_DESCRIPTIVE, &[A, Animate, Set, Tspan])
+        }
+        LinearGradient | RadialGradient => {
+            ContentModel::of(SVG_DESCRIPTIVE, &[Animate, AnimateTransform, Set, Stop])
+        }
+        Stop => ContentModel::of(0, &[Animate, Set, Script, Style]),
+        Filter => ContentModel::of(SVG_DESCRIPTIVE | SVG_FILTER_PRIMITIVE, &[Animate, Set]),
+        FeComponentTransfer => {
+            ContentModel::of(SVG_DESCRIPTIVE, &[FeFuncA, FeFuncB, FeFuncG, FeFuncR])
+        }
+        FeMerge => ContentModel::of(SVG_DESCRIPTIVE, &[FeMergeNode]),
+        FeFuncA | FeFuncB | FeFuncG | FeFuncR | FeMergeNode => {
+            ContentModel::of(SVG_DESCRIPTIVE, &[Animate, Set])
+        }
+        FeDiffuseLighting | FeSpecularLighting => {
+            ContentModel::of(SVG_DESCRIPTIVE | SVG_LIGHT_SOURCE, &[])
+        }
+        AnimateMotion => ContentModel::of(SVG_DESCRIPTIVE, &[Mpath]),
+        Image | Use => ContentModel::of(SVG_ANIMATION | SVG_DESCRIPTIVE, &[]),
+        View | Mpath => ContentModel::of(SVG_DESCRIPTIVE, &[]),
+        _ if tag_type.any(SVG_CONTAINER) => {
+            ContentModel::of(CONTAINER_CATEGORIES, CONTAINER_ELEMENTS)
+        }
+        _ if tag_type.any(SVG_SHAPE) => ContentModel::of(SVG_ANIMATION | SVG_DESCRIPTIVE, &[]),
+        _ if tag_type.any(SVG_FILTER_PRIMITIVE | SVG_LIGHT_SOURCE) => {
+            ContentModel::of(SVG_DESCRIPTIVE, &[Animate, Set])
+        }
+        _ if tag_type.any(SVG_ANIMATION) => ContentModel::of(SVG_DESCRIPTIVE, &[]),
+        _ => ContentModel::Any,
+    }
+}
+
+///
+/// Check whether `child` is allowed as a child of the SVG element `parent`.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::svg::SvgTag;
+/// use web_ns::svg::content_model::{is_child_allowed, Child};
+///
+/// let circle = web::Tag::Svg(SvgTag::Circle);
+/// let stop = web::Tag::Svg(SvgTag::Stop);
+/// let div = web::Tag::Html5(html5::HtmlTag::Div);
+///
+/// assert!(is_child_allowed(&SvgTag::G, Child::Element(&circle, &[])));
+/// assert!(!is_child_allowed(&SvgTag::Circle, Child::Element(&circle, &[])));
+/// assert!(!is_child_allowed(&SvgTag::G, Child::Element(&stop, &[])));
+/// assert!(is_child_allowed(&SvgTag::LinearGradient, Child::Element(&stop, &[])));
+/// assert!(!is_child_allowed(&SvgTag::G, Child::Element(&div, &[])));
+/// assert!(is_child_allowed(&SvgTag::ForeignObject, Child::Element(&div, &[])));
+/// ```
+///
+pub fn is_child_allowed(parent: &SvgTag, child: Child) -> bool {
+    let model = content_model(parent);
+
+    match child {
+        Child::Text(text) => model.allows_text() || text.chars().all(|c| c.is_ascii_whitespace()),
+        Child::Element(child, _) => match model {
+            ContentModel::Text => false,
+            ContentModel::Any => true,
+            ContentModel::Elements {
+                categories,
+                elements,
+                ..
+            } => match child {
+                web::Tag::Svg(child) => {
+                    child.tag_type().any(categories) || elements.contains(child)
+                }
+                web::Tag::Html5(_) => false,
+            },
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn svg(tag: SvgTag) -> web::Tag {
+        web::Tag::Svg(tag)
+    }
+
+    #[test]
+    fn categories() {
+        assert!(SvgTag::G.tag_type().any(SVG_CONTAINER | SVG_STRUCTURAL));
+        assert!(SvgTag::Rect.tag_type().any(SVG_SHAPE));
+        assert!(SvgTag::Rect.tag_type().any(SVG_GRAPHICS));
+        assert!(SvgTag::Pattern.tag_type().any(SVG_PAINT_SERVER));
+        assert!(SvgTag::FePointLight.tag_type().any(SVG_LIGHT_SOURCE));
+        assert!(!SvgTag::Path.tag_type().any(SVG_CONTAINER));
+    }
+
+    #[test]
+    fn containers() {
+        for parent in &[
+            SvgTag::Svg,
+            SvgTag::G,
+            SvgTag::Defs,
+            SvgTag::Symbol,
+            SvgTag::Mask,
+        ] {
+            for child in &[
+                SvgTag::G,
+                SvgTag::Path,
+                SvgTag::Use,
+                SvgTag::Text,
+                SvgTag::Title,
+            ] {
+                assert!(is_child_allowed(
+                    parent,
+                    Child::Element(&svg(child.clone()), &[])
+                ));
+            }
+            assert!(!is_child_allowed(
+                parent,
+                Child::Element(&svg(SvgTag::Tspan), &[])
+            ));
+            assert!(!is_child_allowed(parent, Child::Text("text")));
+            assert!(is_child_allowed(parent, Child::Text("\n ")));
+        }
+    }
+
+    #[test]
+    fn filters() {
+        let blur = svg(SvgTag::FeGaussianBlur);
+        let light = svg(SvgTag::FeSpotLight);
+
+        assert!(is_child_allowed(
+            &SvgTag::Filter,
+            Child::Element(&blur, &[])
+        ));
+        assert!(!is_child_allowed(&SvgTag::G, Child::Element(&blur, &[])));
+        assert!(is_child_allowed(
+            &SvgTag::FeDiffuseLighting,
+            Child::Element(&light, &[])
+        ));
+        assert!(!is_child_allowed(
+            &SvgTag::FeGaussianBlur,
+            Child::Element(&light, &[])
+        ));
+
+        let func = svg(SvgTag::FeFuncA);
+        let node = svg(SvgTag::FeMergeNode);
+        assert!(!is_child_allowed(
+            &SvgTag::Filter,
+            Child::Element(&func, &[])
+        ));
+        assert!(!is_child_allowed(
+            &SvgTag::Filter,
+            Child::Element(&node, &[])
+        ));
+        assert!(is_child_allowed(
+            &SvgTag::FeComponentTransfer,
+            Child::Element(&func, &[])
+        ));
+        assert!(is_child_allowed(
+            &SvgTag::FeMerge,
+            Child::Element(&node, &[])
+        ));
+        assert!(!is_child_allowed(
+            &SvgTag::FeMerge,
+            Child::Element(&func, &[])
+        ));
+    }
+
+    #[test]
+    fn text() {
+        let tspan = svg(SvgTag::Tspan);
+
+        assert!(is_child_allowed(&SvgTag::Text, Child::Text("hello")));
+        assert!(is_child_allowed(&SvgTag::Text, Child::Element(&tspan, &[])));
+        assert!(is_child_allowed(
+            &SvgTag::Tspan,
+            Child::Element(&tspan, &[])
+        ));
+        assert!(!is_child_allowed(&SvgTag::Circle, Child::Text("hello")));
+        assert!(is_child_allowed(&SvgTag::Circle, Child::Text(" \n")));
+        assert!(!is_child_allowed(&SvgTag::Circle, Child::Text("\u{a0}")));
+    }
+}
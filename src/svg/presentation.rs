@@ -0,0 +1,414 @@
+//!
+//! Conversion between SVG presentation attributes and the `style` attribute.
+//!
+//! See: https://www.w3.org/TR/SVG2/styling.html#PresentationAttributes
+//!
+
+use super::{SvgAttr, SvgTag};
+use crate::attr::{
+    AttributeValue, CssProperty, DeserializeAttributeValue, SerializeAttributeValue,
+    SerializedAttributeValue,
+};
+use crate::{web, AttrByLocalName};
+
+///
+/// A CSS declaration from a `style` attribute.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Declaration {
+    pub property: String,
+    pub value: String,
+    pub important: bool,
+}
+
+///
+/// Parse the declarations of a `style` attribute.
+///
+/// Example:
+/// ```
+/// use web_ns::svg::presentation::parse_style;
+///
+/// let declarations = parse_style("fill: red; font-family: 'a;b' !important");
+/// assert_eq!(declarations[0].property, "fill");
+/// assert_eq!(declarations[0].value, "red");
+/// assert_eq!(declarations[1].value, "'a;b'");
+/// assert!(declarations[1].important);
+/// ```
+///
+pub fn parse_style(style: &str) -> Vec<Declaration> {
+    split_top_level(&strip_comments(style), ';')
+        .into_iter()
+        .filter_map(|declaration| {
+            let colon = declaration.find(':')?;
+            let property = declaration[..colon].trim();
+            let mut value = declaration[colon + 1..].trim();
+            let mut important = false;
+
+            if let Some(bang) = value.rfind('!') {
+                if value[bang + 1..].trim().eq_ignore_ascii_case("important") {
+                    important = true;
+                    value = value[..bang].trim_end();
+                }
+            }
+
+            if property.is_empty() || value.is_empty() {
+                None
+            } else {
+                Some(Declaration {
+                    property: property.to_string(),
+                    value: value.to_string(),
+                    important,
+                })
+            }
+        })
+        .collect()
+}
+
+///
+/// Serialize declarations into a `style` attribute value.
+///
+pub fn serialize_style(declarations: &[Declaration]) -> String {
+    declarations
+        .iter()
+        .map(|declaration| {
+            if declaration.important {
+                format!("{}: {} !important", declaration.property, declaration.value)
+            } else {
+                format!("{}: {}", declaration.property, declaration.value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+///
+/// Move all presentation attributes into the `style` attribute.
+///
+/// Declarations already in `style` take precedence over presentation attributes,
+/// so presentation attributes that are overridden are just removed.
+///
+/// Attributes whose values are not valid CSS as written stay attributes:
+/// - `transform`, as its attribute syntax differs from the CSS property,
+///   e.g. angles without units in `rotate(45)`.
+/// - Values with unitless numbers, e.g. `font-size="12"`, unless the property
+///   takes plain numbers, e.g. `opacity` or `stroke-width`.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::svg::SvgAttr;
+///
+/// let mut attrs = vec![
+///     (web::Attr::Svg(SvgAttr::Fill), AttributeValue::String("red".into())),
+///     (web::Attr::Svg(SvgAttr::D), AttributeValue::String("M0 0".into())),
+/// ];
+/// svg::presentation::presentation_to_style(&mut attrs);
+///
+/// assert_eq!(attrs, vec![
+///     (web::Attr::Svg(SvgAttr::D), AttributeValue::String("M0 0".into())),
+///     (web::Attr::Svg(SvgAttr::Style), AttributeValue::String("fill: red".into())),
+/// ]);
+/// ```
+///
+pub fn presentation_to_style(attrs: &mut Vec<(web::Attr, AttributeValue)>) {
+    let mut existing = style_declarations(attrs);
+    let mut declarations = vec![];
+
+    attrs.retain(|(attr, value)| {
+        let property = match attr.css_property() {
+            Some(property) => property,
+            None => return true,
+        };
+        let serialized = attr.serialize_attribute_value(value);
+        if !is_valid_css(property, &serialized) {
+            return true;
+        }
+
+        if !existing
+            .iter()
+            .any(|declaration| declaration.property.eq_ignore_ascii_case(property))
+        {
+            if let SerializedAttributeValue::String(value) = serialized {
+                declarations.push(Declaration {
+                    property: property.to_string(),
+                    value,
+                    important: false,
+                });
+            }
+        }
+        false
+    });
+
+    declarations.append(&mut existing);
+    set_style(attrs, &declarations);
+}
+
+///
+/// Move declarations of the `style` attribute that correspond to presentation
+/// attributes out into attributes.
+///
+/// `!important` declarations, declarations using custom properties and values that
+/// are not valid attribute values stay in `style`.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::svg::SvgAttr;
+///
+/// let mut attrs = vec![
+///     (web::Attr::Svg(SvgAttr::Style), AttributeValue::String("fill: red; --x: 1".into())),
+/// ];
+/// svg::presentation::style_to_presentation(&svg::SvgTag::Rect, &mut attrs);
+///
+/// assert_eq!(attrs, vec![
+///     (web::Attr::Svg(SvgAttr::Style), AttributeValue::String("--x: 1".into())),
+///     (web::Attr::Svg(SvgAttr::Fill), AttributeValue::String("red".into())),
+/// ]);
+/// ```
+///
+pub fn style_to_presentation(tag: &SvgTag, attrs: &mut Vec<(web::Attr, AttributeValue)>) {
+    let mut remaining = vec![];
+    let mut moved = vec![];
+
+    for declaration in style_declarations(attrs) {
+        let attr: Option<SvgAttr> = tag.attr_by_local_name(&declaration.property).ok();
+        let value = attr
+            .as_ref()
+            .filter(|attr| attr.css_property().is_some())
+            .filter(|_| !declaration.important && !declaration.value.contains("var("))
+            .and_then(|attr| {
+                attr.deserialize_attribute_value(Some(declaration.value.as_str()))
+                    .ok()
+            });
+
+        match (attr, value) {
+            (Some(attr), Some(value)) => moved.push((web::Attr::Svg(attr), value)),
+            _ => remaining.push(declaration),
+        }
+    }
+
+    set_style(attrs, &remaining);
+
+    for (attr, value) in moved {
+        match attrs.iter_mut().find(|(existing, _)| existing == &attr) {
+            Some(existing) => existing.1 = value,
+            None => attrs.push((attr, value)),
+        }
+    }
+}
+
+///
+/// The properties of presentation attributes that take plain numbers in CSS.
+///
+const NUMBER_PROPERTIES: &[&str] = &[
+    "fill-opacity",
+    "flood-opacity",
+    "font-size-adjust",
+    "font-weight",
+    "opacity",
+    "stop-opacity",
+    "stroke-dasharray",
+    "stroke-dashoffset",
+    "stroke-miterlimit",
+    "stroke-opacity",
+    "stroke-width",
+];
+
+///
+/// Whether a presentation attribute value is also valid as the value of `property`.
+///
+fn is_valid_css(property: &str, value: &SerializedAttributeValue) -> bool {
+    let value = match value {
+        SerializedAttributeValue::String(value) => value,
+        _ => return true,
+    };
+    if property == "transform" {
+        return false;
+    }
+    NUMBER_PROPERTIES.contains(&property)
+        || !value
+            .split(|c: char| c.is_ascii_whitespace() || c == ',')
+            .any(is_number)
+}
+
+fn is_number(token: &str) -> bool {
+    token.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '.' | '+' | '-'))
+        && token.parse::<f64>().is_ok()
+}
+
+fn style_declarations(attrs: &[(web::Attr, AttributeValue)]) -> Vec<Declaration> {
+    attrs
+        .iter()
+        .find(|(attr, _)| attr == &web::Attr::Svg(SvgAttr::Style))
+        .map(|(_, value)| match value {
+            AttributeValue::String(style) => parse_style(style),
+            _ => vec![],
+        })
+        .unwrap_or_default()
+}
+
+fn set_style(attrs: &mut Vec<(web::Attr, AttributeValue)>, declarations: &[Declaration]) {
+    let style_attr = web::Attr::Svg(SvgAttr::Style);
+
+    if declarations.is_empty() {
+        attrs.retain(|(attr, _)| attr != &style_attr);
+        return;
+    }
+
+    let style = AttributeValue::String(serialize_style(declarations));
+    match attrs.iter_mut().find(|(attr, _)| attr == &style_attr) {
+        Some(existing) => existing.1 = style,
+        None => attrs.push((style_attr, style)),
+    }
+}
+
+fn strip_comments(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(start) = rest.find("/*") {
+        output.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    output.push_str(rest);
+    output
+}
+
+///
+/// Split on `separator`, except inside quotes and parentheses.
+///
+fn split_top_level(input: &str, separator: char) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut quote = None;
+    let mut depth = 0usize;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (index, c) in input.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (quote, c) {
+            (_, '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, c) if c == separator && depth == 0 => {
+                parts.push(&input[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn svg_attr(attr: SvgAttr, value: &str) -> (web::Attr, AttributeValue) {
+        (
+            web::Attr::Svg(attr),
+            AttributeValue::String(value.to_string()),
+        )
+    }
+
+    #[test]
+    fn parse_style_edge_cases() {
+        let declarations = parse_style("/* c */ fill : url(a;b) ; ; stroke:blue!IMPORTANT; x");
+        assert_eq!(
+            declarations,
+            vec![
+                Declaration {
+                    property: "fill".to_string(),
+                    value: "url(a;b)".to_string(),
+                    important: false
+                },
+                Declaration {
+                    property: "stroke".to_string(),
+                    value: "blue".to_string(),
+                    important: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn style_takes_precedence() {
+        let mut attrs = vec![
+            svg_attr(SvgAttr::Fill, "red"),
+            svg_attr(SvgAttr::Opacity, "0.5"),
+            svg_attr(SvgAttr::Style, "fill: blue"),
+        ];
+        presentation_to_style(&mut attrs);
+
+        assert_eq!(
+            attrs,
+            vec![svg_attr(SvgAttr::Style, "opacity: 0.5; fill: blue")]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut attrs = vec![
+            svg_attr(SvgAttr::Stroke, "red"),
+            svg_attr(SvgAttr::StrokeWidth, "2"),
+            svg_attr(SvgAttr::Style, "fill: blue !important; stroke: green"),
+        ];
+        style_to_presentation(&SvgTag::Path, &mut attrs);
+
+        assert_eq!(
+            attrs,
+            vec![
+                svg_attr(SvgAttr::Stroke, "green"),
+                svg_attr(SvgAttr::StrokeWidth, "2"),
+                svg_attr(SvgAttr::Style, "fill: blue !important"),
+            ]
+        );
+
+        presentation_to_style(&mut attrs);
+        assert_eq!(
+            attrs,
+            vec![svg_attr(
+                SvgAttr::Style,
+                "stroke: green; stroke-width: 2; fill: blue !important"
+            )]
+        );
+    }
+
+    #[test]
+    fn invalid_css_stays_attribute() {
+        let mut attrs = vec![
+            svg_attr(SvgAttr::Transform, "rotate(45)"),
+            svg_attr(SvgAttr::FontSize, "12"),
+            svg_attr(SvgAttr::LetterSpacing, "1.5em"),
+            svg_attr(SvgAttr::StrokeDasharray, "5, 2"),
+            svg_attr(SvgAttr::FillOpacity, ".5"),
+        ];
+        presentation_to_style(&mut attrs);
+
+        assert_eq!(
+            attrs,
+            vec![
+                svg_attr(SvgAttr::Transform, "rotate(45)"),
+                svg_attr(SvgAttr::FontSize, "12"),
+                svg_attr(
+                    SvgAttr::Style,
+                    "letter-spacing: 1.5em; stroke-dasharray: 5, 2; fill-opacity: .5"
+                ),
+            ]
+        );
+
+        let mut attrs = vec![svg_attr(SvgAttr::FontSize, "12px")];
+        presentation_to_style(&mut attrs);
+        assert_eq!(attrs, vec![svg_attr(SvgAttr::Style, "font-size: 12px")]);
+    }
+}
@@ -0,0 +1,128 @@
+//! Typed web tags.
+//!
+//! Every tag has a [tag_type::TagType], a set of flags generated from the
+//! tag definitions, which the accessors of the [Tag] trait are built upon.
+//!
+pub mod tag_type;
+
+use tag_type::flags;
+
+///
+/// Basic trait for tags.
+///
+pub trait Tag {
+    fn tag_type(&self) -> tag_type::TagType;
+
+    ///
+    /// The kind of content this element has, which decides how its text
+    /// is tokenized and serialized.
+    ///
+    /// Example:
+    /// ```
+    /// use web_ns::*;
+    ///
+    /// assert_eq!(html5::HtmlTag::Script.text_kind(), TextKind::RawText);
+    /// assert_eq!(html5::HtmlTag::Title.text_kind(), TextKind::EscapableRawText);
+    /// assert_eq!(svg::SvgTag::Script.text_kind(), TextKind::Foreign);
+    /// ```
+    fn text_kind(&self) -> TextKind {
+        let tag_type = self.tag_type();
+        if tag_type.any(flags::VOID) {
+            TextKind::Void
+        } else if tag_type.any(flags::RAW_TEXT) {
+            TextKind::RawText
+        } else if tag_type.any(flags::ESCAPABLE_RAW_TEXT) {
+            TextKind::EscapableRawText
+        } else if tag_type.any(flags::PLAINTEXT) {
+            TextKind::PlainText
+        } else if tag_type.any(flags::TEMPLATE) {
+            TextKind::Template
+        } else if tag_type.any(flags::FOREIGN) {
+            TextKind::Foreign
+        } else {
+            TextKind::Normal
+        }
+    }
+
+    ///
+    /// Whether this is in the "special" category of the HTML parser.
+    ///
+    fn is_special(&self) -> bool {
+        self.tag_type().any(flags::SPECIAL)
+    }
+
+    ///
+    /// Whether this is in the "formatting" category of the HTML parser.
+    ///
+    fn is_formatting(&self) -> bool {
+        self.tag_type().any(flags::FORMATTING)
+    }
+
+    ///
+    /// Whether this element delimits the given scope, i.e. whether the search for
+    /// an element "in scope" stops at this element.
+    ///
+    /// Example:
+    /// ```
+    /// use web_ns::*;
+    ///
+    /// assert!(html5::HtmlTag::Table.is_scope_boundary(Scope::Default));
+    /// assert!(html5::HtmlTag::Ul.is_scope_boundary(Scope::ListItem));
+    /// assert!(!html5::HtmlTag::Ul.is_scope_boundary(Scope::Default));
+    /// assert!(!html5::HtmlTag::Title.is_scope_boundary(Scope::Default));
+    /// assert!(svg::SvgTag::Title.is_scope_boundary(Scope::Default));
+    /// ```
+    fn is_scope_boundary(&self, scope: Scope) -> bool {
+        let tag_type = self.tag_type();
+        match scope {
+            Scope::Default => tag_type.any(flags::SCOPE),
+            Scope::ListItem => tag_type.any(flags::SCOPE | flags::LIST_ITEM_SCOPE),
+            Scope::Button => tag_type.any(flags::SCOPE | flags::BUTTON_SCOPE),
+            Scope::Table => tag_type.any(flags::TABLE_SCOPE),
+            Scope::Select => !tag_type.any(flags::NOT_SELECT_SCOPE),
+        }
+    }
+}
+
+///
+/// The scopes of the HTML tree construction stage.
+///
+/// See: https://html.spec.whatwg.org/#has-an-element-in-the-specific-scope
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    /// "has an element in scope"
+    Default,
+    /// "has an element in list item scope"
+    ListItem,
+    /// "has an element in button scope"
+    Button,
+    /// "has an element in table scope"
+    Table,
+    /// "has an element in select scope"
+    Select,
+}
+
+///
+/// The kinds of elements, as far as their text content is concerned.
+///
+/// See: https://html.spec.whatwg.org/#elements-2
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextKind {
+    /// No content at all, e.g. `<br>`.
+    Void,
+    /// Text that is neither decoded nor escaped, e.g. `<script>` and `<style>`.
+    RawText,
+    /// Text where character references are decoded, e.g. `<textarea>` and `<title>`.
+    EscapableRawText,
+    /// `<plaintext>`: the rest of the document is raw text, there is no end tag.
+    PlainText,
+    /// `<template>`: the content is a separate document fragment.
+    Template,
+    /// An element in a foreign namespace, e.g. SVG.
+    /// `<script>` and `<style>` are not raw text in foreign content.
+    Foreign,
+    /// Any other element.
+    Normal,
+}
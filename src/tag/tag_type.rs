@@ -0,0 +1,142 @@
+pub type Flags = u64;
+
+///
+/// The runtime type of a tag.
+///
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct TagType(pub Flags);
+
+impl TagType {
+    pub fn any(self, flags: Flags) -> bool {
+        self.0 & flags > 0
+    }
+}
+
+pub mod flags {
+    use super::Flags;
+
+    /// A void element: it has no content and no end tag.
+    pub const VOID: Flags = 0x1;
+
+    /// An element from a foreign (non-HTML) namespace, e.g. SVG.
+    pub const FOREIGN: Flags = 0x2;
+
+    /// A raw text element: its content is never escaped, e.g. `<script>`.
+    pub const RAW_TEXT: Flags = 0x4;
+
+    /// An escapable raw text element: text only, but character references are decoded, e.g. `<textarea>`.
+    pub const ESCAPABLE_RAW_TEXT: Flags = 0x8;
+
+    /// The `<plaintext>` element: everything after its start tag is text.
+    pub const PLAINTEXT: Flags = 0x10;
+
+    /// The `<template>` element: its content is a separate document fragment.
+    pub const TEMPLATE: Flags = 0x20;
+
+    /// In the "special" parsing category.
+    /// See: https://html.spec.whatwg.org/#special
+    pub const SPECIAL: Flags = 0x40;
+
+    /// In the "formatting" parsing category.
+    pub const FORMATTING: Flags = 0x80;
+
+    /// Delimits "has an element in scope", and thereby list item and button scope.
+    /// See: https://html.spec.whatwg.org/#has-an-element-in-the-specific-scope
+    pub const SCOPE: Flags = 0x100;
+
+    /// Additionally delimits "has an element in list item scope".
+    pub const LIST_ITEM_SCOPE: Flags = 0x200;
+
+    /// Additionally delimits "has an element in button scope".
+    pub const BUTTON_SCOPE: Flags = 0x400;
+
+    /// Delimits "has an element in table scope".
+    pub const TABLE_SCOPE: Flags = 0x800;
+
+    /// Does not delimit "has an element in select scope", which every other element does.
+    pub const NOT_SELECT_SCOPE: Flags = 0x1000;
+
+    /// Metadata content.
+    /// See: https://html.spec.whatwg.org/#kinds-of-content
+    pub const METADATA: Flags = 0x2000;
+
+    /// Flow content.
+    pub const FLOW: Flags = 0x4000;
+
+    /// Sectioning content.
+    pub const SECTIONING: Flags = 0x8000;
+
+    /// Heading content.
+    pub const HEADING: Flags = 0x10000;
+
+    /// Phrasing content.
+    pub const PHRASING: Flags = 0x20000;
+
+    /// Embedded content.
+    pub const EMBEDDED: Flags = 0x40000;
+
+    /// Interactive content. Some elements are only interactive given certain attributes.
+    pub const INTERACTIVE: Flags = 0x80000;
+
+    /// Form-associated elements.
+    pub const FORM_ASSOCIATED: Flags = 0x100000;
+
+    /// Palpable content. Some elements are only palpable given certain attributes.
+    pub const PALPABLE: Flags = 0x200000;
+
+    /// Script-supporting elements.
+    pub const SCRIPT_SUPPORTING: Flags = 0x400000;
+
+    /// SVG container elements.
+    /// See: https://www.w3.org/TR/SVG2/struct.html#TermContainerElement
+    pub const SVG_CONTAINER: Flags = 0x800000;
+
+    /// SVG basic shapes.
+    pub const SVG_SHAPE: Flags = 0x1000000;
+
+    /// SVG graphics elements.
+    pub const SVG_GRAPHICS: Flags = 0x2000000;
+
+    /// SVG gradient elements.
+    pub const SVG_GRADIENT: Flags = 0x4000000;
+
+    /// SVG filter primitive elements.
+    pub const SVG_FILTER_PRIMITIVE: Flags = 0x8000000;
+
+    /// SVG light source elements.
+    pub const SVG_LIGHT_SOURCE: Flags = 0x10000000;
+
+    /// SVG animation elements.
+    pub const SVG_ANIMATION: Flags = 0x20000000;
+
+    /// SVG descriptive elements.
+    pub const SVG_DESCRIPTIVE: Flags = 0x40000000;
+
+    /// SVG structural elements.
+    pub const SVG_STRUCTURAL: Flags = 0x80000000;
+
+    /// SVG paint server elements.
+    pub const SVG_PAINT_SERVER: Flags = 0x100000000;
+
+    /// SVG text content elements.
+    pub const SVG_TEXT_CONTENT: Flags = 0x200000000;
+
+    /// SVG text content child elements.
+    pub const SVG_TEXT_CONTENT_CHILD: Flags = 0x400000000;
+
+    /// Listed form-associated elements, those in `form.elements`.
+    /// See: https://html.spec.whatwg.org/#categories
+    pub const LISTED: Flags = 0x800000000;
+
+    /// Labelable elements, which can be associated with a `<label>`.
+    pub const LABELABLE: Flags = 0x1000000000;
+
+    /// Submittable elements, which can contribute to form submission.
+    pub const SUBMITTABLE: Flags = 0x2000000000;
+
+    /// Resettable elements, which are affected when a form is reset.
+    pub const RESETTABLE: Flags = 0x4000000000;
+
+    /// Autocapitalize-inheriting elements, which inherit `autocapitalize` from their form owner.
+    pub const AUTOCAPITALIZE_INHERITING: Flags = 0x8000000000;
+}
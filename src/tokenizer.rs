@@ -0,0 +1,2225 @@
+//!
+//! An HTML tokenizer emitting typed tags and attributes.
+//!
+//! The tokenizer implements all the states of the tokenization stage of the
+//! HTML standard, including character references. Tag and attribute names are
+//! resolved with the lookups of the crate, into [web::Tag] and [web::Attr].
+//!
+//! The tree construction stage is not implemented, but the tokenizer tracks
+//! what it needs to tokenize typical documents correctly:
+//! - After a start tag of a raw text element, e.g. `<script>` or `<textarea>`,
+//!   it switches state according to the [TextKind] of the element.
+//! - Tags inside `<svg>` are resolved in the SVG namespace, until the
+//!   matching end tag or a tag breaking out of foreign content.
+//!
+//! See: https://html.spec.whatwg.org/#tokenization
+//!
+
+pub mod char_ref;
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::dom::FOREIGN_BREAKOUT;
+use crate::html5::{HtmlTag, HTML5_NS};
+use crate::svg::{SvgTag, SVG_NS};
+use crate::tag::{Tag, TextKind};
+use crate::{web, AttrByLocalName, LocalName, TagByLocalName};
+
+use char_ref::{is_ascii_whitespace, is_control, is_noncharacter, NumericIssue};
+
+///
+/// A range of byte offsets in the input.
+///
+pub type Span = Range<usize>;
+
+///
+/// A token, and the span of input it was tokenized from.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+    Doctype(Doctype),
+    StartTag(StartTag),
+    EndTag(EndTag),
+    Comment(String),
+    /// A run of characters, with character references decoded.
+    Text(String),
+}
+
+///
+/// A name resolved by the lookups of the crate, or kept as a string if unknown.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Resolved<T> {
+    Known(T),
+    Unknown(String),
+}
+
+impl<T: LocalName> Resolved<T> {
+    pub fn local_name(&self) -> &str {
+        match self {
+            Resolved::Known(known) => known.local_name(),
+            Resolved::Unknown(name) => name,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StartTag {
+    pub name: Resolved<web::Tag>,
+    pub attrs: Vec<TagAttribute>,
+    pub self_closing: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EndTag {
+    pub name: Resolved<web::Tag>,
+}
+
+///
+/// An attribute of a start tag. Duplicate attributes are dropped.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagAttribute {
+    pub name: Resolved<web::Attr>,
+    /// The value, with character references decoded.
+    pub value: String,
+    pub span: Span,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Doctype {
+    pub name: Option<String>,
+    pub public_id: Option<String>,
+    pub system_id: Option<String>,
+    pub force_quirks: bool,
+}
+
+///
+/// A parse error, at a byte offset of the input.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub offset: usize,
+    pub code: ErrorCode,
+}
+
+///
+/// The parse errors of the tokenization stage.
+///
+/// See: https://html.spec.whatwg.org/#parse-errors
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    AbruptClosingOfEmptyComment,
+    AbruptDoctypePublicIdentifier,
+    AbruptDoctypeSystemIdentifier,
+    AbsenceOfDigitsInNumericCharacterReference,
+    CdataInHtmlContent,
+    CharacterReferenceOutsideUnicodeRange,
+    ControlCharacterInInputStream,
+    ControlCharacterReference,
+    DuplicateAttribute,
+    EndTagWithAttributes,
+    EndTagWithTrailingSolidus,
+    EofBeforeTagName,
+    EofInCdata,
+    EofInComment,
+    EofInDoctype,
+    EofInScriptHtmlCommentLikeText,
+    EofInTag,
+    IncorrectlyClosedComment,
+    IncorrectlyOpenedComment,
+    InvalidCharacterSequenceAfterDoctypeName,
+    InvalidFirstCharacterOfTagName,
+    MissingAttributeValue,
+    MissingDoctypeName,
+    MissingDoctypePublicIdentifier,
+    MissingDoctypeSystemIdentifier,
+    MissingEndTagName,
+    MissingQuoteBeforeDoctypePublicIdentifier,
+    MissingQuoteBeforeDoctypeSystemIdentifier,
+    MissingSemicolonAfterCharacterReference,
+    MissingWhitespaceAfterDoctypePublicKeyword,
+    MissingWhitespaceAfterDoctypeSystemKeyword,
+    MissingWhitespaceBeforeDoctypeName,
+    MissingWhitespaceBetweenAttributes,
+    MissingWhitespaceBetweenDoctypePublicAndSystemIdentifiers,
+    NestedComment,
+    NoncharacterCharacterReference,
+    NoncharacterInInputStream,
+    NullCharacterReference,
+    SurrogateCharacterReference,
+    UnexpectedCharacterAfterDoctypeSystemIdentifier,
+    UnexpectedCharacterInAttributeName,
+    UnexpectedCharacterInUnquotedAttributeValue,
+    UnexpectedEqualsSignBeforeAttributeName,
+    UnexpectedNullCharacter,
+    UnexpectedQuestionMarkInsteadOfTagName,
+    UnexpectedSolidusInTag,
+    UnknownNamedCharacterReference,
+}
+
+impl ErrorCode {
+    ///
+    /// The code of the error as written in the HTML standard.
+    ///
+    /// Example:
+    /// ```
+    /// use web_ns::tokenizer::ErrorCode;
+    ///
+    /// assert_eq!(ErrorCode::EofInTag.code(), "eof-in-tag");
+    /// assert_eq!(ErrorCode::CdataInHtmlContent.code(), "cdata-in-html-content");
+    /// ```
+    ///
+    pub fn code(&self) -> &'static str {
+        use ErrorCode::*;
+
+        match self {
+            AbruptClosingOfEmptyComment => "abrupt-closing-of-empty-comment",
+            AbruptDoctypePublicIdentifier => "abrupt-doctype-public-identifier",
+            AbruptDoctypeSystemIdentifier => "abrupt-doctype-system-identifier",
+            AbsenceOfDigitsInNumericCharacterReference => {
+                "absence-of-digits-in-numeric-character-reference"
+            }
+            CdataInHtmlContent => "cdata-in-html-content",
+            CharacterReferenceOutsideUnicodeRange => "character-reference-outside-unicode-range",
+            ControlCharacterInInputStream => "control-character-in-input-stream",
+            ControlCharacterReference => "control-character-reference",
+            DuplicateAttribute => "duplicate-attribute",
+            EndTagWithAttributes => "end-tag-with-attributes",
+            EndTagWithTrailingSolidus => "end-tag-with-trailing-solidus",
+            EofBeforeTagName => "eof-before-tag-name",
+            EofInCdata => "eof-in-cdata",
+            EofInComment => "eof-in-comment",
+            EofInDoctype => "eof-in-doctype",
+            EofInScriptHtmlCommentLikeText => "eof-in-script-html-comment-like-text",
+            EofInTag => "eof-in-tag",
+            IncorrectlyClosedComment => "incorrectly-closed-comment",
+            IncorrectlyOpenedComment => "incorrectly-opened-comment",
+            InvalidCharacterSequenceAfterDoctypeName => {
+                "invalid-character-sequence-after-doctype-name"
+            }
+            InvalidFirstCharacterOfTagName => "invalid-first-character-of-tag-name",
+            MissingAttributeValue => "missing-attribute-value",
+            MissingDoctypeName => "missing-doctype-name",
+            MissingDoctypePublicIdentifier => "missing-doctype-public-identifier",
+            MissingDoctypeSystemIdentifier => "missing-doctype-system-identifier",
+            MissingEndTagName => "missing-end-tag-name",
+            MissingQuoteBeforeDoctypePublicIdentifier => {
+                "missing-quote-before-doctype-public-identifier"
+            }
+            MissingQuoteBeforeDoctypeSystemIdentifier => {
+                "missing-quote-before-doctype-system-identifier"
+            }
+            MissingSemicolonAfterCharacterReference => {
+                "missing-semicolon-after-character-reference"
+            }
+            MissingWhitespaceAfterDoctypePublicKeyword => {
+                "missing-whitespace-after-doctype-public-keyword"
+            }
+            MissingWhitespaceAfterDoctypeSystemKeyword => {
+                "missing-whitespace-after-doctype-system-keyword"
+            }
+            MissingWhitespaceBeforeDoctypeName => "missing-whitespace-before-doctype-name",
+            MissingWhitespaceBetweenAttributes => "missing-whitespace-between-attributes",
+            MissingWhitespaceBetweenDoctypePublicAndSystemIdentifiers => {
+                "missing-whitespace-between-doctype-public-and-system-identifiers"
+            }
+            NestedComment => "nested-comment",
+            NoncharacterCharacterReference => "noncharacter-character-reference",
+            NoncharacterInInputStream => "noncharacter-in-input-stream",
+            NullCharacterReference => "null-character-reference",
+            SurrogateCharacterReference => "surrogate-character-reference",
+            UnexpectedCharacterAfterDoctypeSystemIdentifier => {
+                "unexpected-character-after-doctype-system-identifier"
+            }
+            UnexpectedCharacterInAttributeName => "unexpected-character-in-attribute-name",
+            UnexpectedCharacterInUnquotedAttributeValue => {
+                "unexpected-character-in-unquoted-attribute-value"
+            }
+            UnexpectedEqualsSignBeforeAttributeName => {
+                "unexpected-equals-sign-before-attribute-name"
+            }
+            UnexpectedNullCharacter => "unexpected-null-character",
+            UnexpectedQuestionMarkInsteadOfTagName => {
+                "unexpected-question-mark-instead-of-tag-name"
+            }
+            UnexpectedSolidusInTag => "unexpected-solidus-in-tag",
+            UnknownNamedCharacterReference => "unknown-named-character-reference",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Data,
+    Rcdata,
+    Rawtext,
+    ScriptData,
+    Plaintext,
+    TagOpen,
+    EndTagOpen,
+    TagName,
+    RcdataLessThanSign,
+    RcdataEndTagOpen,
+    RcdataEndTagName,
+    RawtextLessThanSign,
+    RawtextEndTagOpen,
+    RawtextEndTagName,
+    ScriptDataLessThanSign,
+    ScriptDataEndTagOpen,
+    ScriptDataEndTagName,
+    ScriptDataEscapeStart,
+    ScriptDataEscapeStartDash,
+    ScriptDataEscaped,
+    ScriptDataEscapedDash,
+    ScriptDataEscapedDashDash,
+    ScriptDataEscapedLessThanSign,
+    ScriptDataEscapedEndTagOpen,
+    ScriptDataEscapedEndTagName,
+    ScriptDataDoubleEscapeStart,
+    ScriptDataDoubleEscaped,
+    ScriptDataDoubleEscapedDash,
+    ScriptDataDoubleEscapedDashDash,
+    ScriptDataDoubleEscapedLessThanSign,
+    ScriptDataDoubleEscapeEnd,
+    BeforeAttributeName,
+    AttributeName,
+    AfterAttributeName,
+    BeforeAttributeValue,
+    AttributeValueDoubleQuoted,
+    AttributeValueSingleQuoted,
+    AttributeValueUnquoted,
+    AfterAttributeValueQuoted,
+    SelfClosingStartTag,
+    BogusComment,
+    MarkupDeclarationOpen,
+    CommentStart,
+    CommentStartDash,
+    Comment,
+    CommentLessThanSign,
+    CommentLessThanSignBang,
+    CommentLessThanSignBangDash,
+    CommentLessThanSignBangDashDash,
+    CommentEndDash,
+    CommentEnd,
+    CommentEndBang,
+    Doctype,
+    BeforeDoctypeName,
+    DoctypeName,
+    AfterDoctypeName,
+    AfterDoctypePublicKeyword,
+    BeforeDoctypePublicIdentifier,
+    DoctypePublicIdentifierDoubleQuoted,
+    DoctypePublicIdentifierSingleQuoted,
+    AfterDoctypePublicIdentifier,
+    BetweenDoctypePublicAndSystemIdentifiers,
+    AfterDoctypeSystemKeyword,
+    BeforeDoctypeSystemIdentifier,
+    DoctypeSystemIdentifierDoubleQuoted,
+    DoctypeSystemIdentifierSingleQuoted,
+    AfterDoctypeSystemIdentifier,
+    BogusDoctype,
+    CdataSection,
+    CdataSectionBracket,
+    CdataSectionEnd,
+    CharacterReference,
+    NamedCharacterReference,
+    AmbiguousAmpersand,
+    NumericCharacterReference,
+    HexadecimalCharacterReferenceStart,
+    DecimalCharacterReferenceStart,
+    HexadecimalCharacterReference,
+    DecimalCharacterReference,
+    NumericCharacterReferenceEnd,
+}
+
+#[derive(Default)]
+struct TagBuilder {
+    name: String,
+    is_end: bool,
+    self_closing: bool,
+    attrs: Vec<AttrBuilder>,
+}
+
+struct AttrBuilder {
+    name: String,
+    value: String,
+    span: Span,
+    duplicate: bool,
+}
+
+///
+/// An open SVG element, as tracked for resolving names in the right namespace.
+///
+#[derive(Clone, Debug)]
+struct ForeignElement {
+    tag: SvgTag,
+    html_integration_point: bool,
+}
+
+///
+/// The state of a tokenizer between two tokens, to tokenize further input from.
+///
+#[derive(Clone, Debug)]
+pub(crate) struct Checkpoint {
+    state: State,
+    last_start_tag: Option<String>,
+    foreign: Vec<ForeignElement>,
+}
+
+impl Default for Checkpoint {
+    fn default() -> Self {
+        Self {
+            state: State::Data,
+            last_start_tag: None,
+            foreign: vec![],
+        }
+    }
+}
+
+///
+/// The HTML tokenizer, an iterator of [Token]s.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::tokenizer::*;
+///
+/// let mut tokenizer = Tokenizer::new("<p CLASS=a>x&lt;<x-y>&notit;<svg viewbox='0 0 1 1'>");
+/// let tokens: Vec<TokenKind> = tokenizer.by_ref().map(|token| token.kind).collect();
+///
+/// assert_eq!(tokens, vec![
+///     TokenKind::StartTag(StartTag {
+///         name: Resolved::Known(web::Tag::Html5(html5::HtmlTag::P)),
+///         attrs: vec![TagAttribute {
+///             name: Resolved::Known(web::Attr::Html5(html5::HtmlAttr::Class)),
+///             value: "a".to_string(),
+///             span: 3..10,
+///         }],
+///         self_closing: false,
+///     }),
+///     TokenKind::Text("x<".to_string()),
+///     TokenKind::StartTag(StartTag {
+///         name: Resolved::Unknown("x-y".to_string()),
+///         attrs: vec![],
+///         self_closing: false,
+///     }),
+///     TokenKind::Text("\u{ac}it;".to_string()),
+///     TokenKind::StartTag(StartTag {
+///         name: Resolved::Known(web::Tag::Svg(svg::SvgTag::Svg)),
+///         attrs: vec![TagAttribute {
+///             name: Resolved::Known(web::Attr::Svg(svg::SvgAttr::ViewBox)),
+///             value: "0 0 1 1".to_string(),
+///             span: 33..50,
+///         }],
+///         self_closing: false,
+///     }),
+/// ]);
+///
+/// assert_eq!(tokenizer.errors(), &[ParseError {
+///     offset: 21,
+///     code: ErrorCode::MissingSemicolonAfterCharacterReference,
+/// }]);
+/// ```
+///
+pub struct Tokenizer<'i> {
+    input: &'i str,
+    /// The offset of the next character
+    pos: usize,
+    /// The offset of the current character
+    char_start: usize,
+    current: Option<char>,
+    reconsume: bool,
+
+    state: State,
+    return_state: State,
+
+    text: String,
+    last_token_end: usize,
+    token_start: usize,
+
+    tag: TagBuilder,
+    comment: String,
+    doctype: Doctype,
+    temp: String,
+    last_start_tag: Option<String>,
+    char_ref_start: usize,
+    char_ref_code: u32,
+
+    foreign: Vec<ForeignElement>,
+
+    pending: VecDeque<Token>,
+    errors: Vec<ParseError>,
+    finished: bool,
+}
+
+impl<'i> Tokenizer<'i> {
+    pub fn new(input: &'i str) -> Self {
+        Self {
+            input,
+            pos: 0,
+            char_start: 0,
+            current: None,
+            reconsume: false,
+            state: State::Data,
+            return_state: State::Data,
+            text: String::new(),
+            last_token_end: 0,
+            token_start: 0,
+            tag: TagBuilder::default(),
+            comment: String::new(),
+            doctype: Doctype::default(),
+            temp: String::new(),
+            last_start_tag: None,
+            char_ref_start: 0,
+            char_ref_code: 0,
+            foreign: vec![],
+            pending: VecDeque::new(),
+            errors: vec![],
+            finished: false,
+        }
+    }
+
+    ///
+    /// A tokenizer continuing from `checkpoint`, as if `input` followed the
+    /// input it was taken from.
+    ///
+    pub(crate) fn resume(input: &'i str, checkpoint: Checkpoint) -> Self {
+        Self {
+            state: checkpoint.state,
+            last_start_tag: checkpoint.last_start_tag,
+            foreign: checkpoint.foreign,
+            ..Self::new(input)
+        }
+    }
+
+    ///
+    /// The state after the last token returned, if that token is complete
+    /// whatever input follows: it ended with `>`, not at the end of the input.
+    /// `None` after text, which may continue.
+    ///
+    pub(crate) fn checkpoint(&self) -> Option<Checkpoint> {
+        if !self.pending.is_empty() || self.finished || self.last_token_end != self.pos {
+            return None;
+        }
+        Some(Checkpoint {
+            state: self.state,
+            last_start_tag: self.last_start_tag.clone(),
+            foreign: self.foreign.clone(),
+        })
+    }
+
+    ///
+    /// The parse errors encountered so far.
+    ///
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    fn consume(&mut self) -> Option<char> {
+        if self.reconsume {
+            self.reconsume = false;
+            return self.current;
+        }
+
+        self.char_start = self.pos;
+        let c = self.input[self.pos..].chars().next();
+        self.current = match c {
+            None => None,
+            Some('\r') => {
+                // Newlines are normalized
+                self.pos += 1;
+                if self.input[self.pos..].starts_with('\n') {
+                    self.pos += 1;
+                }
+                Some('\n')
+            }
+            Some(c) => {
+                self.pos += c.len_utf8();
+                if is_noncharacter(c) {
+                    self.error(ErrorCode::NoncharacterInInputStream);
+                } else if is_control(c) && !is_ascii_whitespace(c) && c != '\0' {
+                    self.error(ErrorCode::ControlCharacterInInputStream);
+                }
+                Some(c)
+            }
+        };
+        self.current
+    }
+
+    fn reconsume_in(&mut self, state: State) {
+        self.reconsume = true;
+        self.state = state;
+    }
+
+    ///
+    /// Consume `prefix` if the input continues with it, ASCII case-insensitively.
+    ///
+    fn consume_if(&mut self, from: usize, prefix: &str, case_sensitive: bool) -> bool {
+        let matches = match self.input.get(from..from + prefix.len()) {
+            Some(next) if case_sensitive => next == prefix,
+            Some(next) => next.eq_ignore_ascii_case(prefix),
+            None => false,
+        };
+        if matches {
+            self.pos = from + prefix.len();
+            self.reconsume = false;
+        }
+        matches
+    }
+
+    fn error(&mut self, code: ErrorCode) {
+        self.error_at(self.char_start, code);
+    }
+
+    fn error_at(&mut self, offset: usize, code: ErrorCode) {
+        self.errors.push(ParseError { offset, code });
+    }
+
+    fn emit_char(&mut self, c: char) {
+        self.text.push(c);
+    }
+
+    fn emit_str(&mut self, s: &str) {
+        self.text.push_str(s);
+    }
+
+    fn flush_text(&mut self, end: usize) {
+        if !self.text.is_empty() {
+            let text = std::mem::take(&mut self.text);
+            self.pending.push_back(Token {
+                kind: TokenKind::Text(text),
+                span: self.last_token_end..end,
+            });
+        }
+    }
+
+    fn emit_token(&mut self, kind: TokenKind) {
+        let start = self.token_start;
+        self.flush_text(start);
+        self.pending.push_back(Token {
+            kind,
+            span: start..self.pos,
+        });
+        self.last_token_end = self.pos;
+    }
+
+    fn emit_eof(&mut self) {
+        self.flush_text(self.input.len());
+        self.finished = true;
+    }
+
+    fn emit_comment(&mut self) {
+        let comment = std::mem::take(&mut self.comment);
+        self.emit_token(TokenKind::Comment(comment));
+    }
+
+    fn emit_doctype(&mut self) {
+        let doctype = std::mem::take(&mut self.doctype);
+        self.emit_token(TokenKind::Doctype(doctype));
+    }
+
+    fn new_tag(&mut self, is_end: bool) {
+        self.tag = TagBuilder {
+            is_end,
+            ..TagBuilder::default()
+        };
+    }
+
+    fn new_attr(&mut self) {
+        self.tag.attrs.push(AttrBuilder {
+            name: String::new(),
+            value: String::new(),
+            span: self.char_start..self.char_start,
+            duplicate: false,
+        });
+    }
+
+    fn attr(&mut self) -> &mut AttrBuilder {
+        self.tag.attrs.last_mut().expect("no current attribute")
+    }
+
+    ///
+    /// Leave the attribute name state, checking for duplicates.
+    ///
+    fn finish_attr_name(&mut self) {
+        let end = self.char_start;
+        let (last, previous) = self
+            .tag
+            .attrs
+            .split_last_mut()
+            .expect("no current attribute");
+        last.span.end = end;
+        if previous.iter().any(|attr| attr.name == last.name) {
+            last.duplicate = true;
+            let start = last.span.start;
+            self.error_at(start, ErrorCode::DuplicateAttribute);
+        }
+    }
+
+    fn is_appropriate_end_tag(&self) -> bool {
+        self.last_start_tag.as_deref() == Some(self.tag.name.as_str())
+    }
+
+    fn start_char_ref(&mut self, return_state: State) {
+        self.return_state = return_state;
+        self.char_ref_start = self.char_start;
+        self.state = State::CharacterReference;
+    }
+
+    fn char_ref_in_attribute(&self) -> bool {
+        matches!(
+            self.return_state,
+            State::AttributeValueDoubleQuoted
+                | State::AttributeValueSingleQuoted
+                | State::AttributeValueUnquoted
+        )
+    }
+
+    fn flush_char_ref(&mut self) {
+        let temp = std::mem::take(&mut self.temp);
+        if self.char_ref_in_attribute() {
+            let end = self.pos;
+            let attr = self.attr();
+            attr.value.push_str(&temp);
+            attr.span.end = end;
+        } else {
+            self.emit_str(&temp);
+        }
+    }
+
+    fn emit_tag(&mut self) {
+        let tag = std::mem::take(&mut self.tag);
+
+        if tag.is_end {
+            if !tag.attrs.is_empty() {
+                self.error(ErrorCode::EndTagWithAttributes);
+            }
+            if tag.self_closing {
+                self.error(ErrorCode::EndTagWithTrailingSolidus);
+            }
+            let name = self.resolve_end_tag(&tag.name);
+            self.emit_token(TokenKind::EndTag(EndTag { name }));
+            return;
+        }
+
+        let in_html_content = !self.in_foreign_content()
+            || FOREIGN_BREAKOUT
+                .iter()
+                .any(|breakout| *breakout == tag.name);
+        let name = self.resolve_start_tag(&tag.name, tag.self_closing);
+
+        let attrs = tag
+            .attrs
+            .into_iter()
+            .filter(|attr| !attr.duplicate)
+            .map(|attr| {
+                let resolved = match &name {
+                    Resolved::Known(tag) => tag.attr_by_local_name(&attr.name).ok(),
+                    Resolved::Unknown(_) => None,
+                };
+                TagAttribute {
+                    name: match resolved {
+                        Some(resolved) => Resolved::Known(resolved),
+                        None => Resolved::Unknown(attr.name),
+                    },
+                    value: attr.value,
+                    span: attr.span,
+                }
+            })
+            .collect();
+
+        if let (true, Resolved::Known(known)) = (in_html_content, &name) {
+            match known.text_kind() {
+                TextKind::RawText if known == &web::Tag::Html5(HtmlTag::Script) => {
+                    self.state = State::ScriptData
+                }
+                TextKind::RawText => self.state = State::Rawtext,
+                TextKind::EscapableRawText => self.state = State::Rcdata,
+                TextKind::PlainText => self.state = State::Plaintext,
+                _ => {}
+            }
+        }
+
+        self.last_start_tag = Some(tag.name);
+        self.emit_token(TokenKind::StartTag(StartTag {
+            name,
+            attrs,
+            self_closing: tag.self_closing,
+        }));
+    }
+
+    ///
+    /// Whether the current SVG element is not an HTML integration point.
+    ///
+    fn in_foreign_content(&self) -> bool {
+        self.foreign
+            .last()
+            .map(|element| !element.html_integration_point)
+            .unwrap_or(false)
+    }
+
+    fn resolve_start_tag(&mut self, name: &str, self_closing: bool) -> Resolved<web::Tag> {
+        if self.in_foreign_content() && FOREIGN_BREAKOUT.contains(&name) {
+            while self.in_foreign_content() {
+                self.foreign.pop();
+            }
+        }
+
+        let lookup: Result<web::Tag, _> = if self.in_foreign_content() {
+            SVG_NS.tag_by_local_name(name)
+        } else {
+            HTML5_NS.tag_by_local_name(name)
+        };
+
+        match lookup {
+            Ok(tag) => {
+                if let (web::Tag::Svg(svg_tag), false) = (&tag, self_closing) {
+                    self.foreign.push(ForeignElement {
+                        tag: svg_tag.clone(),
+                        html_integration_point: matches!(
+                            svg_tag,
+                            SvgTag::ForeignObject | SvgTag::Desc | SvgTag::Title
+                        ),
+                    });
+                }
+                Resolved::Known(tag)
+            }
+            Err(_) => Resolved::Unknown(name.to_string()),
+        }
+    }
+
+    fn resolve_end_tag(&mut self, name: &str) -> Resolved<web::Tag> {
+        let open = self
+            .foreign
+            .iter()
+            .rposition(|element| element.tag.local_name().eq_ignore_ascii_case(name));
+
+        let lookup: Result<web::Tag, _> = match open {
+            Some(index) => {
+                self.foreign.truncate(index);
+                SVG_NS.tag_by_local_name(name)
+            }
+            None if self.in_foreign_content() => SVG_NS.tag_by_local_name(name),
+            None => HTML5_NS.tag_by_local_name(name),
+        };
+
+        match lookup {
+            Ok(tag) => Resolved::Known(tag),
+            Err(_) => Resolved::Unknown(name.to_string()),
+        }
+    }
+
+    ///
+    /// The end tag name states of RCDATA, RAWTEXT and script data.
+    ///
+    fn end_tag_name(&mut self, c: Option<char>, text_state: State) {
+        match c {
+            Some('\t') | Some('\n') | Some('\x0c') | Some(' ') if self.is_appropriate_end_tag() => {
+                self.state = State::BeforeAttributeName;
+            }
+            Some('/') if self.is_appropriate_end_tag() => {
+                self.state = State::SelfClosingStartTag;
+            }
+            Some('>') if self.is_appropriate_end_tag() => {
+                self.state = State::Data;
+                self.emit_tag();
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                self.tag.name.push(c.to_ascii_lowercase());
+                self.temp.push(c);
+            }
+            _ => {
+                self.emit_str("</");
+                let temp = std::mem::take(&mut self.temp);
+                self.emit_str(&temp);
+                self.reconsume_in(text_state);
+            }
+        }
+    }
+
+    fn step(&mut self) {
+        use State::*;
+
+        // The states which do not consume a character
+        match self.state {
+            MarkupDeclarationOpen => {
+                let from = if self.reconsume {
+                    self.char_start
+                } else {
+                    self.pos
+                };
+                if self.consume_if(from, "--", true) {
+                    self.comment.clear();
+                    self.state = CommentStart;
+                } else if self.consume_if(from, "doctype", false) {
+                    self.state = Doctype;
+                } else if self.consume_if(from, "[CDATA[", true) {
+                    if self.in_foreign_content() {
+                        self.state = CdataSection;
+                    } else {
+                        self.error_at(from, ErrorCode::CdataInHtmlContent);
+                        self.comment = "[CDATA[".to_string();
+                        self.state = BogusComment;
+                    }
+                } else {
+                    self.error_at(from, ErrorCode::IncorrectlyOpenedComment);
+                    self.comment.clear();
+                    self.state = BogusComment;
+                }
+                return;
+            }
+            NumericCharacterReferenceEnd => {
+                let (c, issue) = char_ref::resolve_numeric(self.char_ref_code);
+                let code = match issue {
+                    Some(NumericIssue::Null) => Some(ErrorCode::NullCharacterReference),
+                    Some(NumericIssue::OutsideUnicodeRange) => {
+                        Some(ErrorCode::CharacterReferenceOutsideUnicodeRange)
+                    }
+                    Some(NumericIssue::Surrogate) => Some(ErrorCode::SurrogateCharacterReference),
+                    Some(NumericIssue::Noncharacter) => {
+                        Some(ErrorCode::NoncharacterCharacterReference)
+                    }
+                    Some(NumericIssue::Control) => Some(ErrorCode::ControlCharacterReference),
+                    None => None,
+                };
+                if let Some(code) = code {
+                    self.error_at(self.char_ref_start, code);
+                }
+                self.temp = c.to_string();
+                self.flush_char_ref();
+                self.state = self.return_state;
+                return;
+            }
+            _ => {}
+        }
+
+        let c = self.consume();
+
+        match self.state {
+            Data => match c {
+                Some('&') => self.start_char_ref(Data),
+                Some('<') => {
+                    self.token_start = self.char_start;
+                    self.state = TagOpen;
+                }
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.emit_char('\0');
+                }
+                None => self.emit_eof(),
+                Some(c) => self.emit_char(c),
+            },
+            Rcdata => match c {
+                Some('&') => self.start_char_ref(Rcdata),
+                Some('<') => {
+                    self.token_start = self.char_start;
+                    self.state = RcdataLessThanSign;
+                }
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.emit_char('\u{fffd}');
+                }
+                None => self.emit_eof(),
+                Some(c) => self.emit_char(c),
+            },
+            Rawtext => match c {
+                Some('<') => {
+                    self.token_start = self.char_start;
+                    self.state = RawtextLessThanSign;
+                }
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.emit_char('\u{fffd}');
+                }
+                None => self.emit_eof(),
+                Some(c) => self.emit_char(c),
+            },
+            ScriptData => match c {
+                Some('<') => {
+                    self.token_start = self.char_start;
+                    self.state = ScriptDataLessThanSign;
+                }
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.emit_char('\u{fffd}');
+                }
+                None => self.emit_eof(),
+                Some(c) => self.emit_char(c),
+            },
+            Plaintext => match c {
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.emit_char('\u{fffd}');
+                }
+                None => self.emit_eof(),
+                Some(c) => self.emit_char(c),
+            },
+            TagOpen => match c {
+                Some('!') => self.state = MarkupDeclarationOpen,
+                Some('/') => self.state = EndTagOpen,
+                Some(c) if c.is_ascii_alphabetic() => {
+                    self.new_tag(false);
+                    self.reconsume_in(TagName);
+                }
+                Some('?') => {
+                    self.error(ErrorCode::UnexpectedQuestionMarkInsteadOfTagName);
+                    self.comment.clear();
+                    self.reconsume_in(BogusComment);
+                }
+                None => {
+                    self.error(ErrorCode::EofBeforeTagName);
+                    self.emit_char('<');
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    self.error(ErrorCode::InvalidFirstCharacterOfTagName);
+                    self.emit_char('<');
+                    self.reconsume_in(Data);
+                }
+            },
+            EndTagOpen => match c {
+                Some(c) if c.is_ascii_alphabetic() => {
+                    self.new_tag(true);
+                    self.reconsume_in(TagName);
+                }
+                Some('>') => {
+                    self.error(ErrorCode::MissingEndTagName);
+                    self.state = Data;
+                }
+                None => {
+                    self.error(ErrorCode::EofBeforeTagName);
+                    self.emit_str("</");
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    self.error(ErrorCode::InvalidFirstCharacterOfTagName);
+                    self.comment.clear();
+                    self.reconsume_in(BogusComment);
+                }
+            },
+            TagName => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {
+                    self.state = BeforeAttributeName
+                }
+                Some('/') => self.state = SelfClosingStartTag,
+                Some('>') => {
+                    self.state = Data;
+                    self.emit_tag();
+                }
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.tag.name.push('\u{fffd}');
+                }
+                None => {
+                    self.error(ErrorCode::EofInTag);
+                    self.emit_eof();
+                }
+                Some(c) => self.tag.name.push(c.to_ascii_lowercase()),
+            },
+            RcdataLessThanSign => match c {
+                Some('/') => {
+                    self.temp.clear();
+                    self.state = RcdataEndTagOpen;
+                }
+                _ => {
+                    self.emit_char('<');
+                    self.reconsume_in(Rcdata);
+                }
+            },
+            RcdataEndTagOpen => match c {
+                Some(c) if c.is_ascii_alphabetic() => {
+                    self.new_tag(true);
+                    self.reconsume_in(RcdataEndTagName);
+                }
+                _ => {
+                    self.emit_str("</");
+                    self.reconsume_in(Rcdata);
+                }
+            },
+            RcdataEndTagName => self.end_tag_name(c, Rcdata),
+            RawtextLessThanSign => match c {
+                Some('/') => {
+                    self.temp.clear();
+                    self.state = RawtextEndTagOpen;
+                }
+                _ => {
+                    self.emit_char('<');
+                    self.reconsume_in(Rawtext);
+                }
+            },
+            RawtextEndTagOpen => match c {
+                Some(c) if c.is_ascii_alphabetic() => {
+                    self.new_tag(true);
+                    self.reconsume_in(RawtextEndTagName);
+                }
+                _ => {
+                    self.emit_str("</");
+                    self.reconsume_in(Rawtext);
+                }
+            },
+            RawtextEndTagName => self.end_tag_name(c, Rawtext),
+            ScriptDataLessThanSign => match c {
+                Some('/') => {
+                    self.temp.clear();
+                    self.state = ScriptDataEndTagOpen;
+                }
+                Some('!') => {
+                    self.state = ScriptDataEscapeStart;
+                    self.emit_str("<!");
+                }
+                _ => {
+                    self.emit_char('<');
+                    self.reconsume_in(ScriptData);
+                }
+            },
+            ScriptDataEndTagOpen => match c {
+                Some(c) if c.is_ascii_alphabetic() => {
+                    self.new_tag(true);
+                    self.reconsume_in(ScriptDataEndTagName);
+                }
+                _ => {
+                    self.emit_str("</");
+                    self.reconsume_in(ScriptData);
+                }
+            },
+            ScriptDataEndTagName => self.end_tag_name(c, ScriptData),
+            ScriptDataEscapeStart => match c {
+                Some('-') => {
+                    self.state = ScriptDataEscapeStartDash;
+                    self.emit_char('-');
+                }
+                _ => self.reconsume_in(ScriptData),
+            },
+            ScriptDataEscapeStartDash => match c {
+                Some('-') => {
+                    self.state = ScriptDataEscapedDashDash;
+                    self.emit_char('-');
+                }
+                _ => self.reconsume_in(ScriptData),
+            },
+            ScriptDataEscaped => match c {
+                Some('-') => {
+                    self.state = ScriptDataEscapedDash;
+                    self.emit_char('-');
+                }
+                Some('<') => {
+                    self.token_start = self.char_start;
+                    self.state = ScriptDataEscapedLessThanSign;
+                }
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.emit_char('\u{fffd}');
+                }
+                None => {
+                    self.error(ErrorCode::EofInScriptHtmlCommentLikeText);
+                    self.emit_eof();
+                }
+                Some(c) => self.emit_char(c),
+            },
+            ScriptDataEscapedDash | ScriptDataEscapedDashDash => match c {
+                Some('-') => {
+                    self.state = ScriptDataEscapedDashDash;
+                    self.emit_char('-');
+                }
+                Some('<') => {
+                    self.token_start = self.char_start;
+                    self.state = ScriptDataEscapedLessThanSign;
+                }
+                Some('>') if self.state == ScriptDataEscapedDashDash => {
+                    self.state = ScriptData;
+                    self.emit_char('>');
+                }
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.state = ScriptDataEscaped;
+                    self.emit_char('\u{fffd}');
+                }
+                None => {
+                    self.error(ErrorCode::EofInScriptHtmlCommentLikeText);
+                    self.emit_eof();
+                }
+                Some(c) => {
+                    self.state = ScriptDataEscaped;
+                    self.emit_char(c);
+                }
+            },
+            ScriptDataEscapedLessThanSign => match c {
+                Some('/') => {
+                    self.temp.clear();
+                    self.state = ScriptDataEscapedEndTagOpen;
+                }
+                Some(c) if c.is_ascii_alphabetic() => {
+                    self.temp.clear();
+                    self.emit_char('<');
+                    self.reconsume_in(ScriptDataDoubleEscapeStart);
+                }
+                _ => {
+                    self.emit_char('<');
+                    self.reconsume_in(ScriptDataEscaped);
+                }
+            },
+            ScriptDataEscapedEndTagOpen => match c {
+                Some(c) if c.is_ascii_alphabetic() => {
+                    self.new_tag(true);
+                    self.reconsume_in(ScriptDataEscapedEndTagName);
+                }
+                _ => {
+                    self.emit_str("</");
+                    self.reconsume_in(ScriptDataEscaped);
+                }
+            },
+            ScriptDataEscapedEndTagName => self.end_tag_name(c, ScriptDataEscaped),
+            ScriptDataDoubleEscapeStart | ScriptDataDoubleEscapeEnd => {
+                let (matched, otherwise) = if self.state == ScriptDataDoubleEscapeStart {
+                    (ScriptDataDoubleEscaped, ScriptDataEscaped)
+                } else {
+                    (ScriptDataEscaped, ScriptDataDoubleEscaped)
+                };
+                match c {
+                    Some(c @ '\t') | Some(c @ '\n') | Some(c @ '\x0c') | Some(c @ ' ')
+                    | Some(c @ '/') | Some(c @ '>') => {
+                        self.state = if self.temp == "script" {
+                            matched
+                        } else {
+                            otherwise
+                        };
+                        self.emit_char(c);
+                    }
+                    Some(c) if c.is_ascii_alphabetic() => {
+                        self.temp.push(c.to_ascii_lowercase());
+                        self.emit_char(c);
+                    }
+                    _ => self.reconsume_in(otherwise),
+                }
+            }
+            ScriptDataDoubleEscaped
+            | ScriptDataDoubleEscapedDash
+            | ScriptDataDoubleEscapedDashDash => match c {
+                Some('-') => {
+                    self.state = match self.state {
+                        ScriptDataDoubleEscaped => ScriptDataDoubleEscapedDash,
+                        _ => ScriptDataDoubleEscapedDashDash,
+                    };
+                    self.emit_char('-');
+                }
+                Some('<') => {
+                    self.state = ScriptDataDoubleEscapedLessThanSign;
+                    self.emit_char('<');
+                }
+                Some('>') if self.state == ScriptDataDoubleEscapedDashDash => {
+                    self.state = ScriptData;
+                    self.emit_char('>');
+                }
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.state = ScriptDataDoubleEscaped;
+                    self.emit_char('\u{fffd}');
+                }
+                None => {
+                    self.error(ErrorCode::EofInScriptHtmlCommentLikeText);
+                    self.emit_eof();
+                }
+                Some(c) => {
+                    self.state = ScriptDataDoubleEscaped;
+                    self.emit_char(c);
+                }
+            },
+            ScriptDataDoubleEscapedLessThanSign => match c {
+                Some('/') => {
+                    self.temp.clear();
+                    self.state = ScriptDataDoubleEscapeEnd;
+                    self.emit_char('/');
+                }
+                _ => self.reconsume_in(ScriptDataDoubleEscaped),
+            },
+            BeforeAttributeName => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {}
+                Some('/') | Some('>') | None => self.reconsume_in(AfterAttributeName),
+                Some('=') => {
+                    self.error(ErrorCode::UnexpectedEqualsSignBeforeAttributeName);
+                    self.new_attr();
+                    self.attr().name.push('=');
+                    self.state = AttributeName;
+                }
+                Some(_) => {
+                    self.new_attr();
+                    self.reconsume_in(AttributeName);
+                }
+            },
+            AttributeName => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') | Some('/') | Some('>')
+                | None => {
+                    self.finish_attr_name();
+                    self.reconsume_in(AfterAttributeName);
+                }
+                Some('=') => {
+                    self.finish_attr_name();
+                    self.state = BeforeAttributeValue;
+                }
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.attr().name.push('\u{fffd}');
+                }
+                Some(c) => {
+                    if c == '"' || c == '\'' || c == '<' {
+                        self.error(ErrorCode::UnexpectedCharacterInAttributeName);
+                    }
+                    self.attr().name.push(c.to_ascii_lowercase());
+                }
+            },
+            AfterAttributeName => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {}
+                Some('/') => self.state = SelfClosingStartTag,
+                Some('=') => self.state = BeforeAttributeValue,
+                Some('>') => {
+                    self.state = Data;
+                    self.emit_tag();
+                }
+                None => {
+                    self.error(ErrorCode::EofInTag);
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    self.new_attr();
+                    self.reconsume_in(AttributeName);
+                }
+            },
+            BeforeAttributeValue => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {}
+                Some('"') => self.state = AttributeValueDoubleQuoted,
+                Some('\'') => self.state = AttributeValueSingleQuoted,
+                Some('>') => {
+                    self.error(ErrorCode::MissingAttributeValue);
+                    self.state = Data;
+                    self.emit_tag();
+                }
+                _ => self.reconsume_in(AttributeValueUnquoted),
+            },
+            AttributeValueDoubleQuoted | AttributeValueSingleQuoted => {
+                let quote = if self.state == AttributeValueDoubleQuoted {
+                    '"'
+                } else {
+                    '\''
+                };
+                match c {
+                    Some(c) if c == quote => {
+                        let end = self.pos;
+                        self.attr().span.end = end;
+                        self.state = AfterAttributeValueQuoted;
+                    }
+                    Some('&') => self.start_char_ref(self.state),
+                    Some('\0') => {
+                        self.error(ErrorCode::UnexpectedNullCharacter);
+                        self.attr().value.push('\u{fffd}');
+                    }
+                    None => {
+                        self.error(ErrorCode::EofInTag);
+                        self.emit_eof();
+                    }
+                    Some(c) => self.attr().value.push(c),
+                }
+            }
+            AttributeValueUnquoted => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {
+                    self.state = BeforeAttributeName
+                }
+                Some('&') => self.start_char_ref(AttributeValueUnquoted),
+                Some('>') => {
+                    self.state = Data;
+                    self.emit_tag();
+                }
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.attr().value.push('\u{fffd}');
+                }
+                None => {
+                    self.error(ErrorCode::EofInTag);
+                    self.emit_eof();
+                }
+                Some(c) => {
+                    if matches!(c, '"' | '\'' | '<' | '=' | '`') {
+                        self.error(ErrorCode::UnexpectedCharacterInUnquotedAttributeValue);
+                    }
+                    let end = self.pos;
+                    let attr = self.attr();
+                    attr.value.push(c);
+                    attr.span.end = end;
+                }
+            },
+            AfterAttributeValueQuoted => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {
+                    self.state = BeforeAttributeName
+                }
+                Some('/') => self.state = SelfClosingStartTag,
+                Some('>') => {
+                    self.state = Data;
+                    self.emit_tag();
+                }
+                None => {
+                    self.error(ErrorCode::EofInTag);
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    self.error(ErrorCode::MissingWhitespaceBetweenAttributes);
+                    self.reconsume_in(BeforeAttributeName);
+                }
+            },
+            SelfClosingStartTag => match c {
+                Some('>') => {
+                    self.tag.self_closing = true;
+                    self.state = Data;
+                    self.emit_tag();
+                }
+                None => {
+                    self.error(ErrorCode::EofInTag);
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    self.error(ErrorCode::UnexpectedSolidusInTag);
+                    self.reconsume_in(BeforeAttributeName);
+                }
+            },
+            BogusComment => match c {
+                Some('>') => {
+                    self.state = Data;
+                    self.emit_comment();
+                }
+                None => {
+                    self.emit_comment();
+                    self.emit_eof();
+                }
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.comment.push('\u{fffd}');
+                }
+                Some(c) => self.comment.push(c),
+            },
+            CommentStart => match c {
+                Some('-') => self.state = CommentStartDash,
+                Some('>') => {
+                    self.error(ErrorCode::AbruptClosingOfEmptyComment);
+                    self.state = Data;
+                    self.emit_comment();
+                }
+                _ => self.reconsume_in(Comment),
+            },
+            CommentStartDash => match c {
+                Some('-') => self.state = CommentEnd,
+                Some('>') => {
+                    self.error(ErrorCode::AbruptClosingOfEmptyComment);
+                    self.state = Data;
+                    self.emit_comment();
+                }
+                None => {
+                    self.error(ErrorCode::EofInComment);
+                    self.emit_comment();
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    self.comment.push('-');
+                    self.reconsume_in(Comment);
+                }
+            },
+            Comment => match c {
+                Some('<') => {
+                    self.comment.push('<');
+                    self.state = CommentLessThanSign;
+                }
+                Some('-') => self.state = CommentEndDash,
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.comment.push('\u{fffd}');
+                }
+                None => {
+                    self.error(ErrorCode::EofInComment);
+                    self.emit_comment();
+                    self.emit_eof();
+                }
+                Some(c) => self.comment.push(c),
+            },
+            CommentLessThanSign => match c {
+                Some('!') => {
+                    self.comment.push('!');
+                    self.state = CommentLessThanSignBang;
+                }
+                Some('<') => self.comment.push('<'),
+                _ => self.reconsume_in(Comment),
+            },
+            CommentLessThanSignBang => match c {
+                Some('-') => self.state = CommentLessThanSignBangDash,
+                _ => self.reconsume_in(Comment),
+            },
+            CommentLessThanSignBangDash => match c {
+                Some('-') => self.state = CommentLessThanSignBangDashDash,
+                _ => self.reconsume_in(CommentEndDash),
+            },
+            CommentLessThanSignBangDashDash => match c {
+                Some('>') | None => self.reconsume_in(CommentEnd),
+                Some(_) => {
+                    self.error(ErrorCode::NestedComment);
+                    self.reconsume_in(CommentEnd);
+                }
+            },
+            CommentEndDash => match c {
+                Some('-') => self.state = CommentEnd,
+                None => {
+                    self.error(ErrorCode::EofInComment);
+                    self.emit_comment();
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    self.comment.push('-');
+                    self.reconsume_in(Comment);
+                }
+            },
+            CommentEnd => match c {
+                Some('>') => {
+                    self.state = Data;
+                    self.emit_comment();
+                }
+                Some('!') => self.state = CommentEndBang,
+                Some('-') => self.comment.push('-'),
+                None => {
+                    self.error(ErrorCode::EofInComment);
+                    self.emit_comment();
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    self.comment.push_str("--");
+                    self.reconsume_in(Comment);
+                }
+            },
+            CommentEndBang => match c {
+                Some('-') => {
+                    self.comment.push_str("--!");
+                    self.state = CommentEndDash;
+                }
+                Some('>') => {
+                    self.error(ErrorCode::IncorrectlyClosedComment);
+                    self.state = Data;
+                    self.emit_comment();
+                }
+                None => {
+                    self.error(ErrorCode::EofInComment);
+                    self.emit_comment();
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    self.comment.push_str("--!");
+                    self.reconsume_in(Comment);
+                }
+            },
+            Doctype => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {
+                    self.state = BeforeDoctypeName
+                }
+                Some('>') => self.reconsume_in(BeforeDoctypeName),
+                None => {
+                    self.error(ErrorCode::EofInDoctype);
+                    self.doctype.force_quirks = true;
+                    self.emit_doctype();
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    self.error(ErrorCode::MissingWhitespaceBeforeDoctypeName);
+                    self.reconsume_in(BeforeDoctypeName);
+                }
+            },
+            BeforeDoctypeName => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {}
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.doctype.name = Some('\u{fffd}'.to_string());
+                    self.state = DoctypeName;
+                }
+                Some('>') => {
+                    self.error(ErrorCode::MissingDoctypeName);
+                    self.doctype.force_quirks = true;
+                    self.state = Data;
+                    self.emit_doctype();
+                }
+                None => {
+                    self.error(ErrorCode::EofInDoctype);
+                    self.doctype.force_quirks = true;
+                    self.emit_doctype();
+                    self.emit_eof();
+                }
+                Some(c) => {
+                    self.doctype.name = Some(c.to_ascii_lowercase().to_string());
+                    self.state = DoctypeName;
+                }
+            },
+            DoctypeName => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => self.state = AfterDoctypeName,
+                Some('>') => {
+                    self.state = Data;
+                    self.emit_doctype();
+                }
+                Some('\0') => {
+                    self.error(ErrorCode::UnexpectedNullCharacter);
+                    self.doctype_name().push('\u{fffd}');
+                }
+                None => {
+                    self.error(ErrorCode::EofInDoctype);
+                    self.doctype.force_quirks = true;
+                    self.emit_doctype();
+                    self.emit_eof();
+                }
+                Some(c) => self.doctype_name().push(c.to_ascii_lowercase()),
+            },
+            AfterDoctypeName => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {}
+                Some('>') => {
+                    self.state = Data;
+                    self.emit_doctype();
+                }
+                None => {
+                    self.error(ErrorCode::EofInDoctype);
+                    self.doctype.force_quirks = true;
+                    self.emit_doctype();
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    let from = self.char_start;
+                    if self.consume_if(from, "public", false) {
+                        self.state = AfterDoctypePublicKeyword;
+                    } else if self.consume_if(from, "system", false) {
+                        self.state = AfterDoctypeSystemKeyword;
+                    } else {
+                        self.error(ErrorCode::InvalidCharacterSequenceAfterDoctypeName);
+                        self.doctype.force_quirks = true;
+                        self.reconsume_in(BogusDoctype);
+                    }
+                }
+            },
+            AfterDoctypePublicKeyword | AfterDoctypeSystemKeyword => {
+                let public = self.state == AfterDoctypePublicKeyword;
+                match c {
+                    Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {
+                        self.state = if public {
+                            BeforeDoctypePublicIdentifier
+                        } else {
+                            BeforeDoctypeSystemIdentifier
+                        };
+                    }
+                    Some(quote @ '"') | Some(quote @ '\'') => {
+                        self.error(if public {
+                            ErrorCode::MissingWhitespaceAfterDoctypePublicKeyword
+                        } else {
+                            ErrorCode::MissingWhitespaceAfterDoctypeSystemKeyword
+                        });
+                        self.begin_doctype_identifier(public, quote);
+                    }
+                    _ => self.doctype_identifier_missing(c, public),
+                }
+            }
+            BeforeDoctypePublicIdentifier | BeforeDoctypeSystemIdentifier => {
+                let public = self.state == BeforeDoctypePublicIdentifier;
+                match c {
+                    Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {}
+                    Some(quote @ '"') | Some(quote @ '\'') => {
+                        self.begin_doctype_identifier(public, quote)
+                    }
+                    _ => self.doctype_identifier_missing(c, public),
+                }
+            }
+            DoctypePublicIdentifierDoubleQuoted
+            | DoctypePublicIdentifierSingleQuoted
+            | DoctypeSystemIdentifierDoubleQuoted
+            | DoctypeSystemIdentifierSingleQuoted => {
+                let (public, quote) = match self.state {
+                    DoctypePublicIdentifierDoubleQuoted => (true, '"'),
+                    DoctypePublicIdentifierSingleQuoted => (true, '\''),
+                    DoctypeSystemIdentifierDoubleQuoted => (false, '"'),
+                    _ => (false, '\''),
+                };
+                let identifier = if public {
+                    &mut self.doctype.public_id
+                } else {
+                    &mut self.doctype.system_id
+                };
+                match c {
+                    Some(c) if c == quote => {
+                        self.state = if public {
+                            AfterDoctypePublicIdentifier
+                        } else {
+                            AfterDoctypeSystemIdentifier
+                        };
+                    }
+                    Some('\0') => {
+                        identifier.get_or_insert_with(String::new).push('\u{fffd}');
+                        self.error(ErrorCode::UnexpectedNullCharacter);
+                    }
+                    Some('>') => {
+                        self.error(if public {
+                            ErrorCode::AbruptDoctypePublicIdentifier
+                        } else {
+                            ErrorCode::AbruptDoctypeSystemIdentifier
+                        });
+                        self.doctype.force_quirks = true;
+                        self.state = Data;
+                        self.emit_doctype();
+                    }
+                    None => {
+                        self.error(ErrorCode::EofInDoctype);
+                        self.doctype.force_quirks = true;
+                        self.emit_doctype();
+                        self.emit_eof();
+                    }
+                    Some(c) => identifier.get_or_insert_with(String::new).push(c),
+                }
+            }
+            AfterDoctypePublicIdentifier | BetweenDoctypePublicAndSystemIdentifiers => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {
+                    self.state = BetweenDoctypePublicAndSystemIdentifiers
+                }
+                Some('>') => {
+                    self.state = Data;
+                    self.emit_doctype();
+                }
+                Some(quote @ '"') | Some(quote @ '\'') => {
+                    if self.state == AfterDoctypePublicIdentifier {
+                        self.error(
+                            ErrorCode::MissingWhitespaceBetweenDoctypePublicAndSystemIdentifiers,
+                        );
+                    }
+                    self.begin_doctype_identifier(false, quote);
+                }
+                None => {
+                    self.error(ErrorCode::EofInDoctype);
+                    self.doctype.force_quirks = true;
+                    self.emit_doctype();
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    self.error(ErrorCode::MissingQuoteBeforeDoctypeSystemIdentifier);
+                    self.doctype.force_quirks = true;
+                    self.reconsume_in(BogusDoctype);
+                }
+            },
+            AfterDoctypeSystemIdentifier => match c {
+                Some('\t') | Some('\n') | Some('\x0c') | Some(' ') => {}
+                Some('>') => {
+                    self.state = Data;
+                    self.emit_doctype();
+                }
+                None => {
+                    self.error(ErrorCode::EofInDoctype);
+                    self.doctype.force_quirks = true;
+                    self.emit_doctype();
+                    self.emit_eof();
+                }
+                Some(_) => {
+                    self.error(ErrorCode::UnexpectedCharacterAfterDoctypeSystemIdentifier);
+                    self.reconsume_in(BogusDoctype);
+                }
+            },
+            BogusDoctype => match c {
+                Some('>') => {
+                    self.state = Data;
+                    self.emit_doctype();
+                }
+                Some('\0') => self.error(ErrorCode::UnexpectedNullCharacter),
+                None => {
+                    self.emit_doctype();
+                    self.emit_eof();
+                }
+                Some(_) => {}
+            },
+            CdataSection => match c {
+                Some(']') => self.state = CdataSectionBracket,
+                None => {
+                    self.error(ErrorCode::EofInCdata);
+                    self.emit_eof();
+                }
+                Some(c) => self.emit_char(c),
+            },
+            CdataSectionBracket => match c {
+                Some(']') => self.state = CdataSectionEnd,
+                _ => {
+                    self.emit_char(']');
+                    self.reconsume_in(CdataSection);
+                }
+            },
+            CdataSectionEnd => match c {
+                Some(']') => self.emit_char(']'),
+                Some('>') => self.state = Data,
+                _ => {
+                    self.emit_str("]]");
+                    self.reconsume_in(CdataSection);
+                }
+            },
+            CharacterReference => {
+                self.temp = "&".to_string();
+                match c {
+                    Some(c) if c.is_ascii_alphanumeric() => {
+                        self.reconsume_in(NamedCharacterReference)
+                    }
+                    Some('#') => {
+                        self.temp.push('#');
+                        self.state = NumericCharacterReference;
+                    }
+                    _ => {
+                        self.flush_char_ref();
+                        let return_state = self.return_state;
+                        self.reconsume_in(return_state);
+                    }
+                }
+            }
+            NamedCharacterReference => {
+                match char_ref::match_named(&self.input[self.char_start..]) {
+                    Some((len, characters)) => {
+                        let name = &self.input[self.char_start..self.char_start + len];
+                        self.pos = self.char_start + len;
+                        let next = self.input[self.pos..].chars().next();
+                        let return_state = self.return_state;
+
+                        if !name.ends_with(';')
+                            && self.char_ref_in_attribute()
+                            && next
+                                .map(|next| next == '=' || next.is_ascii_alphanumeric())
+                                .unwrap_or(false)
+                        {
+                            // Historical reasons: kept as is in attributes
+                            self.temp.push_str(name);
+                        } else {
+                            if !name.ends_with(';') {
+                                self.error_at(
+                                    self.char_ref_start,
+                                    ErrorCode::MissingSemicolonAfterCharacterReference,
+                                );
+                            }
+                            self.temp = characters.to_string();
+                        }
+                        self.flush_char_ref();
+                        self.state = return_state;
+                    }
+                    None => {
+                        self.flush_char_ref();
+                        self.reconsume_in(AmbiguousAmpersand);
+                    }
+                }
+            }
+            AmbiguousAmpersand => match c {
+                Some(c) if c.is_ascii_alphanumeric() => {
+                    self.temp = c.to_string();
+                    self.flush_char_ref();
+                }
+                Some(';') => {
+                    self.error_at(
+                        self.char_ref_start,
+                        ErrorCode::UnknownNamedCharacterReference,
+                    );
+                    let return_state = self.return_state;
+                    self.reconsume_in(return_state);
+                }
+                _ => {
+                    let return_state = self.return_state;
+                    self.reconsume_in(return_state);
+                }
+            },
+            NumericCharacterReference => {
+                self.char_ref_code = 0;
+                match c {
+                    Some(c @ 'x') | Some(c @ 'X') => {
+                        self.temp.push(c);
+                        self.state = HexadecimalCharacterReferenceStart;
+                    }
+                    _ => self.reconsume_in(DecimalCharacterReferenceStart),
+                }
+            }
+            HexadecimalCharacterReferenceStart | DecimalCharacterReferenceStart => {
+                let (radix, next) = if self.state == HexadecimalCharacterReferenceStart {
+                    (16, HexadecimalCharacterReference)
+                } else {
+                    (10, DecimalCharacterReference)
+                };
+                match c {
+                    Some(c) if c.is_digit(radix) => self.reconsume_in(next),
+                    _ => {
+                        self.error_at(
+                            self.char_ref_start,
+                            ErrorCode::AbsenceOfDigitsInNumericCharacterReference,
+                        );
+                        self.flush_char_ref();
+                        let return_state = self.return_state;
+                        self.reconsume_in(return_state);
+                    }
+                }
+            }
+            HexadecimalCharacterReference | DecimalCharacterReference => {
+                let radix = if self.state == HexadecimalCharacterReference {
+                    16
+                } else {
+                    10
+                };
+                match c {
+                    Some(c) if c.is_digit(radix) => {
+                        let digit = c.to_digit(radix).unwrap_or(0);
+                        // Anything larger is outside the Unicode range anyway
+                        self.char_ref_code = (self.char_ref_code * radix + digit).min(0x11_0000);
+                    }
+                    Some(';') => self.state = NumericCharacterReferenceEnd,
+                    _ => {
+                        self.error_at(
+                            self.char_ref_start,
+                            ErrorCode::MissingSemicolonAfterCharacterReference,
+                        );
+                        self.reconsume_in(NumericCharacterReferenceEnd);
+                    }
+                }
+            }
+            MarkupDeclarationOpen | NumericCharacterReferenceEnd => unreachable!(),
+        }
+    }
+
+    fn doctype_name(&mut self) -> &mut String {
+        self.doctype.name.get_or_insert_with(String::new)
+    }
+
+    fn begin_doctype_identifier(&mut self, public: bool, quote: char) {
+        use State::*;
+
+        self.state = match (public, quote) {
+            (true, '"') => {
+                self.doctype.public_id = Some(String::new());
+                DoctypePublicIdentifierDoubleQuoted
+            }
+            (true, _) => {
+                self.doctype.public_id = Some(String::new());
+                DoctypePublicIdentifierSingleQuoted
+            }
+            (false, '"') => {
+                self.doctype.system_id = Some(String::new());
+                DoctypeSystemIdentifierDoubleQuoted
+            }
+            (false, _) => {
+                self.doctype.system_id = Some(String::new());
+                DoctypeSystemIdentifierSingleQuoted
+            }
+        };
+    }
+
+    ///
+    /// A doctype keyword not followed by a quoted identifier.
+    ///
+    fn doctype_identifier_missing(&mut self, c: Option<char>, public: bool) {
+        match c {
+            Some('>') => {
+                self.error(if public {
+                    ErrorCode::MissingDoctypePublicIdentifier
+                } else {
+                    ErrorCode::MissingDoctypeSystemIdentifier
+                });
+                self.doctype.force_quirks = true;
+                self.state = State::Data;
+                self.emit_doctype();
+            }
+            None => {
+                self.error(ErrorCode::EofInDoctype);
+                self.doctype.force_quirks = true;
+                self.emit_doctype();
+                self.emit_eof();
+            }
+            Some(_) => {
+                self.error(if public {
+                    ErrorCode::MissingQuoteBeforeDoctypePublicIdentifier
+                } else {
+                    ErrorCode::MissingQuoteBeforeDoctypeSystemIdentifier
+                });
+                self.doctype.force_quirks = true;
+                self.reconsume_in(State::BogusDoctype);
+            }
+        }
+    }
+}
+
+impl<'i> Iterator for Tokenizer<'i> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        while self.pending.is_empty() && !self.finished {
+            self.step();
+        }
+        self.pending.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html5::HtmlAttr;
+    use crate::PropertyName;
+
+    fn tokenize(input: &str) -> (Vec<TokenKind>, Vec<(usize, &'static str)>) {
+        let mut tokenizer = Tokenizer::new(input);
+        let tokens = tokenizer.by_ref().map(|token| token.kind).collect();
+        let errors = tokenizer
+            .errors()
+            .iter()
+            .map(|error| (error.offset, error.code.code()))
+            .collect();
+        (tokens, errors)
+    }
+
+    fn text(text: &str) -> TokenKind {
+        TokenKind::Text(text.to_string())
+    }
+
+    fn start(tag: HtmlTag) -> TokenKind {
+        TokenKind::StartTag(StartTag {
+            name: Resolved::Known(web::Tag::Html5(tag)),
+            attrs: vec![],
+            self_closing: false,
+        })
+    }
+
+    fn end(tag: web::Tag) -> TokenKind {
+        TokenKind::EndTag(EndTag {
+            name: Resolved::Known(tag),
+        })
+    }
+
+    #[test]
+    fn raw_text_switching() {
+        let (tokens, errors) = tokenize(
+            "<script>a<b></c><!--<script></script>--></script><title>&lt;<i></title><style>&lt;</style>",
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                start(HtmlTag::Script),
+                text("a<b></c><!--<script></script>-->"),
+                end(web::Tag::Html5(HtmlTag::Script)),
+                start(HtmlTag::Title),
+                text("<<i>"),
+                end(web::Tag::Html5(HtmlTag::Title)),
+                start(HtmlTag::Style),
+                text("&lt;"),
+                end(web::Tag::Html5(HtmlTag::Style)),
+            ]
+        );
+        assert!(errors.is_empty());
+
+        let (tokens, _) = tokenize("<plaintext></plaintext>");
+        assert_eq!(
+            tokens,
+            vec![start(HtmlTag::Plaintext), text("</plaintext>")]
+        );
+    }
+
+    #[test]
+    fn character_references() {
+        let (tokens, errors) = tokenize("&amp&#x41;&#66&#0;&#x80;&bogus;&;&#;");
+        assert_eq!(tokens, vec![text("&AB\u{fffd}\u{20ac}&bogus;&;&#;")]);
+        assert_eq!(
+            errors,
+            vec![
+                (0, "missing-semicolon-after-character-reference"),
+                (10, "missing-semicolon-after-character-reference"),
+                (14, "null-character-reference"),
+                (18, "control-character-reference"),
+                (24, "unknown-named-character-reference"),
+                (33, "absence-of-digits-in-numeric-character-reference"),
+            ]
+        );
+
+        let (tokens, errors) = tokenize("<a href='?a=1&copy=2&amp=3&copy;'>");
+        match &tokens[0] {
+            TokenKind::StartTag(tag) => {
+                assert_eq!(tag.attrs[0].value, "?a=1&copy=2&amp=3\u{a9}");
+            }
+            _ => panic!(),
+        }
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn attributes() {
+        let input = "<div ID=a id=b data-x title = 't' bogus\"=x />";
+        let mut tokenizer = Tokenizer::new(input);
+        let token = tokenizer.next().unwrap();
+        assert_eq!(token.span, 0..input.len());
+
+        let tag = match token.kind {
+            TokenKind::StartTag(tag) => tag,
+            _ => panic!(),
+        };
+        assert!(tag.self_closing);
+        assert_eq!(
+            tag.attrs
+                .iter()
+                .map(|attr| (
+                    attr.name.local_name(),
+                    attr.value.as_str(),
+                    &input[attr.span.clone()]
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("id", "a", "ID=a"),
+                ("data-x", "", "data-x"),
+                ("title", "t", "title = 't'"),
+                ("bogus\"", "x", "bogus\"=x"),
+            ]
+        );
+        assert_eq!(
+            tag.attrs[0].name,
+            Resolved::Known(web::Attr::Html5(HtmlAttr::Id))
+        );
+        assert!(matches!(tag.attrs[3].name, Resolved::Unknown(_)));
+        assert_eq!(
+            tokenizer
+                .errors()
+                .iter()
+                .map(|error| (error.offset, error.code))
+                .collect::<Vec<_>>(),
+            vec![
+                (10, ErrorCode::DuplicateAttribute),
+                (39, ErrorCode::UnexpectedCharacterInAttributeName),
+            ]
+        );
+    }
+
+    #[test]
+    fn non_ascii_attribute_names() {
+        let names = |input: &str| -> Vec<Resolved<web::Attr>> {
+            match Tokenizer::new(input).next().unwrap().kind {
+                TokenKind::StartTag(tag) => tag.attrs.into_iter().map(|attr| attr.name).collect(),
+                _ => panic!(),
+            }
+        };
+
+        assert_eq!(
+            names("<div\tx\t😀€>"),
+            vec![
+                Resolved::Unknown("x".to_string()),
+                Resolved::Unknown("😀€".to_string())
+            ]
+        );
+        match &names("<p data-é=1>")[0] {
+            Resolved::Known(attr) => {
+                assert_eq!(attr.local_name(), "data-é");
+                assert_eq!(attr.property_name(), "dataÉ");
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn comments_and_doctypes() {
+        let (tokens, errors) = tokenize(
+            "<!DOCTYPE html PUBLIC \"-//W3C//DTD HTML 4.01//EN\"><!-- a -- b --!><!--><?x><!x></3>",
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                TokenKind::Doctype(Doctype {
+                    name: Some("html".to_string()),
+                    public_id: Some("-//W3C//DTD HTML 4.01//EN".to_string()),
+                    system_id: None,
+                    force_quirks: false,
+                }),
+                TokenKind::Comment(" a -- b ".to_string()),
+                TokenKind::Comment("".to_string()),
+                TokenKind::Comment("?x".to_string()),
+                TokenKind::Comment("x".to_string()),
+                TokenKind::Comment("3".to_string()),
+            ]
+        );
+        assert_eq!(
+            errors.iter().map(|(_, code)| *code).collect::<Vec<_>>(),
+            vec![
+                "incorrectly-closed-comment",
+                "abrupt-closing-of-empty-comment",
+                "unexpected-question-mark-instead-of-tag-name",
+                "incorrectly-opened-comment",
+                "invalid-first-character-of-tag-name",
+            ]
+        );
+
+        let (tokens, errors) = tokenize("<!doctype>");
+        assert_eq!(
+            tokens,
+            vec![TokenKind::Doctype(Doctype {
+                force_quirks: true,
+                ..Doctype::default()
+            })]
+        );
+        assert_eq!(errors, vec![(9, "missing-doctype-name")]);
+    }
+
+    #[test]
+    fn svg_and_cdata() {
+        let (tokens, errors) = tokenize(
+            "<svg><clippath/><foreignObject><p></foreignObject><![CDATA[<x>]]><p><![CDATA[y]]>",
+        );
+        let svg = |tag| Resolved::Known(web::Tag::Svg(tag));
+
+        let names: Vec<_> = tokens
+            .iter()
+            .map(|token| match token {
+                TokenKind::StartTag(tag) => Some(tag.name.clone()),
+                TokenKind::EndTag(tag) => Some(tag.name.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                Some(svg(SvgTag::Svg)),
+                Some(svg(SvgTag::ClipPath)),
+                Some(svg(SvgTag::ForeignObject)),
+                Some(Resolved::Known(web::Tag::Html5(HtmlTag::P))),
+                Some(svg(SvgTag::ForeignObject)),
+                None,
+                Some(Resolved::Known(web::Tag::Html5(HtmlTag::P))),
+                None,
+            ]
+        );
+        assert_eq!(tokens[5], text("<x>"));
+        assert_eq!(tokens[7], TokenKind::Comment("[CDATA[y]]".to_string()));
+        assert_eq!(errors, vec![(70, "cdata-in-html-content")]);
+    }
+
+    #[test]
+    fn spans_and_newlines() {
+        let input = "a\r\nb<br>\rc<!---->";
+        let tokens: Vec<Token> = Tokenizer::new(input).collect();
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|token| (&input[token.span.clone()], &token.kind))
+                .collect::<Vec<_>>(),
+            vec![
+                ("a\r\nb", &text("a\nb")),
+                ("<br>", &start(HtmlTag::Br)),
+                ("\rc", &text("\nc")),
+                ("<!---->", &TokenKind::Comment("".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn eof() {
+        assert_eq!(tokenize("<div id=a").1, vec![(9, "eof-in-tag")]);
+        assert_eq!(tokenize("<").0, vec![text("<")]);
+        assert_eq!(tokenize("</").0, vec![text("</")]);
+        assert_eq!(
+            tokenize("<!--x").0,
+            vec![TokenKind::Comment("x".to_string())]
+        );
+        assert_eq!(
+            tokenize("<script><!--<script>").1,
+            vec![(20, "eof-in-script-html-comment-like-text")]
+        );
+    }
+}
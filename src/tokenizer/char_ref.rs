@@ -0,0 +1,171 @@
+//!
+//! Character references.
+//!
+//! See: https://html.spec.whatwg.org/#character-references
+//!
+
+mod entities {
+    //! Named character references, generated from `src/defs/entity_defs.rs`
+    include!(concat!(env!("OUT_DIR"), "/codegen_entities.rs"));
+}
+
+///
+/// The longest named character reference that `input` starts with,
+/// as `(length of the name, characters)`.
+///
+/// Example:
+/// ```
+/// use web_ns::tokenizer::char_ref::match_named;
+///
+/// assert_eq!(match_named("amp;x"), Some((4, "&")));
+/// assert_eq!(match_named("notin;"), Some((6, "\u{2209}")));
+/// assert_eq!(match_named("notit;"), Some((3, "\u{ac}")));
+/// assert_eq!(match_named("bogus;"), None);
+/// ```
+///
+pub fn match_named(input: &str) -> Option<(usize, &'static str)> {
+    let alphanumeric = input
+        .bytes()
+        .take(entities::LONGEST_NAME)
+        .take_while(u8::is_ascii_alphanumeric)
+        .count();
+    let candidate_len = if input[alphanumeric..].starts_with(';') {
+        alphanumeric + 1
+    } else {
+        alphanumeric
+    };
+
+    (1..=candidate_len).rev().find_map(|len| {
+        entities::NAMED_CHARACTER_REFERENCES
+            .get(&input[..len])
+            .map(|characters| (len, *characters))
+    })
+}
+
+///
+/// The replacements of numeric character references to C1 controls,
+/// interpreting them as windows-1252.
+///
+const C1_REPLACEMENTS: &[(u32, char)] = &[
+    (0x80, '\u{20ac}'),
+    (0x82, '\u{201a}'),
+    (0x83, '\u{0192}'),
+    (0x84, '\u{201e}'),
+    (0x85, '\u{2026}'),
+    (0x86, '\u{2020}'),
+    (0x87, '\u{2021}'),
+    (0x88, '\u{02c6}'),
+    (0x89, '\u{2030}'),
+    (0x8a, '\u{0160}'),
+    (0x8b, '\u{2039}'),
+    (0x8c, '\u{0152}'),
+    (0x8e, '\u{017d}'),
+    (0x91, '\u{2018}'),
+    (0x92, '\u{2019}'),
+    (0x93, '\u{201c}'),
+    (0x94, '\u{201d}'),
+    (0x95, '\u{2022}'),
+    (0x96, '\u{2013}'),
+    (0x97, '\u{2014}'),
+    (0x98, '\u{02dc}'),
+    (0x99, '\u{2122}'),
+    (0x9a, '\u{0161}'),
+    (0x9b, '\u{203a}'),
+    (0x9c, '\u{0153}'),
+    (0x9e, '\u{017e}'),
+    (0x9f, '\u{0178}'),
+];
+
+///
+/// What is wrong with the code point of a numeric character reference, if anything.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum NumericIssue {
+    Null,
+    OutsideUnicodeRange,
+    Surrogate,
+    Noncharacter,
+    Control,
+}
+
+///
+/// The character of a numeric character reference, and the issue with it.
+///
+pub(crate) fn resolve_numeric(code: u32) -> (char, Option<NumericIssue>) {
+    match code {
+        0 => ('\u{fffd}', Some(NumericIssue::Null)),
+        0x11_0000..=u32::MAX => ('\u{fffd}', Some(NumericIssue::OutsideUnicodeRange)),
+        0xd800..=0xdfff => ('\u{fffd}', Some(NumericIssue::Surrogate)),
+        _ => {
+            let c = std::char::from_u32(code).unwrap_or('\u{fffd}');
+            if is_noncharacter(c) {
+                (c, Some(NumericIssue::Noncharacter))
+            } else if code == 0x0d || (is_control(c) && !is_ascii_whitespace(c)) {
+                let replacement = C1_REPLACEMENTS
+                    .iter()
+                    .find(|(c1, _)| *c1 == code)
+                    .map(|(_, replacement)| *replacement)
+                    .unwrap_or(c);
+                (replacement, Some(NumericIssue::Control))
+            } else {
+                (c, None)
+            }
+        }
+    }
+}
+
+pub(crate) fn is_noncharacter(c: char) -> bool {
+    let code = c as u32;
+    (0xfdd0..=0xfdef).contains(&code) || (code & 0xfffe) == 0xfffe
+}
+
+pub(crate) fn is_control(c: char) -> bool {
+    let code = c as u32;
+    code <= 0x1f || (0x7f..=0x9f).contains(&code)
+}
+
+pub(crate) fn is_ascii_whitespace(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\x0c' | '\r' | ' ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric() {
+        assert_eq!(resolve_numeric(0x41), ('A', None));
+        assert_eq!(resolve_numeric(0), ('\u{fffd}', Some(NumericIssue::Null)));
+        assert_eq!(
+            resolve_numeric(0x110000),
+            ('\u{fffd}', Some(NumericIssue::OutsideUnicodeRange))
+        );
+        assert_eq!(
+            resolve_numeric(0xd800),
+            ('\u{fffd}', Some(NumericIssue::Surrogate))
+        );
+        assert_eq!(
+            resolve_numeric(0x80),
+            ('\u{20ac}', Some(NumericIssue::Control))
+        );
+        assert_eq!(
+            resolve_numeric(0x81),
+            ('\u{81}', Some(NumericIssue::Control))
+        );
+        assert_eq!(
+            resolve_numeric(0x1fffe),
+            ('\u{1fffe}', Some(NumericIssue::Noncharacter))
+        );
+        assert_eq!(resolve_numeric(0x0a), ('\n', None));
+    }
+
+    #[test]
+    fn named() {
+        assert_eq!(match_named("lt"), Some((2, "<")));
+        assert_eq!(match_named("LT;"), Some((3, "<")));
+        assert_eq!(match_named("fjlig;"), Some((6, "fj")));
+        assert_eq!(match_named("ltx"), Some((2, "<")));
+        assert_eq!(match_named(""), None);
+        assert_eq!(match_named(";"), None);
+    }
+}
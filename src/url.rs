@@ -0,0 +1,404 @@
+//!
+//! Minimal URL handling for attribute values containing URLs.
+//!
+//! This is not a complete implementation of the URL standard, but resolves relative
+//! references like browsers do for the common cases.
+//!
+//! See: https://url.spec.whatwg.org/
+//!
+
+use crate::attr::{Attribute, AttributeValue};
+use crate::html5::HtmlAttr;
+use crate::web;
+
+const SPECIAL_SCHEMES: &[&str] = &["ftp", "file", "http", "https", "ws", "wss"];
+
+///
+/// The scheme of an absolute URL, e.g. `https` or `javascript`.
+///
+/// Leading whitespace and control characters are ignored, and tabs and newlines
+/// inside the scheme are removed, the same way browsers do.
+///
+/// Example:
+/// ```
+/// use web_ns::url::scheme;
+///
+/// assert_eq!(scheme("HTTPS://example.com").as_deref(), Some("https"));
+/// assert_eq!(scheme(" java\tscript:alert(1)").as_deref(), Some("javascript"));
+/// assert_eq!(scheme("/relative/path"), None);
+/// assert_eq!(scheme("c:/windows"), Some("c".to_string()));
+/// ```
+///
+pub fn scheme(input: &str) -> Option<String> {
+    let input = strip_input(input);
+    let colon = input.find(':')?;
+    let scheme = &input[..colon];
+
+    let mut chars = scheme.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => {}
+        _ => return None,
+    }
+    if chars.all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.') {
+        Some(scheme.to_ascii_lowercase())
+    } else {
+        None
+    }
+}
+
+///
+/// Whether the value of `attr` contains URLs, as flagged in the attribute definitions.
+///
+/// This includes attributes not reflected as `USVString`, e.g. `background`,
+/// `codebase` or `itemtype`.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::url::is_url_attr;
+///
+/// assert!(is_url_attr(&web::Attr::Html5(html5::HtmlAttr::Href)));
+/// assert!(is_url_attr(&web::Attr::Html5(html5::HtmlAttr::Background)));
+/// assert!(is_url_attr(&web::Attr::Svg(svg::SvgAttr::Href)));
+/// assert!(!is_url_attr(&web::Attr::Html5(html5::HtmlAttr::Title)));
+/// ```
+///
+pub fn is_url_attr(attr: &web::Attr) -> bool {
+    attr.attr_type().is_url()
+}
+
+///
+/// The URLs in the value of a URL attribute, e.g. `href`, `ping` or `srcset`.
+///
+/// Empty for attributes that do not contain URLs.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::url::attr_urls;
+///
+/// let img: web::Tag = html5::HTML5_NS.tag_by_local_name("img").unwrap();
+/// let srcset: web::Attr = img.attr_by_local_name("srcset").unwrap();
+/// let value = srcset.deserialize_attribute_value(Some("a.png 1x, b.png 2x")).unwrap();
+/// assert_eq!(attr_urls(&srcset, &value), vec!["a.png", "b.png"]);
+///
+/// let alt: web::Attr = img.attr_by_local_name("alt").unwrap();
+/// let value = alt.deserialize_attribute_value(Some("a.png")).unwrap();
+/// assert!(attr_urls(&alt, &value).is_empty());
+/// ```
+///
+pub fn attr_urls<'v>(attr: &web::Attr, value: &'v AttributeValue) -> Vec<&'v str> {
+    if !is_url_attr(attr) {
+        return vec![];
+    }
+
+    let strings: Vec<&str> = match value {
+        AttributeValue::String(string) => vec![string],
+        AttributeValue::Multi(strings) => strings.iter().map(String::as_str).collect(),
+        _ => vec![],
+    };
+
+    match attr {
+        // Image candidates are a URL followed by a descriptor
+        web::Attr::Html5(HtmlAttr::Srcset) | web::Attr::Html5(HtmlAttr::Imagesrcset) => strings
+            .into_iter()
+            .filter_map(|candidate| candidate.split_ascii_whitespace().next())
+            .collect(),
+        _ => strings,
+    }
+}
+
+///
+/// Resolve `input` relative to the absolute URL `base`.
+///
+/// Returns `None` if the result is not a valid URL.
+///
+/// Example:
+/// ```
+/// use web_ns::url::resolve;
+///
+/// let base = "https://example.com/a/b?q#f";
+///
+/// assert_eq!(resolve(base, "c").unwrap(), "https://example.com/a/c");
+/// assert_eq!(resolve(base, "../c d").unwrap(), "https://example.com/c%20d");
+/// assert_eq!(resolve(base, "//other.org").unwrap(), "https://other.org/");
+/// assert_eq!(resolve(base, "?x").unwrap(), "https://example.com/a/b?x");
+/// assert_eq!(resolve(base, "").unwrap(), "https://example.com/a/b?q");
+/// assert_eq!(resolve(base, "MAILTO:a@b").unwrap(), "mailto:a@b");
+/// assert_eq!(resolve("about:blank", "c"), None);
+/// ```
+///
+pub fn resolve(base: &str, input: &str) -> Option<String> {
+    let input = strip_input(input);
+
+    if let Some(scheme) = scheme(&input) {
+        let rest = &input[scheme.len() + 1..];
+        return Some(normalize(&scheme, rest));
+    }
+
+    let base = strip_input(base);
+    let base_scheme = scheme(&base)?;
+    let base = normalize(&base_scheme, &base[base_scheme.len() + 1..]);
+    let base_url = Parts::parse(&base_scheme, &base[base_scheme.len() + 1..]);
+
+    let input = if is_special(&base_scheme) {
+        input.replace('\\', "/")
+    } else {
+        input
+    };
+
+    if base_url.authority.is_none() && !base_url.path.starts_with('/') {
+        // An opaque URL like `mailto:` or `data:`, only fragments can be resolved.
+        return if input.starts_with('#') || input.is_empty() {
+            Some(
+                Parts {
+                    fragment: input.strip_prefix('#').map(str::to_string),
+                    ..base_url
+                }
+                .serialize(),
+            )
+        } else {
+            None
+        };
+    }
+
+    if input.starts_with("//") {
+        return Some(normalize(&base_scheme, &input));
+    }
+
+    let reference = Parts::parse(&base_scheme, &input);
+    let mut resolved = Parts {
+        fragment: reference.fragment,
+        ..base_url
+    };
+
+    if input.starts_with('/') {
+        resolved.path = remove_dot_segments(&reference.path);
+        resolved.query = reference.query;
+    } else if !reference.path.is_empty() {
+        let directory = match resolved.path.rfind('/') {
+            Some(slash) => &resolved.path[..slash + 1],
+            None => "/",
+        };
+        resolved.path = remove_dot_segments(&format!("{}{}", directory, reference.path));
+        resolved.query = reference.query;
+    } else if reference.query.is_some() {
+        resolved.query = reference.query;
+    }
+
+    Some(resolved.serialize())
+}
+
+struct Parts {
+    scheme: String,
+    authority: Option<String>,
+    path: String,
+    query: Option<String>,
+    fragment: Option<String>,
+}
+
+impl Parts {
+    ///
+    /// Split the part after `scheme:` into its components.
+    ///
+    fn parse(scheme: &str, rest: &str) -> Self {
+        let (rest, fragment) = match rest.find('#') {
+            Some(hash) => (&rest[..hash], Some(rest[hash + 1..].to_string())),
+            None => (rest, None),
+        };
+        let (rest, query) = match rest.find('?') {
+            Some(question) => (&rest[..question], Some(rest[question + 1..].to_string())),
+            None => (rest, None),
+        };
+        let (authority, path) = match rest.strip_prefix("//") {
+            Some(rest) => {
+                let end = rest.find('/').unwrap_or(rest.len());
+                (Some(rest[..end].to_string()), rest[end..].to_string())
+            }
+            None => (None, rest.to_string()),
+        };
+
+        Self {
+            scheme: scheme.to_string(),
+            authority,
+            path,
+            query,
+            fragment,
+        }
+    }
+
+    fn serialize(&self) -> String {
+        let mut output = format!("{}:", self.scheme);
+        if let Some(authority) = &self.authority {
+            output.push_str("//");
+            output.push_str(authority);
+        }
+        output.push_str(&percent_encode(&self.path, "\"<>`{} "));
+        if let Some(query) = &self.query {
+            output.push('?');
+            output.push_str(&percent_encode(query, "\"<>` "));
+        }
+        if let Some(fragment) = &self.fragment {
+            output.push('#');
+            output.push_str(&percent_encode(fragment, "\"<>` "));
+        }
+        output
+    }
+}
+
+///
+/// Normalize an absolute URL: lowercase the scheme and host and remove dot segments.
+///
+fn normalize(scheme: &str, rest: &str) -> String {
+    let rest = if is_special(scheme) {
+        rest.replace('\\', "/")
+    } else {
+        rest.to_string()
+    };
+    let mut parts = Parts::parse(scheme, &rest);
+
+    if let Some(authority) = &mut parts.authority {
+        *authority = authority.to_ascii_lowercase();
+        parts.path = remove_dot_segments(&parts.path);
+        if parts.path.is_empty() && is_special(scheme) {
+            parts.path.push('/');
+        }
+    } else if parts.path.starts_with('/') {
+        parts.path = remove_dot_segments(&parts.path);
+    }
+
+    parts.serialize()
+}
+
+///
+/// See: https://tools.ietf.org/html/rfc3986#section-5.2.4
+///
+fn remove_dot_segments(path: &str) -> String {
+    let mut segments: Vec<&str> = vec![];
+    let mut trailing_slash = false;
+
+    for segment in path.split('/').skip(1) {
+        trailing_slash = false;
+        match segment {
+            "." | "%2e" | "%2E" => trailing_slash = true,
+            ".." | ".%2e" | ".%2E" | "%2e." | "%2E." | "%2e%2e" | "%2E%2E" => {
+                segments.pop();
+                trailing_slash = true;
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    let mut output = String::with_capacity(path.len());
+    for segment in &segments {
+        output.push('/');
+        output.push_str(segment);
+    }
+    if trailing_slash || (output.is_empty() && path.starts_with('/')) {
+        output.push('/');
+    }
+    output
+}
+
+fn percent_encode(input: &str, set: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    for c in input.chars() {
+        if c.is_ascii_control() || set.contains(c) || !c.is_ascii() {
+            let mut buf = [0; 4];
+            for byte in c.encode_utf8(&mut buf).bytes() {
+                output.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+///
+/// Strip leading and trailing C0 controls and spaces, and remove tabs and newlines.
+///
+fn strip_input(input: &str) -> String {
+    input
+        .trim_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect()
+}
+
+fn is_special(scheme: &str) -> bool {
+    SPECIAL_SCHEMES.contains(&scheme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc3986_examples() {
+        let base = "http://a/b/c/d;p?q";
+        let cases = [
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y", "http://a/b/c/g?y"),
+            ("#s", "http://a/b/c/d;p?q#s"),
+            ("g#s", "http://a/b/c/g#s"),
+            (";x", "http://a/b/c/;x"),
+            (".", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../g", "http://a/b/g"),
+            ("../..", "http://a/"),
+            ("../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("g.", "http://a/b/c/g."),
+            ("./../g", "http://a/b/g"),
+            ("g;x=1/../y", "http://a/b/c/y"),
+        ];
+
+        for (input, expected) in cases.iter() {
+            assert_eq!(
+                resolve(base, input).as_deref(),
+                Some(*expected),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn browser_quirks() {
+        let base = "https://Example.com/dir/";
+
+        assert_eq!(
+            resolve(base, "\\\\other\\x").as_deref(),
+            Some("https://other/x")
+        );
+        assert_eq!(
+            resolve(base, " a\nb ").as_deref(),
+            Some("https://example.com/dir/ab")
+        );
+        assert_eq!(
+            resolve(base, "HTTP://HOST/../x").as_deref(),
+            Some("http://host/x")
+        );
+        assert_eq!(
+            resolve(base, "é").as_deref(),
+            Some("https://example.com/dir/%C3%A9")
+        );
+        assert_eq!(
+            resolve("data:text/plain,x", "#y").as_deref(),
+            Some("data:text/plain,x#y")
+        );
+    }
+
+    #[test]
+    fn schemes() {
+        assert_eq!(scheme("\u{1}JavaScript:x").as_deref(), Some("javascript"));
+        assert_eq!(scheme("1http://x"), None);
+        assert_eq!(scheme("a b:c"), None);
+        assert_eq!(scheme("no-colon"), None);
+    }
+}
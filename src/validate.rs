@@ -0,0 +1,370 @@
+//!
+//! Whole-element validation of HTML attributes.
+//!
+//! Values are checked one at a time when they are parsed. This module checks
+//! the attributes of an element together: required attributes, attributes that
+//! exclude each other or the element's content, `<input>` attributes that do not
+//! apply to its `type`, and boolean attributes written with a value.
+//!
+//! See: https://html.spec.whatwg.org/#the-input-element
+//!
+
+use crate::attr::{Attribute, AttributeValue};
+use crate::dom::{ElementNode, Node};
+use crate::element::Element;
+use crate::html5::reflect::INPUT_TYPES;
+use crate::html5::{HtmlAttr, HtmlTag};
+use crate::{web, LocalName};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// A required attribute is missing.
+    Missing,
+    /// The attribute has no effect together with, or must not be used with, another.
+    ConflictsWith(web::Attr),
+    /// The attribute excludes content, e.g. `src` on a `<script>` with inline code.
+    ConflictsWithContent,
+    /// An `<input>` attribute that does not apply to the (lowercased) `type`.
+    NotForInputType(String),
+    /// A boolean attribute with a value other than the empty string or its name,
+    /// e.g. `disabled="false"`, which still means disabled.
+    BooleanWithValue,
+}
+
+///
+/// A problem with an attribute of an element.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub tag: web::Tag,
+    pub attr: web::Attr,
+    pub kind: DiagnosticKind,
+}
+
+///
+/// Required attributes, as `(tag, attr, only if this attribute is present)`.
+///
+pub const REQUIRED: &[(HtmlTag, HtmlAttr, Option<HtmlAttr>)] = &[
+    (HtmlTag::Area, HtmlAttr::Alt, Some(HtmlAttr::Href)),
+    (HtmlTag::Bdo, HtmlAttr::Dir, None),
+    (HtmlTag::Img, HtmlAttr::Src, None),
+    (HtmlTag::Optgroup, HtmlAttr::Label, None),
+    (HtmlTag::Track, HtmlAttr::Src, None),
+];
+
+///
+/// Boolean attributes that have no effect together, as `(tag, attr, attr without effect)`.
+/// Disabled and read-only controls are barred from constraint validation, and
+/// disabled controls cannot be focused.
+///
+pub const CONFLICTS: &[(HtmlTag, HtmlAttr, HtmlAttr)] = &[
+    (HtmlTag::Input, HtmlAttr::Disabled, HtmlAttr::Autofocus),
+    (HtmlTag::Input, HtmlAttr::Disabled, HtmlAttr::Required),
+    (HtmlTag::Input, HtmlAttr::Readonly, HtmlAttr::Required),
+    (HtmlTag::Select, HtmlAttr::Disabled, HtmlAttr::Required),
+    (HtmlTag::Textarea, HtmlAttr::Disabled, HtmlAttr::Required),
+    (HtmlTag::Textarea, HtmlAttr::Readonly, HtmlAttr::Required),
+];
+
+const TEXT: &[&str] = &["text", "search", "url", "tel", "email", "password"];
+const DATE_AND_NUMBER: &[&str] = &["date", "month", "week", "time", "datetime-local", "number"];
+const SUBMIT: &[&str] = &["submit", "image"];
+
+///
+/// The `<input>` attributes that only apply to some types, and the types they apply to.
+///
+/// See: https://html.spec.whatwg.org/#input-type-attr-summary
+///
+pub fn input_types(attr: &HtmlAttr) -> Option<Vec<&'static str>> {
+    let types = |lists: &[&[&'static str]]| lists.concat();
+    match attr {
+        HtmlAttr::Accept | HtmlAttr::Capture => Some(vec!["file"]),
+        HtmlAttr::Alt | HtmlAttr::Src => Some(vec!["image"]),
+        HtmlAttr::Height | HtmlAttr::Width => Some(vec!["image"]),
+        HtmlAttr::Checked => Some(vec!["checkbox", "radio"]),
+        HtmlAttr::Dirname => Some(vec!["text", "search"]),
+        HtmlAttr::Formaction
+        | HtmlAttr::Formenctype
+        | HtmlAttr::Formmethod
+        | HtmlAttr::Formnovalidate
+        | HtmlAttr::Formtarget => Some(SUBMIT.to_vec()),
+        HtmlAttr::List => Some(types(&[
+            &["text", "search", "url", "tel", "email"],
+            DATE_AND_NUMBER,
+            &["range", "color"],
+        ])),
+        HtmlAttr::Max | HtmlAttr::Min | HtmlAttr::Step => {
+            Some(types(&[DATE_AND_NUMBER, &["range"]]))
+        }
+        HtmlAttr::Maxlength | HtmlAttr::Minlength | HtmlAttr::Pattern | HtmlAttr::Size => {
+            Some(TEXT.to_vec())
+        }
+        HtmlAttr::Multiple => Some(vec!["email", "file"]),
+        HtmlAttr::Placeholder => Some(types(&[TEXT, &["number"]])),
+        HtmlAttr::Readonly => Some(types(&[TEXT, DATE_AND_NUMBER])),
+        HtmlAttr::Required => Some(types(&[
+            TEXT,
+            DATE_AND_NUMBER,
+            &["checkbox", "radio", "file"],
+        ])),
+        _ => None,
+    }
+}
+
+///
+/// Validate a single element. `content` is its text content, if known.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::element::Element;
+/// use web_ns::validate::{validate_element, Diagnostic, DiagnosticKind};
+///
+/// let input = Element::new(web::Tag::Html5(html5::HtmlTag::Input))
+///     .with_attr_str("type", Some("Checkbox"))?
+///     .with_attr_str("maxlength", Some("3"))?
+///     .with_attr_str("disabled", Some("false"))?;
+///
+/// assert_eq!(
+///     validate_element(&input, None),
+///     vec![
+///         Diagnostic {
+///             tag: input.tag().clone(),
+///             attr: web::Attr::Html5(html5::HtmlAttr::Maxlength),
+///             kind: DiagnosticKind::NotForInputType("checkbox".to_string()),
+///         },
+///         Diagnostic {
+///             tag: input.tag().clone(),
+///             attr: web::Attr::Html5(html5::HtmlAttr::Disabled),
+///             kind: DiagnosticKind::BooleanWithValue,
+///         },
+///     ]
+/// );
+/// # Ok::<(), Error>(())
+/// ```
+///
+pub fn validate_element(element: &Element, content: Option<&str>) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let html_tag = match element.tag() {
+        web::Tag::Html5(html_tag) => html_tag,
+        web::Tag::Svg(_) => return diagnostics,
+    };
+    let has = |attr: &HtmlAttr| element.has_attr(&web::Attr::Html5(attr.clone()));
+    let mut report = |attr: &HtmlAttr, kind: DiagnosticKind| {
+        diagnostics.push(Diagnostic {
+            tag: element.tag().clone(),
+            attr: web::Attr::Html5(attr.clone()),
+            kind,
+        })
+    };
+
+    for (tag, attr, condition) in REQUIRED {
+        if tag == html_tag && !has(attr) && condition.as_ref().is_none_or(has) {
+            report(attr, DiagnosticKind::Missing);
+        }
+    }
+
+    for (tag, attr, without_effect) in CONFLICTS {
+        if tag == html_tag && has(attr) && has(without_effect) {
+            report(
+                without_effect,
+                DiagnosticKind::ConflictsWith(web::Attr::Html5(attr.clone())),
+            );
+        }
+    }
+
+    if html_tag == &HtmlTag::Script {
+        let is_module = element
+            .attr_str(&web::Attr::Html5(HtmlAttr::Type))
+            .is_some_and(|script_type| script_type.trim().eq_ignore_ascii_case("module"));
+        if is_module && has(&HtmlAttr::Nomodule) {
+            report(
+                &HtmlAttr::Nomodule,
+                DiagnosticKind::ConflictsWith(web::Attr::Html5(HtmlAttr::Type)),
+            );
+        }
+        if has(&HtmlAttr::Src) && content.is_some_and(|content| !is_documentation(content)) {
+            report(&HtmlAttr::Src, DiagnosticKind::ConflictsWithContent);
+        }
+    }
+
+    let input_type = if html_tag == &HtmlTag::Input {
+        // Missing and invalid values are the text state
+        let input_type = element
+            .attr_str(&web::Attr::Html5(HtmlAttr::Type))
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if INPUT_TYPES.contains(&input_type.as_str()) {
+            Some(input_type)
+        } else {
+            Some("text".to_string())
+        }
+    } else {
+        None
+    };
+
+    for (attr, value) in element.attrs().iter() {
+        let html_attr = match attr {
+            web::Attr::Html5(html_attr) => html_attr,
+            web::Attr::Svg(_) => continue,
+        };
+        if let Some(input_type) = &input_type {
+            if let Some(types) = input_types(html_attr) {
+                if !types.contains(&input_type.as_str()) {
+                    report(
+                        html_attr,
+                        DiagnosticKind::NotForInputType(input_type.clone()),
+                    );
+                }
+            }
+        }
+        if attr.attr_type().is_only_bool() {
+            let misused = match value {
+                AttributeValue::False | AttributeValue::Multi(_) => true,
+                AttributeValue::String(string) => {
+                    !string.is_empty() && !string.eq_ignore_ascii_case(attr.local_name())
+                }
+                AttributeValue::True => false,
+            };
+            if misused {
+                report(html_attr, DiagnosticKind::BooleanWithValue);
+            }
+        }
+    }
+
+    diagnostics
+}
+
+///
+/// Whether the content of an external script is only whitespace and comments.
+///
+/// See: https://html.spec.whatwg.org/#inline-documentation-for-external-scripts
+///
+fn is_documentation(content: &str) -> bool {
+    let mut rest = content.trim_start();
+    while let Some(comment) = rest.strip_prefix("<!--") {
+        match comment.find("-->") {
+            Some(end) => rest = comment[end + 3..].trim_start(),
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+///
+/// Validate a tree of nodes, in document order.
+///
+/// Example:
+/// ```
+/// use web_ns::*;
+/// use web_ns::dom::parse_fragment;
+/// use web_ns::validate::{validate, DiagnosticKind};
+///
+/// let nodes = parse_fragment("<script src=a.js>b()</script><area href=c>").nodes;
+/// assert_eq!(
+///     validate(&nodes)
+///         .into_iter()
+///         .map(|diagnostic| (diagnostic.attr.local_name().to_string(), diagnostic.kind))
+///         .collect::<Vec<_>>(),
+///     vec![
+///         ("src".to_string(), DiagnosticKind::ConflictsWithContent),
+///         ("alt".to_string(), DiagnosticKind::Missing),
+///     ]
+/// );
+/// ```
+///
+pub fn validate(nodes: &[Node]) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    validate_nodes(nodes, &mut diagnostics);
+    diagnostics
+}
+
+fn validate_nodes(nodes: &[Node], diagnostics: &mut Vec<Diagnostic>) {
+    for node in nodes {
+        if let Node::Element(ElementNode { element, children }) = node {
+            let content: String = children
+                .iter()
+                .filter_map(|node| match node {
+                    Node::Text(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            diagnostics.extend(validate_element(element, Some(&content)));
+            validate_nodes(children, diagnostics);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dom::parse_fragment;
+
+    fn kinds(input: &str) -> Vec<(String, DiagnosticKind)> {
+        validate(&parse_fragment(input).nodes)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.attr.local_name().to_string(), diagnostic.kind))
+            .collect()
+    }
+
+    fn kind(attr: &str, kind: DiagnosticKind) -> (String, DiagnosticKind) {
+        (attr.to_string(), kind)
+    }
+
+    #[test]
+    fn required() {
+        assert_eq!(
+            kinds("<img alt=a><area alt=b href=c><area><bdo>d</bdo><track>"),
+            vec![
+                kind("src", DiagnosticKind::Missing),
+                kind("dir", DiagnosticKind::Missing),
+                kind("src", DiagnosticKind::Missing),
+            ]
+        );
+    }
+
+    #[test]
+    fn conflicts() {
+        let conflicts_with = |attr| DiagnosticKind::ConflictsWith(web::Attr::Html5(attr));
+        assert_eq!(
+            kinds(
+                "<input disabled required autofocus><textarea readonly required></textarea>\
+                 <script type=module nomodule src=a.js> </script><script src=b.js><!--c--></script>"
+            ),
+            vec![
+                kind("autofocus", conflicts_with(HtmlAttr::Disabled)),
+                kind("required", conflicts_with(HtmlAttr::Disabled)),
+                kind("required", conflicts_with(HtmlAttr::Readonly)),
+                kind("nomodule", conflicts_with(HtmlAttr::Type)),
+            ]
+        );
+    }
+
+    #[test]
+    fn input_type_attrs() {
+        let not_for = |input_type: &str| DiagnosticKind::NotForInputType(input_type.to_string());
+        assert_eq!(
+            kinds(
+                "<input type=range min=0 max=10 step=2 readonly>\
+                 <input type=bogus placeholder=a checked multiple>\
+                 <input type=file accept=image/* multiple required>"
+            ),
+            vec![
+                kind("readonly", not_for("range")),
+                kind("checked", not_for("text")),
+                kind("multiple", not_for("text")),
+            ]
+        );
+    }
+
+    #[test]
+    fn boolean_values() {
+        assert_eq!(
+            kinds("<button disabled=false></button><button DISABLED=Disabled></button><button disabled=''></button><button disabled=yes></button><a download=report.pdf></a><a download></a>"),
+            vec![
+                kind("disabled", DiagnosticKind::BooleanWithValue),
+                kind("disabled", DiagnosticKind::BooleanWithValue),
+            ]
+        );
+    }
+}
@@ -62,6 +62,49 @@ impl super::AttrByProperty<Attr> for Tag {
     }
 }
 
+impl crate::tag::Tag for Tag {
+    fn tag_type(&self) -> crate::tag::tag_type::TagType {
+        match self {
+            Self::Html5(tag) => tag.tag_type(),
+            Self::Svg(tag) => tag.tag_type(),
+        }
+    }
+}
+
+impl super::InterfaceName for Tag {
+    fn interface_name(&self) -> &str {
+        match self {
+            Self::Html5(tag) => tag.interface_name(),
+            Self::Svg(tag) => tag.interface_name(),
+        }
+    }
+}
+
+impl super::TagName for Tag {
+    fn tag_name(&self) -> &str {
+        match self {
+            Self::Html5(tag) => tag.tag_name(),
+            Self::Svg(tag) => tag.tag_name(),
+        }
+    }
+}
+
+impl super::Conformance for Tag {
+    fn status(&self) -> crate::status::Status {
+        match self {
+            Self::Html5(tag) => tag.status(),
+            Self::Svg(tag) => tag.status(),
+        }
+    }
+
+    fn replacement_hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Html5(tag) => tag.replacement_hint(),
+            Self::Svg(tag) => tag.replacement_hint(),
+        }
+    }
+}
+
 impl super::IsVoid for Tag {
     fn is_void(&self) -> bool {
         match self {
@@ -107,6 +150,22 @@ impl crate::PropertyName for Attr {
     }
 }
 
+impl crate::Conformance for Attr {
+    fn status(&self) -> crate::status::Status {
+        match self {
+            Self::Html5(attr) => attr.status(),
+            Self::Svg(attr) => attr.status(),
+        }
+    }
+
+    fn replacement_hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Html5(attr) => attr.replacement_hint(),
+            Self::Svg(attr) => attr.replacement_hint(),
+        }
+    }
+}
+
 impl crate::attr::Attribute for Attr {
     fn attr_type(&self) -> crate::attr_type::AttrType {
         match self {
@@ -114,4 +173,11 @@ impl crate::attr::Attribute for Attr {
             Self::Svg(attr) => attr.attr_type(),
         }
     }
+
+    fn idl_type(&self) -> Option<crate::attr::idl_type::IdlType> {
+        match self {
+            Self::Html5(attr) => attr.idl_type(),
+            Self::Svg(attr) => attr.idl_type(),
+        }
+    }
 }